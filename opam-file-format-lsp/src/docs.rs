@@ -0,0 +1,156 @@
+//! Documentation of the fields and variables of opam files, shown on hover and completion.
+
+/// Fields of `opam` files, and of their `url` and `extra-source` sections as `url.field`.
+const FIELDS: [(&str, &str); 49] = [
+    ("opam-version", "The version of the file format, e.g. \"2.0\". Must come first."),
+    ("name", "The name of the package. Usually given by the directory or file name instead."),
+    ("version", "The version of the package. Usually given by the directory name instead."),
+    ("maintainer", "Contacts of the maintainers of the opam package, as `Name <email>` strings."),
+    ("authors", "The authors of the software."),
+    ("author", "Deprecated alias of `authors`."),
+    ("license", "The license of the software, as SPDX license expressions."),
+    ("homepage", "URLs of the home page of the project."),
+    ("doc", "URLs of the documentation of the project."),
+    ("bug-reports", "URLs of the bug tracker of the project."),
+    ("dev-repo", "The version control repository of the project, e.g. `git+https://...`."),
+    ("tags", "Keywords describing the package, shown by `opam search`."),
+    ("synopsis", "A one-line description of the package."),
+    ("description", "A longer description of the package, following the synopsis."),
+    ("build", "Commands building the package, run in its source directory. Each command is a list of arguments with optional filters."),
+    ("install", "Commands installing the package, run after `build`."),
+    ("remove", "Commands removing the package. Rarely needed, as opam tracks installed files."),
+    ("run-test", "Commands running the tests, when tests are enabled."),
+    ("depends", "The dependencies of the package: a formula of `\"name\" {constraint}` atoms combined with `&` and `|`. Constraints may use the `build`, `post`, `with-test`, `with-doc` and `with-dev-setup` flags."),
+    ("depopts", "Optional dependencies: packages the package uses when they are installed, and is rebuilt with."),
+    ("conflicts", "Packages that can't be installed along with this one."),
+    ("conflict-class", "Classes of mutually exclusive packages: at most one package of a class can be installed."),
+    ("depexts", "System packages the package needs, each list with a filter selecting the systems it applies to."),
+    ("messages", "Messages shown before installing the package, each with an optional filter."),
+    ("post-messages", "Messages shown after installing the package, each with an optional filter. `success` and `failure` tell how the installation went."),
+    ("available", "A filter telling whether the package can be installed, e.g. `os != \"win32\"`."),
+    ("flags", "Package flags, such as `conf`, `compiler`, `avoid-version` or `deprecated`."),
+    ("features", "Experimental: optional features of the package."),
+    ("setenv", "Environment updates made when the package is installed, as `VAR = \"value\"`."),
+    ("build-env", "Environment updates made while building, installing and removing the package."),
+    ("substs", "Files to generate by substituting the variables of their `.in` counterparts."),
+    ("patches", "Patches to apply to the sources before building, each with an optional filter."),
+    ("extra-files", "Files of the package directory to copy to the sources, with their checksums."),
+    ("pin-depends", "Packages to pin when this one is pinned, as `[\"name.version\" \"url\"]` pairs."),
+    ("libraries", "Deprecated: OCaml libraries the package installs."),
+    ("syntax", "Deprecated: OCaml syntax extensions the package installs."),
+    ("build-test", "Deprecated: commands building and running the tests. Use `run-test`."),
+    ("build-doc", "Deprecated: commands building the documentation. Use `{with-doc}` filters in `build`."),
+    ("ocaml-version", "Deprecated: constraint on the OCaml version. Use a dependency on `ocaml`."),
+    ("os", "Deprecated: constraint on the operating system. Use `available`."),
+    ("url", "Where to get the sources of the package, with their checksums."),
+    ("extra-source", "An additional source file to download, named after the section."),
+    ("x-", "Extension fields, free for tools to use."),
+    ("url.src", "The URL of the sources: an archive, or a version control repository such as `git+https://...`."),
+    ("url.checksum", "Checksums of the archive, as `\"algo=digest\"` strings where `algo` is `md5`, `sha256` or `sha512`."),
+    ("url.mirrors", "Other URLs the sources can be downloaded from."),
+    ("url.swhid", "The Software Heritage identifier of the sources, used as a fallback."),
+    ("url.archive", "Deprecated alias of `src`."),
+    ("url.git", "Deprecated: a git repository holding the sources."),
+];
+
+/// Variables usable in filters and `%{var}%` interpolations.
+pub const VARIABLES: [(&str, &str); 36] = [
+    (
+        "arch",
+        "The architecture of the machine, e.g. `x86_64`, `arm64`.",
+    ),
+    (
+        "os",
+        "The operating system, e.g. `linux`, `macos`, `win32`, `freebsd`.",
+    ),
+    (
+        "os-family",
+        "The family of the distribution, e.g. `debian`, `bsd`, `windows`.",
+    ),
+    (
+        "os-distribution",
+        "The distribution, e.g. `ubuntu`, `alpine`, `homebrew`.",
+    ),
+    (
+        "os-version",
+        "The version of the operating system or distribution.",
+    ),
+    ("opam-version", "The version of opam."),
+    ("jobs", "The number of parallel jobs to use for building."),
+    ("make", "The `make` command to use."),
+    ("root", "The opam root directory."),
+    ("switch", "The current switch."),
+    ("prefix", "The prefix of the current switch."),
+    ("lib", "The library directory of the switch."),
+    ("bin", "The binary directory of the switch."),
+    ("sbin", "The system binary directory of the switch."),
+    ("share", "The data directory of the switch."),
+    ("doc", "The documentation directory of the switch."),
+    ("etc", "The configuration directory of the switch."),
+    ("man", "The manual page directory of the switch."),
+    ("toplevel", "The directory of OCaml toplevel scripts."),
+    ("stublibs", "The directory of OCaml C stub libraries."),
+    ("user", "The current user."),
+    ("group", "The group of the current user."),
+    ("name", "The name of the package."),
+    ("version", "The version of the package."),
+    ("pinned", "Whether the package is pinned."),
+    ("installed", "Whether the package is installed."),
+    (
+        "enable",
+        "`enable` when the package is installed, `disable` otherwise.",
+    ),
+    (
+        "dev",
+        "Whether the package is a development version, as when pinned to a repository.",
+    ),
+    (
+        "build-id",
+        "A hash identifying the build of the package and its dependencies.",
+    ),
+    (
+        "build",
+        "Dependency flag: the dependency is only needed to build the package.",
+    ),
+    (
+        "post",
+        "Dependency flag: the dependency is installed after the package.",
+    ),
+    ("with-test", "Whether tests are enabled for the package."),
+    (
+        "with-doc",
+        "Whether documentation is built for the package.",
+    ),
+    (
+        "with-dev-setup",
+        "Whether the development setup of the package is requested (opam 2.2).",
+    ),
+    (
+        "success",
+        "In `post-messages`: whether the installation succeeded.",
+    ),
+    (
+        "failure",
+        "In `post-messages`: whether the installation failed.",
+    ),
+];
+
+/// Documentation of a field, named `section.field` within a section. `x-` fields share theirs.
+pub fn field(name: &str) -> Option<&'static str> {
+    let name = match name.strip_prefix("extra-source.") {
+        Some(field) => format!("url.{}", field),
+        None if name.starts_with("x-") => "x-".to_string(),
+        None => name.to_string(),
+    };
+    FIELDS
+        .iter()
+        .find(|(field, _)| *field == name)
+        .map(|(_, doc)| *doc)
+}
+
+pub fn variable(name: &str) -> Option<&'static str> {
+    VARIABLES
+        .iter()
+        .find(|(variable, _)| *variable == name)
+        .map(|(_, doc)| *doc)
+}
@@ -0,0 +1,355 @@
+//! The language features, each computed from the text of a document.
+use indexmap::IndexMap;
+use serde_json::{json, Value as JsonValue};
+
+use opam_file_format::formatter::format_str;
+use opam_file_format::incremental::Tree;
+use opam_file_format::lexer::Span;
+use opam_file_format::lint::{lint, Level};
+use opam_file_format::parser::{section_kind, Item, ItemSpan};
+use opam_file_format::schema::{Kind, Schema};
+
+use crate::docs;
+use crate::text::LineIndex;
+
+const SOURCE: &str = "opam-file-format";
+
+// LSP enumerations.
+const SEVERITY_ERROR: u32 = 1;
+const SEVERITY_WARNING: u32 = 2;
+const COMPLETION_FIELD: u32 = 5;
+const COMPLETION_VARIABLE: u32 = 6;
+const COMPLETION_MODULE: u32 = 9;
+const SYMBOL_NAMESPACE: u32 = 3;
+const SYMBOL_FIELD: u32 = 8;
+
+/// The syntax error of the document, or its lint findings.
+pub fn diagnostics(tree: &Tree) -> JsonValue {
+    let text = tree.text();
+    let index = LineIndex::new(text);
+    let diagnostics: Vec<JsonValue> = match tree.ast() {
+        Err(e) => {
+            let end = Span {
+                start: text.len(),
+                end: text.len(),
+            };
+            vec![json!({
+                "range": index.range(e.span().unwrap_or(end)),
+                "severity": SEVERITY_ERROR,
+                "source": SOURCE,
+                "message": e.message(),
+            })]
+        }
+        Ok((ast, spans)) => lint(&ast, &spans)
+            .iter()
+            .map(|lint| {
+                json!({
+                    "range": index.range(lint.span.unwrap_or(Span { start: 0, end: 0 })),
+                    "severity": match lint.level {
+                        Level::Error => SEVERITY_ERROR,
+                        Level::Warning => SEVERITY_WARNING,
+                    },
+                    "code": lint.code,
+                    "source": SOURCE,
+                    "message": lint.message,
+                })
+            })
+            .collect(),
+    };
+    json!(diagnostics)
+}
+
+/// The documentation of the field whose name is under the cursor, or of a variable.
+pub fn hover(tree: &Tree, position: &JsonValue) -> JsonValue {
+    let text = tree.text();
+    let index = LineIndex::new(text);
+    let offset = index.offset(position);
+    if let Ok((_, spans)) = tree.ast() {
+        if let Some((field, key)) = key_at(&spans.items, offset, "") {
+            let doc = match docs::field(&field) {
+                Some(doc) => doc,
+                None => return JsonValue::Null,
+            };
+            let kind = match kind(&field) {
+                Some(kind) => format!(": {}", kind.description()),
+                None => String::new(),
+            };
+            return json!({
+                "contents": {
+                    "kind": "markdown",
+                    "value": format!("`{}`{}\n\n{}", field, kind, doc),
+                },
+                "range": index.range(key),
+            });
+        }
+    }
+    match word_at(text, offset) {
+        Some(word) => match docs::variable(&text[word.start..word.end]) {
+            Some(doc) => json!({
+                "contents": {
+                    "kind": "markdown",
+                    "value": format!("`{}` variable\n\n{}", &text[word.start..word.end], doc),
+                },
+                "range": index.range(word),
+            }),
+            None => JsonValue::Null,
+        },
+        None => JsonValue::Null,
+    }
+}
+
+// The field, named `section.field` within sections, whose key contains `offset`.
+fn key_at(
+    items: &IndexMap<String, ItemSpan>,
+    offset: usize,
+    prefix: &str,
+) -> Option<(String, Span)> {
+    items.iter().find_map(|(key, item)| {
+        let name = format!("{}{}", prefix, section_kind(key));
+        if item.key.start <= offset && offset <= item.key.end {
+            Some((name, item.key))
+        } else if item.span.start <= offset && offset <= item.span.end {
+            key_at(&item.items, offset, &format!("{}.", name))
+        } else {
+            None
+        }
+    })
+}
+
+// The kind of a field of `opam` files, named `section.field` within sections.
+fn kind(field: &str) -> Option<Kind> {
+    let opam = Schema::opam();
+    match field.split_once('.') {
+        Some((section, field)) => match opam.get(section) {
+            Some(Kind::Section(schema)) => schema.get(field).cloned(),
+            _ => None,
+        },
+        None => opam.get(field).cloned(),
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+// The identifier around `offset`.
+fn word_at(text: &str, offset: usize) -> Option<Span> {
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_word(c))
+        .last()
+        .map_or(offset, |(i, _)| i);
+    let end = text[offset..]
+        .char_indices()
+        .find(|&(_, c)| !is_word(c))
+        .map_or(text.len(), |(i, _)| offset + i);
+    if start == end {
+        None
+    } else {
+        Some(Span { start, end })
+    }
+}
+
+// What encloses a position: sections, the options of a value, or lists and groups.
+#[derive(Debug, Clone, PartialEq)]
+enum Open {
+    Section(String),
+    Options,
+    Brackets,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Context {
+    /// An item can start here, in the top level or the given section.
+    Field(Option<String>),
+    Variable,
+    Other,
+}
+
+// Where a position is, from the text before it. The scan is lexical so that it works while the
+// document doesn't parse.
+fn context(before: &str) -> Context {
+    let mut open = vec![];
+    let mut string = None;
+    let mut line = 0;
+    let mut chars = before.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if string.is_some() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => string = None,
+                _ => (),
+            }
+            continue;
+        }
+        match c {
+            '"' => string = Some(i + 1),
+            '\n' => line = i + 1,
+            '#' => {
+                for (i, c) in chars.by_ref() {
+                    if c == '\n' {
+                        line = i + 1;
+                        break;
+                    }
+                }
+            }
+            '{' => open.push(match section_header(&before[line..i]) {
+                Some(kind) => Open::Section(kind.to_string()),
+                None => Open::Options,
+            }),
+            '[' | '(' => open.push(Open::Brackets),
+            '}' | ']' | ')' => {
+                open.pop();
+            }
+            _ => (),
+        }
+    }
+    if let Some(start) = string {
+        // Interpolations such as `%{jobs}%` take variables.
+        let content = &before[start..];
+        return match (content.rfind("%{"), content.rfind("}%")) {
+            (Some(i), Some(j)) if i > j => Context::Variable,
+            (Some(_), None) => Context::Variable,
+            _ => Context::Other,
+        };
+    }
+    let section = match open.last() {
+        None => None,
+        Some(Open::Section(kind)) => Some(kind.clone()),
+        Some(Open::Options) => return Context::Variable,
+        Some(Open::Brackets) => return Context::Other,
+    };
+    let current = before[line..].trim_start();
+    if current.chars().all(is_word) {
+        return Context::Field(section);
+    }
+    // Filter fields take variables after the colon.
+    match current.split_once(':') {
+        Some((field, _)) if section.is_none() => match kind(field.trim()) {
+            Some(Kind::Filter) => Context::Variable,
+            _ => Context::Other,
+        },
+        _ => Context::Other,
+    }
+}
+
+// The kind of the section a line opens, as in `url {` or `extra-source "file" {`.
+fn section_header(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let (kind, name) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let name = name.trim();
+    let named =
+        name.is_empty() || (name.len() >= 2 && name.starts_with('"') && name.ends_with('"'));
+    let ident = kind.starts_with(|c: char| c.is_ascii_alphabetic()) && kind.chars().all(is_word);
+    if ident && named {
+        Some(kind)
+    } else {
+        None
+    }
+}
+
+/// Field names where an item can start, variables in filters and interpolations.
+pub fn completion(text: &str, position: &JsonValue) -> JsonValue {
+    let index = LineIndex::new(text);
+    let offset = index.offset(position);
+    let items: Vec<JsonValue> = match context(&text[..offset]) {
+        Context::Field(section) => {
+            let schema = match section {
+                None => Schema::opam(),
+                Some(section) => match Schema::opam().get(&section) {
+                    Some(Kind::Section(schema)) => schema.clone(),
+                    _ => return json!([]),
+                },
+            };
+            let prefix = match &schema.name[..] {
+                "opam" => String::new(),
+                section => format!("{}.", section),
+            };
+            schema
+                .fields()
+                .map(|(field, kind)| {
+                    let (completion, insert) = match kind {
+                        Kind::Section(_) => (COMPLETION_MODULE, format!("{} {{", field)),
+                        _ => (COMPLETION_FIELD, format!("{}: ", field)),
+                    };
+                    json!({
+                        "label": field,
+                        "kind": completion,
+                        "detail": kind.description(),
+                        "documentation": docs::field(&format!("{}{}", prefix, field)).unwrap_or(""),
+                        "insertText": insert,
+                    })
+                })
+                .collect()
+        }
+        Context::Variable => docs::VARIABLES
+            .iter()
+            .map(|(variable, doc)| {
+                json!({
+                    "label": variable,
+                    "kind": COMPLETION_VARIABLE,
+                    "documentation": doc,
+                })
+            })
+            .collect(),
+        Context::Other => vec![],
+    };
+    json!(items)
+}
+
+/// The fields and sections of the document, with the items of sections nested. While the
+/// document doesn't parse, those around the error.
+pub fn symbols(tree: &Tree) -> JsonValue {
+    let text = tree.text();
+    let index = LineIndex::new(text);
+    let symbols: Vec<JsonValue> = tree
+        .nodes()
+        .iter()
+        .map(|node| symbol(text, &index, &node.key, &node.item, &node.span))
+        .collect();
+    json!(symbols)
+}
+
+fn symbol(text: &str, index: &LineIndex, key: &str, item: &Item, span: &ItemSpan) -> JsonValue {
+    let mut node = json!({
+        "name": key,
+        "range": index.range(span.span),
+        "selectionRange": index.range(span.key),
+    });
+    match item {
+        Item::Section { items, .. } => {
+            let children: Vec<JsonValue> = items
+                .iter()
+                .filter_map(|(key, item)| {
+                    Some(symbol(text, index, key, item, span.items.get(key)?))
+                })
+                .collect();
+            node["kind"] = json!(SYMBOL_NAMESPACE);
+            node["children"] = json!(children);
+        }
+        Item::Variable(_) => {
+            node["kind"] = json!(SYMBOL_FIELD);
+            if let Some(value) = &span.value {
+                let value = &text[value.span.start..value.span.end];
+                node["detail"] = json!(value.lines().next().unwrap_or(""));
+            }
+        }
+    }
+    node
+}
+
+/// An edit replacing the document with its formatted text. Documents that don't parse aren't
+/// formatted.
+pub fn formatting(text: &str) -> JsonValue {
+    match format_str(text) {
+        Ok(formatted) if formatted == text => json!([]),
+        Ok(formatted) => json!([{
+            "range": LineIndex::new(text).full_range(),
+            "newText": formatted,
+        }]),
+        Err(_) => JsonValue::Null,
+    }
+}
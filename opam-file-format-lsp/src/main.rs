@@ -0,0 +1,172 @@
+//! A Language Server Protocol server for opam files, speaking over stdio.
+//!
+//! Documents are synchronized incrementally and reparsed with [`Tree::edit`]. The server reports
+//! syntax errors and lint findings as diagnostics, documents fields and variables on hover,
+//! completes field names and filter variables, lists the fields and sections of a document as
+//! symbols, and formats documents.
+use std::collections::HashMap;
+use std::io;
+use std::process::exit;
+
+use opam_file_format::incremental::{TextEdit, Tree};
+use opam_file_format::lexer::Span;
+use serde_json::{json, Value as JsonValue};
+
+mod docs;
+mod features;
+mod rpc;
+mod text;
+
+use text::LineIndex;
+
+// JSON-RPC error codes.
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<String, Tree>,
+    shutdown: bool,
+}
+
+impl Server {
+    /// Handles a message, returning the response to a request and any notification to send.
+    fn handle(&mut self, message: &JsonValue) -> Vec<JsonValue> {
+        // Responses of the client have no method and need no answer.
+        let method = match message["method"].as_str() {
+            Some(method) => method,
+            None => return vec![],
+        };
+        let params = &message["params"];
+        let id = match message.get("id") {
+            Some(id) => id,
+            None => return self.notify(method, params),
+        };
+        if self.shutdown {
+            return vec![rpc::error_response(
+                id,
+                INVALID_REQUEST,
+                "the server is shut down",
+            )];
+        }
+        let document = || {
+            params["textDocument"]["uri"]
+                .as_str()
+                .and_then(|uri| self.documents.get(uri))
+        };
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": 2,
+                    "hoverProvider": true,
+                    "completionProvider": { "triggerCharacters": ["{"] },
+                    "documentSymbolProvider": true,
+                    "documentFormattingProvider": true,
+                },
+                "serverInfo": {
+                    "name": "opam-file-format-lsp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+            "shutdown" => {
+                self.shutdown = true;
+                JsonValue::Null
+            }
+            "textDocument/hover" => document().map_or(JsonValue::Null, |tree| {
+                features::hover(tree, &params["position"])
+            }),
+            "textDocument/completion" => document().map_or(JsonValue::Null, |tree| {
+                features::completion(tree.text(), &params["position"])
+            }),
+            "textDocument/documentSymbol" => document().map_or(JsonValue::Null, features::symbols),
+            "textDocument/formatting" => {
+                document().map_or(JsonValue::Null, |tree| features::formatting(tree.text()))
+            }
+            method => {
+                let message = format!("unsupported method {}", method);
+                return vec![rpc::error_response(id, METHOD_NOT_FOUND, &message)];
+            }
+        };
+        vec![rpc::response(id, result)]
+    }
+
+    fn notify(&mut self, method: &str, params: &JsonValue) -> Vec<JsonValue> {
+        let uri = match params["textDocument"]["uri"].as_str() {
+            Some(uri) => uri.to_string(),
+            None => return vec![],
+        };
+        let tree = match method {
+            "textDocument/didOpen" => match params["textDocument"]["text"].as_str() {
+                Some(text) => Tree::parse(text),
+                None => return vec![],
+            },
+            "textDocument/didChange" => match self.documents.remove(&uri) {
+                Some(tree) => params["contentChanges"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .fold(tree, apply),
+                None => return vec![],
+            },
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish(&uri, json!([]))];
+            }
+            _ => return vec![],
+        };
+        let diagnostics = features::diagnostics(&tree);
+        self.documents.insert(uri.clone(), tree);
+        vec![publish(&uri, diagnostics)]
+    }
+}
+
+// Applies a change, which replaces a range of the text, or the whole text when it has none.
+fn apply(tree: Tree, change: &JsonValue) -> Tree {
+    let text = change["text"].as_str().unwrap_or("");
+    let range = &change["range"];
+    if range.is_null() {
+        return Tree::parse(text);
+    }
+    let index = LineIndex::new(tree.text());
+    let (start, end) = (index.offset(&range["start"]), index.offset(&range["end"]));
+    tree.edit(&TextEdit {
+        range: Span {
+            start: start.min(end),
+            end,
+        },
+        text: text.to_string(),
+    })
+}
+
+fn publish(uri: &str, diagnostics: JsonValue) -> JsonValue {
+    rpc::notification(
+        "textDocument/publishDiagnostics",
+        json!({ "uri": uri, "diagnostics": diagnostics }),
+    )
+}
+
+fn main() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let (mut input, mut output) = (stdin.lock(), stdout.lock());
+    let mut server = Server::default();
+    loop {
+        let message = match rpc::read_message(&mut input) {
+            Ok(Some(message)) => message,
+            Ok(None) => exit(1),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                exit(1);
+            }
+        };
+        if message["method"] == "exit" {
+            exit(if server.shutdown { 0 } else { 1 });
+        }
+        for reply in server.handle(&message) {
+            if let Err(e) = rpc::write_message(&mut output, &reply) {
+                eprintln!("error: {}", e);
+                exit(1);
+            }
+        }
+    }
+}
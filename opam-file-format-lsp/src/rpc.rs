@@ -0,0 +1,59 @@
+//! JSON-RPC messages framed with `Content-Length` headers, as the Language Server Protocol sends
+//! them over stdio.
+use std::io;
+use std::io::{BufRead, Write};
+
+use serde_json::{json, Value as JsonValue};
+
+/// Reads the next message, `None` at the end of the input.
+pub fn read_message(input: &mut impl BufRead) -> io::Result<Option<JsonValue>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| invalid("missing Content-Length header"))?;
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| invalid(&e.to_string()))
+}
+
+pub fn write_message(output: &mut impl Write, message: &JsonValue) -> io::Result<()> {
+    let content = message.to_string();
+    write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    output.flush()
+}
+
+pub fn response(id: &JsonValue, result: JsonValue) -> JsonValue {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub fn error_response(id: &JsonValue, code: i64, message: &str) -> JsonValue {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+pub fn notification(method: &str, params: JsonValue) -> JsonValue {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,63 @@
+//! Conversions between byte offsets, which the parser uses, and LSP positions, which count UTF-16
+//! code units within lines.
+use serde_json::{json, Value as JsonValue};
+
+use opam_file_format::lexer::Span;
+
+pub struct LineIndex<'a> {
+    text: &'a str,
+    // Byte offsets of the start of each line.
+    lines: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let mut lines = vec![0];
+        lines.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        LineIndex { text, lines }
+    }
+
+    pub fn position(&self, offset: usize) -> JsonValue {
+        let offset = offset.min(self.text.len());
+        let line = self.lines.partition_point(|&start| start <= offset) - 1;
+        let character: usize = self.text[self.lines[line]..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+        json!({ "line": line, "character": character })
+    }
+
+    pub fn range(&self, span: Span) -> JsonValue {
+        json!({ "start": self.position(span.start), "end": self.position(span.end) })
+    }
+
+    /// The byte offset of an LSP position, clamped to its line.
+    pub fn offset(&self, position: &JsonValue) -> usize {
+        let line = position["line"].as_u64().unwrap_or(0) as usize;
+        let character = position["character"].as_u64().unwrap_or(0) as usize;
+        let start = match self.lines.get(line) {
+            Some(&start) => start,
+            None => return self.text.len(),
+        };
+        let end = self
+            .lines
+            .get(line + 1)
+            .map_or(self.text.len(), |&end| end - 1);
+        let mut units = 0;
+        for (i, c) in self.text[start..end].char_indices() {
+            if units >= character {
+                return start + i;
+            }
+            units += c.len_utf16();
+        }
+        end
+    }
+
+    /// The range of the whole text.
+    pub fn full_range(&self) -> JsonValue {
+        self.range(Span {
+            start: 0,
+            end: self.text.len(),
+        })
+    }
+}
@@ -0,0 +1,140 @@
+//! Drives the server binary through a scripted session over stdio, as an editor would.
+use std::io::{BufReader, Write};
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value as JsonValue};
+
+#[allow(dead_code)]
+#[path = "../src/rpc.rs"]
+mod rpc;
+
+const URI: &str = "file:///pkg/opam";
+
+// The positions of edits count UTF-16 code units: `é` is one unit and two bytes, `😀` two units
+// and four bytes.
+const TEXT: &str = "opam-version: \"2.0\"
+synopsis: \"Café 😀 tools\"
+depends: [\"dune\" {with-test}]
+";
+
+fn request(id: u64, method: &str, params: JsonValue) -> JsonValue {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn position(line: u64, character: u64) -> JsonValue {
+    json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+}
+
+// Sends the messages, then reads everything the server writes until it exits.
+fn run(messages: &[JsonValue]) -> (Vec<JsonValue>, Option<i32>) {
+    let mut server = Command::new(env!("CARGO_BIN_EXE_opam-file-format-lsp"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut input = server.stdin.take().unwrap();
+    for message in messages {
+        rpc::write_message(&mut input, message).unwrap();
+    }
+    input.flush().unwrap();
+    drop(input);
+    let mut output = BufReader::new(server.stdout.take().unwrap());
+    let mut replies = vec![];
+    while let Some(reply) = rpc::read_message(&mut output).unwrap() {
+        replies.push(reply);
+    }
+    (replies, server.wait().unwrap().code())
+}
+
+fn response(replies: &[JsonValue], id: u64) -> &JsonValue {
+    let reply = replies
+        .iter()
+        .find(|reply| reply["id"] == id)
+        .unwrap_or_else(|| panic!("no response to request {}", id));
+    assert!(reply.get("error").is_none(), "{}", reply);
+    &reply["result"]
+}
+
+#[test]
+fn session() {
+    let (replies, code) = run(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {
+                "textDocument": { "uri": URI, "languageId": "opam", "version": 1, "text": TEXT },
+            },
+        }),
+        request(2, "textDocument/hover", position(2, 1)),
+        request(3, "textDocument/hover", position(2, 20)),
+        request(4, "textDocument/completion", position(2, 18)),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{
+                    "range": {
+                        "start": { "line": 1, "character": 19 },
+                        "end": { "line": 1, "character": 24 },
+                    },
+                    "text": "kit",
+                }],
+            },
+        }),
+        request(
+            5,
+            "textDocument/documentSymbol",
+            json!({ "textDocument": { "uri": URI } }),
+        ),
+        // A response to a request of the server, which needs no answer.
+        json!({ "jsonrpc": "2.0", "id": 99, "result": null }),
+        request(6, "shutdown", JsonValue::Null),
+        json!({ "jsonrpc": "2.0", "method": "exit" }),
+    ]);
+    assert_eq!(code, Some(0));
+
+    let initialize = response(&replies, 1);
+    assert_eq!(initialize["capabilities"]["textDocumentSync"], 2);
+    assert_eq!(initialize["capabilities"]["hoverProvider"], true);
+
+    let diagnostics: Vec<&JsonValue> = replies
+        .iter()
+        .filter(|reply| reply["method"] == "textDocument/publishDiagnostics")
+        .collect();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics
+        .iter()
+        .all(|reply| reply["params"]["uri"] == URI));
+
+    let field = response(&replies, 2);
+    assert!(field["contents"]["value"]
+        .as_str()
+        .unwrap()
+        .starts_with("`depends`"));
+    assert_eq!(
+        field["range"],
+        json!({ "start": { "line": 2, "character": 0 }, "end": { "line": 2, "character": 7 } })
+    );
+    let variable = response(&replies, 3);
+    assert!(variable["contents"]["value"]
+        .as_str()
+        .unwrap()
+        .starts_with("`with-test` variable"));
+
+    let completion = response(&replies, 4).as_array().unwrap();
+    assert!(completion.iter().any(|item| item["label"] == "with-test"));
+
+    let symbols = response(&replies, 5).as_array().unwrap();
+    let names: Vec<&str> = symbols
+        .iter()
+        .map(|symbol| symbol["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["opam-version", "synopsis", "depends"]);
+    assert_eq!(symbols[1]["detail"], "\"Café 😀 kit\"");
+
+    assert!(replies.iter().all(|reply| reply["id"] != 99));
+    assert_eq!(response(&replies, 6), &JsonValue::Null);
+}
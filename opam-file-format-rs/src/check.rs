@@ -0,0 +1,131 @@
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+use serde_json::json;
+
+use opam_file_format::filter::Env;
+use opam_file_format::formula::Dependency;
+use opam_file_format::graph::Node;
+use opam_file_format::installability::Report;
+use opam_file_format::repository::Repository;
+use opam_file_format::solver::{Explanation, Solver};
+
+use crate::graph::load_packages;
+use crate::solve::{atoms, env};
+use crate::utils::{fatal, jobs};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("check")
+        .about("Lists the uninstallable package versions of an opam repository checkout")
+        .arg(Arg::with_name("ROOT")
+            .help("Root of the repository, containing the packages directory")
+            .required(true))
+        .arg(Arg::with_name("against")
+            .long("against")
+            .takes_value(true)
+            .value_name("OLD_ROOT")
+            .help("Only list the versions that were installable in this older checkout, or didn't exist there, \
+                   and those that became installable"))
+        .arg(Arg::with_name("with")
+            .long("with")
+            .takes_value(true)
+            .value_name("PACKAGE")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Check every version along with this package, e.g. `ocaml.5.1.0`"))
+        .arg(Arg::with_name("explain")
+            .long("explain")
+            .help("Explain why each listed version is uninstallable. Implied by --against"))
+        .arg(Arg::with_name("json")
+            .long("json")
+            .short("j")
+            .help("Print the results as JSON"))
+        .arg(Arg::with_name("var")
+            .long("var")
+            .takes_value(true)
+            .value_name("NAME=VALUE")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Sets a variable for filters, e.g. `os=linux` or `with-test=true`. `os` and `arch` default \
+                   to those of this machine"))
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .takes_value(true)
+            .value_name("N")
+            .help("Number of threads. Defaults to the number of CPUs"))
+}
+
+pub fn run(matches: &ArgMatches) {
+    let jobs = jobs(matches);
+    let with = atoms(matches.values_of("with").into_iter().flatten());
+    let env = env(matches);
+    let (solver, report) = check(matches.value_of("ROOT").unwrap(), &env, &with, jobs);
+    let explain = |node: &Node| -> Explanation {
+        let mut request = vec![node.dependency()];
+        request.extend_from_slice(&with);
+        solver
+            .solve(&request)
+            .expect_err("uninstallable package has a solution")
+    };
+
+    let (broken, fixed) = match matches.value_of("against") {
+        Some(old) => {
+            let (_, old) = check(old, &env, &with, jobs);
+            (report.broken_since(&old), report.fixed_since(&old))
+        }
+        None => (report.uninstallable().collect(), vec![]),
+    };
+    let explained = matches.is_present("explain") || matches.is_present("against");
+    if matches.is_present("json") {
+        let broken: Vec<_> = broken.iter().map(|&node| match explained {
+            true => json!({
+                "package": node.atom(),
+                "explanation": explain(node).facts.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            }),
+            false => json!(node.atom()),
+        }).collect();
+        let mut json = json!({ "checked": report.packages.len(), "uninstallable": broken });
+        if matches.is_present("against") {
+            json["installable"] = json!(fixed.iter().map(|node| node.atom()).collect::<Vec<_>>());
+        }
+        println!("{}", json);
+    } else {
+        for &node in &broken {
+            println!("{}", node.atom().red());
+            if explained {
+                for fact in &explain(node).facts {
+                    println!("  - {}", fact);
+                }
+            }
+        }
+        for node in &fixed {
+            println!("{}", node.atom().green());
+        }
+        let total = report.packages.len();
+        match matches.is_present("against") {
+            true => eprintln!(
+                "{} newly uninstallable, {} newly installable, out of {} package versions",
+                broken.len(),
+                fixed.len(),
+                total
+            ),
+            false => eprintln!(
+                "{} uninstallable out of {} package versions",
+                broken.len(),
+                total
+            ),
+        }
+    }
+    if !broken.is_empty() {
+        exit(1);
+    }
+}
+
+fn check(root: &str, env: &Env, with: &[Dependency], jobs: usize) -> (Solver, Report) {
+    let repository = Repository::open(root).unwrap_or_else(|e| fatal(e));
+    let packages = load_packages(&repository, jobs);
+    let solver = Solver::new(&packages, env).unwrap_or_else(|e| fatal(e));
+    let report = Report::check(&solver, with, jobs).unwrap_or_else(|e| fatal(e));
+    (solver, report)
+}
@@ -0,0 +1,50 @@
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+use serde_json::Value as JsonValue;
+
+use opam_file_format::diff::{diff, Change};
+
+use crate::utils::read_opam_file;
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("diff")
+        .about("Compares two opam files field by field, dependency by dependency and command by command")
+        .arg(Arg::with_name("OLD")
+            .help("opam file before the change")
+            .required(true))
+        .arg(Arg::with_name("NEW")
+            .help("opam file after the change")
+            .required(true))
+        .arg(Arg::with_name("json")
+            .long("json")
+            .short("j")
+            .help("Print the changes as a JSON array"))
+}
+
+/// Prints the changes, exiting with an error when there are some, like diff.
+pub fn run(matches: &ArgMatches) {
+    let (_, old) = read_opam_file(matches.value_of("OLD").unwrap());
+    let (_, new) = read_opam_file(matches.value_of("NEW").unwrap());
+    let changes = diff(&old, &new);
+    if matches.is_present("json") {
+        println!(
+            "{}",
+            JsonValue::Array(changes.iter().map(Change::to_json).collect())
+        );
+    } else {
+        for change in &changes {
+            let line = change.to_string();
+            let line = match line.chars().next() {
+                Some('+') => line.green(),
+                Some('-') => line.red(),
+                _ => line.yellow(),
+            };
+            println!("{}", line);
+        }
+    }
+    if !changes.is_empty() {
+        exit(1);
+    }
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+
+use opam_file_format::formatter::format_str;
+
+use crate::utils::{fatal, report_error};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("fmt")
+        .about("Formats opam files in a canonical layout, printing the result by default")
+        .arg(
+            Arg::with_name("FILES")
+                .help("opam files to format")
+                .required(true)
+                .multiple(true),
+        )
+        .arg(
+            Arg::with_name("check")
+                .long("check")
+                .conflicts_with("in-place")
+                .help("Only list the files that aren't formatted, exiting with an error if there are any"),
+        )
+        .arg(
+            Arg::with_name("in-place")
+                .long("in-place")
+                .short("i")
+                .help("Rewrite the files instead of printing them"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let check = matches.is_present("check");
+    let in_place = matches.is_present("in-place");
+    let mut failed = 0;
+    let mut unformatted = 0;
+    for filename in matches.values_of("FILES").unwrap() {
+        let source =
+            fs::read_to_string(filename).unwrap_or_else(|e| fatal(format!("{}: {}", filename, e)));
+        let formatted = match format_str(&source) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                failed += 1;
+                report_error(filename, &source, &e);
+                continue;
+            }
+        };
+        if check {
+            if formatted != source {
+                unformatted += 1;
+                println!("{}", format!("{} is not formatted", filename).red().bold());
+            }
+        } else if in_place {
+            if formatted != source {
+                fs::write(filename, formatted)
+                    .unwrap_or_else(|e| fatal(format!("{}: {}", filename, e)));
+            }
+        } else {
+            print!("{}", formatted);
+        }
+    }
+    if failed > 0 || unformatted > 0 {
+        exit(1);
+    }
+}
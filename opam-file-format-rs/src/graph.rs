@@ -0,0 +1,139 @@
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+
+use opam_file_format::bulk::{self, FileError};
+use opam_file_format::filter::{Env, FilterValue};
+use opam_file_format::graph::Graph;
+use opam_file_format::package::{split_atom, PackageName};
+use opam_file_format::repository::{PackageDir, Repository};
+use opam_file_format::{OpamAST, OpamVersion};
+
+use crate::utils::{fatal, jobs, report_error};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("graph")
+        .about("Prints the dependency graph of an opam repository checkout in Graphviz DOT syntax")
+        .arg(Arg::with_name("ROOT")
+            .help("Root of the repository, containing the packages directory")
+            .required(true))
+        .arg(Arg::with_name("json")
+            .long("json")
+            .short("j")
+            .help("Print the graph as JSON instead"))
+        .arg(Arg::with_name("var")
+            .long("var")
+            .takes_value(true)
+            .value_name("NAME=VALUE")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Sets a variable for filters, e.g. `os=linux` or `with-test=true`. Build and post \
+                   dependencies are included by default, test, doc and dev setup ones aren't"))
+        .arg(Arg::with_name("reverse")
+            .long("reverse")
+            .takes_value(true)
+            .value_name("PACKAGE")
+            .conflicts_with("cycles")
+            .help("Print the packages depending on NAME or NAME.VERSION, directly or not, instead"))
+        .arg(Arg::with_name("cycles")
+            .long("cycles")
+            .help("Print the dependency cycles instead, exiting with an error if there are any"))
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .takes_value(true)
+            .value_name("N")
+            .help("Number of parsing threads. Defaults to the number of CPUs"))
+}
+
+pub fn run(matches: &ArgMatches) {
+    let repository =
+        Repository::open(matches.value_of("ROOT").unwrap()).unwrap_or_else(|e| fatal(e));
+    let jobs = jobs(matches);
+    let packages = load_packages(&repository, jobs);
+    let graph = Graph::build(&packages, &env(matches)).unwrap_or_else(|e| fatal(e));
+
+    if let Some(package) = matches.value_of("reverse") {
+        let nodes = match split_atom(package) {
+            Some((name, version)) if graph.node(&name, &version).is_some() => {
+                vec![graph.node(&name, &version).unwrap()]
+            }
+            _ => graph.versions(&PackageName::new(package)).to_vec(),
+        };
+        if nodes.is_empty() {
+            fatal(format!("package {} not found", package));
+        }
+        let mut dependents: Vec<usize> = nodes
+            .iter()
+            .flat_map(|&node| graph.reverse_dependencies(node, true))
+            .collect();
+        dependents.sort_unstable();
+        dependents.dedup();
+        for node in dependents {
+            println!("{}", graph.nodes[node].atom());
+        }
+    } else if matches.is_present("cycles") {
+        let cycles = graph.cycles();
+        for cycle in &cycles {
+            let atoms: Vec<String> = cycle.iter().map(|&node| graph.nodes[node].atom()).collect();
+            println!("{}", atoms.join(" "));
+        }
+        if !cycles.is_empty() {
+            exit(1);
+        }
+    } else if matches.is_present("json") {
+        println!("{}", graph.to_json());
+    } else {
+        print!("{}", graph.to_dot());
+    }
+}
+
+/// The environment of the `--var` arguments, on top of the dependency flags of `opam install`.
+pub fn env(matches: &ArgMatches) -> Env {
+    let mut env = Env::install();
+    for var in matches.values_of("var").into_iter().flatten() {
+        let (name, value) = var
+            .find('=')
+            .map(|i| (&var[..i], &var[i + 1..]))
+            .unwrap_or_else(|| fatal(format!("expected NAME=VALUE, found {}", var)));
+        let value = match value {
+            "true" => FilterValue::Bool(true),
+            "false" => FilterValue::Bool(false),
+            value => FilterValue::from(value),
+        };
+        env.insert(name, value);
+    }
+    env
+}
+
+/// Parses the packages of a repository, reporting those that fail.
+pub fn load_packages(
+    repository: &Repository,
+    jobs: usize,
+) -> Vec<(PackageName, OpamVersion, OpamAST)> {
+    let dirs: Vec<PackageDir> = repository
+        .package_dirs()
+        .unwrap_or_else(|e| fatal(e))
+        .filter_map(|dir| {
+            dir.map_err(|e| eprintln!("{}", format!("{}: {}", "error".red(), e).bold()))
+                .ok()
+        })
+        .collect();
+    let paths: Vec<_> = dirs.iter().map(PackageDir::opam_file).collect();
+    let mut packages = vec![];
+    for (dir, parsed) in dirs
+        .into_iter()
+        .zip(bulk::parse_files(&paths, jobs).unwrap_or_else(|e| fatal(e)))
+    {
+        let filename = parsed.path.to_string_lossy();
+        match parsed.result {
+            Ok(ast) => packages.push((dir.name, dir.version, ast)),
+            Err(FileError::Syntax { source, error }) => report_error(&filename, &source, &error),
+            Err(e) => eprintln!(
+                "{}",
+                format!("{}: {}: {}", "error".red(), filename, e).bold()
+            ),
+        }
+    }
+    packages
+}
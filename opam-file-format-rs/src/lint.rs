@@ -0,0 +1,58 @@
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+
+use opam_file_format::lint::{lint, Level};
+
+use crate::utils::{pretty_diagnostic, read_opam_file_with_spans};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("lint")
+        .about("Checks package definitions for common mistakes, like `opam lint`")
+        .arg(
+            Arg::with_name("FILES")
+                .help("opam files to check")
+                .required(true)
+                .multiple(true),
+        )
+        .arg(
+            Arg::with_name("warn-error")
+                .long("warn-error")
+                .help("Exit with an error on warnings too"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let mut errors = 0;
+    let mut warnings = 0;
+    for filename in matches.values_of("FILES").unwrap() {
+        let (source, (ast, spans)) = read_opam_file_with_spans(filename);
+        for finding in lint(&ast, &spans) {
+            let label = match finding.level {
+                Level::Warning => {
+                    warnings += 1;
+                    format!("warning {}", finding.code).yellow()
+                }
+                Level::Error => {
+                    errors += 1;
+                    format!("error {}", finding.code).red()
+                }
+            };
+            match finding.span {
+                Some(span) => pretty_diagnostic(filename, &source, &span, label, &finding.message),
+                None => eprintln!(
+                    "{}",
+                    format!("{}: {}: {}", label, filename, finding.message).bold()
+                ),
+            }
+        }
+    }
+
+    let message = format!("{} errors, {} warnings", errors, warnings);
+    if errors > 0 || (warnings > 0 && matches.is_present("warn-error")) {
+        println!("{}", message.red().bold());
+        exit(1);
+    }
+    println!("{}", message.green().bold());
+}
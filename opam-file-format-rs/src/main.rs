@@ -3,19 +3,31 @@ use std::io::Read;
 use std::process::exit;
 use std::time::SystemTime;
 
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg};
 use colored::*;
 
-use opam_file_format::{JsonPrinter, lexer, parser};
+use opam_file_format::borrowed::{self, Arena};
+use opam_file_format::{bulk, JsonPrinter, lexer, parser};
 
+mod check;
+mod diff;
+mod fmt;
+mod graph;
+mod lint;
+mod merge;
+mod query;
+mod repository;
+mod solve;
 mod utils;
-use utils::pretty_error;
+mod verify_sources;
+use utils::{fatal, pretty_error, report_error};
 
 fn main() {
     let matches = App::new("opam-file-format-rs")
         .version("0.1.0")
         .author("LightQuantum <self@lightquantum.me>")
         .about("Parser for the opam file syntax written in rust")
+        .setting(AppSettings::SubcommandsNegateReqs)
         .arg(Arg::with_name("INPUT")
             .help("Sets the opam file to be parsed")
             .required(true))
@@ -26,11 +38,54 @@ fn main() {
             .long("json")
             .short("j")
             .help("serialize to json instead of internal ast"))
+        .arg(Arg::with_name("borrowed")
+            .long("borrowed")
+            .requires("benchmark")
+            .help("Use the zero-copy parser in benchmark mode"))
+        .arg(Arg::with_name("fields")
+            .long("fields")
+            .takes_value(true)
+            .value_name("FIELDS")
+            .use_delimiter(true)
+            .requires("benchmark")
+            .help("Only parse these top-level fields in benchmark mode, skipping over the others"))
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .takes_value(true)
+            .value_name("N")
+            .requires("benchmark")
+            .help("Number of parsing threads in benchmark mode. Defaults to the number of CPUs"))
+        .subcommand(check::subcommand())
+        .subcommand(diff::subcommand())
+        .subcommand(fmt::subcommand())
+        .subcommand(graph::subcommand())
+        .subcommand(lint::subcommand())
+        .subcommand(merge::subcommand())
+        .subcommand(query::subcommand())
+        .subcommand(repository::subcommand())
+        .subcommand(solve::subcommand())
+        .subcommand(verify_sources::subcommand())
         .get_matches();
+    match matches.subcommand() {
+        ("check", Some(matches)) => return check::run(matches),
+        ("diff", Some(matches)) => return diff::run(matches),
+        ("fmt", Some(matches)) => return fmt::run(matches),
+        ("graph", Some(matches)) => return graph::run(matches),
+        ("lint", Some(matches)) => return lint::run(matches),
+        ("merge", Some(matches)) => return merge::run(matches),
+        ("query", Some(matches)) => return query::run(matches),
+        ("repository", Some(matches)) => return repository::run(matches),
+        ("solve", Some(matches)) => return solve::run(matches),
+        ("verify-sources", Some(matches)) => return verify_sources::run(matches),
+        _ => (),
+    }
     if matches.is_present("benchmark") {
         benchmark(
             matches.value_of("INPUT").unwrap(),
             matches.is_present("json"),
+            matches.is_present("borrowed"),
+            matches.values_of("fields").map(|fields| fields.collect()),
+            utils::jobs(&matches),
         )
     } else {
         single_file(
@@ -40,53 +95,62 @@ fn main() {
     }
 }
 
-fn benchmark(filename: &str, json: bool) {
+fn benchmark(filename: &str, json: bool, zero_copy: bool, fields: Option<Vec<&str>>, jobs: usize) {
     let mut list_file = File::open(filename).unwrap();
     let mut list_buffer = String::new();
     list_file.read_to_string(&mut list_buffer).unwrap();
 
     println!("{}", "reading files into memory...".blue().bold());
+    let mut unreadable = 0;
     let files: Vec<(&str, String)> = list_buffer
         .lines()
-        .map(|filename| {
-            let mut file = File::open(filename).unwrap();
+        .filter_map(|filename| {
             let mut buffer = String::new();
-            file.read_to_string(&mut buffer).unwrap();
-            (filename, buffer)
+            match File::open(filename).and_then(|mut file| file.read_to_string(&mut buffer)) {
+                Ok(_) => Some((filename, buffer)),
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        format!("{}: {}: {}", "error".red(), filename, e).bold()
+                    );
+                    unreadable += 1;
+                    None
+                }
+            }
         })
         .collect();
+    let sources: Vec<&str> = files.iter().map(|(_, buffer)| buffer.as_str()).collect();
 
     println!("{}", "parsing files...".blue().bold());
     let now = SystemTime::now();
-    files
-        .iter()
-        .map(|(filename, buffer)| {
-            let tokens = lexer::lex(&buffer);
-            if let Err(span) = tokens {
-                pretty_error(&filename, &buffer, &span, "unexpected character");
-                exit(1);
-            }
-            let output = parser::parse(tokens.unwrap().into_iter());
-            match output {
-                Err((e, msg)) => {
-                    if let Some((_, span)) = e {
-                        pretty_error(&filename, &buffer, &span, msg);
-                    } else {
-                        eprintln!("ERR");
-                    }
-                    exit(1);
+    let results = if let Some(fields) = fields {
+        bulk::map_sources(&sources, jobs, |source| {
+            let arena = Arena::new();
+            borrowed::parse_fields(source, &fields, &arena).map(|ast| {
+                if json {
+                    JsonPrinter::new(&ast.to_owned_ast()).to_string();
                 }
-                Ok(ast) => ast,
-            }
+            })
         })
-        .for_each(if json {
-            |ast| {
-                JsonPrinter::new(&ast).to_string();
-                drop(ast)
-            }
-        } else {
-            drop
-        });
+    } else if zero_copy {
+        bulk::map_sources(&sources, jobs, |source| {
+            let arena = Arena::new();
+            borrowed::parse(source, &arena).map(|ast| {
+                if json {
+                    JsonPrinter::new(&ast.to_owned_ast()).to_string();
+                }
+            })
+        })
+    } else {
+        bulk::map_sources(&sources, jobs, |source| {
+            parser::parse_str(source).map(|ast| {
+                if json {
+                    JsonPrinter::new(&ast).to_string();
+                }
+            })
+        })
+    }
+    .unwrap_or_else(|e| fatal(e));
 
     let elapsed_time = now.elapsed().unwrap().as_millis();
     println!(
@@ -100,6 +164,23 @@ fn benchmark(filename: &str, json: bool) {
             .green()
             .bold()
     );
+
+    let mut failed = unreadable;
+    for ((filename, buffer), result) in files.iter().zip(results) {
+        if let Err(e) = result {
+            failed += 1;
+            report_error(filename, buffer, &e);
+        }
+    }
+    if failed > 0 {
+        println!(
+            "{}",
+            format!("{} of {} files failed", failed, files.len() + unreadable)
+                .red()
+                .bold()
+        );
+        exit(1);
+    }
 }
 
 fn single_file(filename: &str, json: bool) {
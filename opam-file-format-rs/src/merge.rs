@@ -0,0 +1,52 @@
+use std::fs;
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+
+use opam_file_format::merge::merge;
+use opam_file_format::OpamPrinter;
+
+use crate::utils::{fatal, read_opam_file};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("merge")
+        .about("Merges the changes two opam files made to a common base, field by field and dependency by dependency")
+        .arg(Arg::with_name("BASE")
+            .help("Common ancestor")
+            .required(true))
+        .arg(Arg::with_name("OURS")
+            .help("Our version, which conflicts resolve to")
+            .required(true))
+        .arg(Arg::with_name("THEIRS")
+            .help("Their version")
+            .required(true))
+        .arg(Arg::with_name("output")
+            .long("output")
+            .short("o")
+            .takes_value(true)
+            .value_name("FILE")
+            .help("Write the merged file there instead of printing it"))
+}
+
+/// Prints the merged file, and reports conflicts on stderr, exiting with an error if there are
+/// any.
+pub fn run(matches: &ArgMatches) {
+    let (_, base) = read_opam_file(matches.value_of("BASE").unwrap());
+    let (_, ours) = read_opam_file(matches.value_of("OURS").unwrap());
+    let (_, theirs) = read_opam_file(matches.value_of("THEIRS").unwrap());
+    let merged = merge(&base, &ours, &theirs);
+    let output = OpamPrinter::new(&merged.ast).to_string();
+    match matches.value_of("output") {
+        Some(filename) => {
+            fs::write(filename, output).unwrap_or_else(|e| fatal(format!("{}: {}", filename, e)))
+        }
+        None => print!("{}", output),
+    }
+    for conflict in &merged.conflicts {
+        eprintln!("{}", conflict.to_string().red().bold());
+    }
+    if !merged.is_clean() {
+        exit(1);
+    }
+}
@@ -0,0 +1,74 @@
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use serde_json::{Map, Value as JsonValue};
+
+use opam_file_format::query::{Match, Query};
+
+use crate::utils::{fatal, read_opam_file};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("query")
+        .about(
+            "Prints the parts of opam files a path like `url.src` or `depends[*].name` points to",
+        )
+        .arg(
+            Arg::with_name("QUERY")
+                .help("Path to look up, e.g. `extra-source[\"patch.diff\"].checksum`")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("FILES")
+                .help("opam files to query")
+                .required(true)
+                .multiple(true),
+        )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .short("j")
+                .help("Print the matches as JSON instead of opam syntax"),
+        )
+}
+
+/// Prints the matches of every file, prefixed by the file name when there are several. Exits
+/// with an error when nothing matches, like grep.
+pub fn run(matches: &ArgMatches) {
+    let query = Query::parse(matches.value_of("QUERY").unwrap()).unwrap_or_else(|e| fatal(e));
+    let filenames: Vec<&str> = matches.values_of("FILES").unwrap().collect();
+    let json = matches.is_present("json");
+    let mut found = false;
+    let mut objects = Map::new();
+    for filename in &filenames {
+        let (_, ast) = read_opam_file(filename);
+        let results = query.eval(&ast);
+        found |= !results.is_empty();
+        if json {
+            let results = JsonValue::Array(results.iter().map(Match::to_json).collect());
+            objects.insert(filename.to_string(), results);
+            continue;
+        }
+        for result in results {
+            if filenames.len() > 1 {
+                println!("{}: {}", filename, result);
+            } else {
+                println!("{}", result);
+            }
+        }
+    }
+    if json {
+        let output = if filenames.len() == 1 {
+            objects
+                .into_iter()
+                .next()
+                .map(|(_, results)| results)
+                .unwrap_or_default()
+        } else {
+            JsonValue::Object(objects)
+        };
+        println!("{}", output);
+    }
+    if !found {
+        exit(1);
+    }
+}
@@ -0,0 +1,90 @@
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+
+use opam_file_format::bulk::{self, FileError};
+use opam_file_format::repository::{PackageDir, Repository};
+
+use crate::utils::{fatal, jobs, report_error};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("repository")
+        .about("Parses every package definition of an opam repository checkout")
+        .arg(
+            Arg::with_name("ROOT")
+                .help("Root of the repository, containing the packages directory")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("list")
+                .long("list")
+                .short("l")
+                .help("Print the name and version of every package found"),
+        )
+        .arg(
+            Arg::with_name("jobs")
+                .long("jobs")
+                .takes_value(true)
+                .value_name("N")
+                .help("Number of parsing threads. Defaults to the number of CPUs"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let repository = Repository::open(matches.value_of("ROOT").unwrap()).unwrap_or_else(|e| {
+        eprintln!("{}", format!("{}: {}", "error".red(), e).bold());
+        exit(1);
+    });
+    let jobs = jobs(matches);
+
+    let mut failed = 0;
+    let dirs: Vec<PackageDir> = repository
+        .package_dirs()
+        .unwrap_or_else(|e| {
+            eprintln!("{}", format!("{}: {}", "error".red(), e).bold());
+            exit(1);
+        })
+        .filter_map(|dir| match dir {
+            Ok(dir) => Some(dir),
+            Err(e) => {
+                eprintln!("{}", format!("{}: {}", "error".red(), e).bold());
+                failed += 1;
+                None
+            }
+        })
+        .collect();
+    let paths: Vec<_> = dirs.iter().map(PackageDir::opam_file).collect();
+
+    for (dir, parsed) in dirs
+        .iter()
+        .zip(bulk::parse_files(&paths, jobs).unwrap_or_else(|e| fatal(e)))
+    {
+        let filename = parsed.path.to_string_lossy();
+        match parsed.result {
+            Ok(_) => {
+                if matches.is_present("list") {
+                    println!("{}.{}", dir.name, dir.version);
+                }
+            }
+            Err(FileError::Syntax { source, error }) => {
+                failed += 1;
+                report_error(&filename, &source, &error);
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!(
+                    "{}",
+                    format!("{}: {}: {}", "error".red(), filename, e).bold()
+                );
+            }
+        }
+    }
+
+    let message = format!("parsed {} packages", dirs.len());
+    if failed > 0 {
+        println!("{}", format!("{}, {} failed", message, failed).red().bold());
+        exit(1);
+    }
+    println!("{}", message.green().bold());
+}
@@ -0,0 +1,112 @@
+use std::env::consts;
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+use serde_json::json;
+
+use opam_file_format::filter::Env;
+use opam_file_format::formula::Dependency;
+use opam_file_format::repository::Repository;
+use opam_file_format::solver::{solve, SolveError};
+
+use crate::graph::{self, load_packages};
+use crate::utils::{fatal, jobs};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("solve")
+        .about("Finds package versions of an opam repository checkout to install the given packages")
+        .arg(Arg::with_name("ROOT")
+            .help("Root of the repository, containing the packages directory")
+            .required(true))
+        .arg(Arg::with_name("PACKAGE")
+            .help("Packages to install, as NAME, NAME.VERSION or a constraint such as `ocaml>=5.1`")
+            .required(true)
+            .multiple(true))
+        .arg(Arg::with_name("json")
+            .long("json")
+            .short("j")
+            .help("Print the solution as JSON"))
+        .arg(Arg::with_name("var")
+            .long("var")
+            .takes_value(true)
+            .value_name("NAME=VALUE")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Sets a variable for filters, e.g. `os=linux` or `with-test=true`. `os` and `arch` default \
+                   to those of this machine"))
+        .arg(Arg::with_name("jobs")
+            .long("jobs")
+            .takes_value(true)
+            .value_name("N")
+            .help("Number of parsing threads. Defaults to the number of CPUs"))
+}
+
+pub fn run(matches: &ArgMatches) {
+    let repository =
+        Repository::open(matches.value_of("ROOT").unwrap()).unwrap_or_else(|e| fatal(e));
+    let jobs = jobs(matches);
+    let request = atoms(matches.values_of("PACKAGE").unwrap());
+    let env = env(matches);
+
+    let packages = load_packages(&repository, jobs);
+    match solve(&packages, &env, &request) {
+        Ok(solution) if matches.is_present("json") => {
+            let packages: Vec<_> = solution
+                .packages
+                .iter()
+                .map(|node| {
+                    json!({
+                        "name": node.name.as_str(),
+                        "version": node.version.as_str(),
+                    })
+                })
+                .collect();
+            println!("{}", json!(packages));
+        }
+        Ok(solution) => {
+            for node in &solution.packages {
+                println!("{}", node.atom());
+            }
+        }
+        Err(SolveError::Unsatisfiable(explanation)) => {
+            eprintln!("{}", format!("{}: no solution", "error".red()).bold());
+            for fact in &explanation.facts {
+                eprintln!("  - {}", fact);
+            }
+            exit(1);
+        }
+        Err(e) => fatal(e),
+    }
+}
+
+/// The environment of the `--var` arguments, `os` and `arch` defaulting to those of this machine.
+pub fn env(matches: &ArgMatches) -> Env {
+    let mut env = graph::env(matches);
+    // opam's names for the platform, where they differ from Rust's.
+    let os = match consts::OS {
+        "windows" => "win32",
+        os => os,
+    };
+    let arch = match consts::ARCH {
+        "aarch64" => "arm64",
+        "x86" => "x86_32",
+        arch => arch,
+    };
+    for (name, value) in [("os", os), ("arch", arch)] {
+        if env.get(name).is_none() {
+            env.insert(name, value);
+        }
+    }
+    env
+}
+
+/// Reads package atoms such as `dune`, `dune.3.11.0` or `ocaml>=5.1`, exiting on invalid ones.
+pub fn atoms<'a>(atoms: impl Iterator<Item = &'a str>) -> Vec<Dependency> {
+    atoms
+        .map(|atom| {
+            Dependency::from_atom(atom)
+                .unwrap_or_else(|| fatal(format!("invalid package {}", atom)))
+        })
+        .collect()
+}
@@ -1,13 +1,29 @@
+use std::fs;
+use std::process::exit;
+
+use clap::ArgMatches;
 use colored::*;
 
-use opam_file_format::lexer;
+use opam_file_format::parser::{parse_str_with_spans, SourceMap};
+use opam_file_format::{lexer, Error, OpamAST};
 
 pub fn pretty_error(filename: &str, source: &str, span: &lexer::Span, message: &str) {
+    pretty_diagnostic(filename, source, span, "error".red(), message)
+}
+
+/// Prints a message with the source lines around `span`, `label` being the severity.
+pub fn pretty_diagnostic(
+    filename: &str,
+    source: &str,
+    span: &lexer::Span,
+    label: ColoredString,
+    message: &str,
+) {
     let split_source: Vec<&str> = source.lines().collect();
     let lexer::Span { start, end } = span.clone();
     let (start_ln, end_ln) = get_line_range(source, start, end);
     let pad = (&end_ln.line).to_string().chars().count();
-    eprintln!("{}", format!("{}: {}", "error".red(), message).bold());
+    eprintln!("{}", format!("{}: {}", label, message).bold());
     eprintln!(
         "{}{} {}:{}:{}",
         repeat_str(" ", pad),
@@ -34,11 +50,52 @@ pub fn pretty_error(filename: &str, source: &str, span: &lexer::Span, message: &
         repeat_str(" ", start_ln.col),
         repeat_str("^", mark_len).red().bold()
     );
-    if end_ln.line < split_source.len() {
+    if start_ln.line + 1 < split_source.len() {
         eprintln!("{} {}", prefix_wo_ln, split_source[start_ln.line + 1]);
     }
 }
 
+pub fn report_error(filename: &str, source: &str, error: &Error) {
+    match error.span() {
+        Some(span) => pretty_error(filename, source, &span, error.message()),
+        None => eprintln!(
+            "{}",
+            format!("{}: {}: {}", "error".red(), filename, error).bold()
+        ),
+    }
+}
+
+pub fn fatal(message: impl std::fmt::Display) -> ! {
+    eprintln!("{}", format!("{}: {}", "error".red(), message).bold());
+    exit(1);
+}
+
+/// Reads the `--jobs` option, exiting if it isn't a number. `0`, the default, uses every CPU.
+pub fn jobs(matches: &ArgMatches) -> usize {
+    matches.value_of("jobs").map_or(0, |jobs| {
+        jobs.parse()
+            .unwrap_or_else(|_| fatal(format!("invalid number of jobs `{}`", jobs)))
+    })
+}
+
+/// Reads and parses an opam file, reporting any error and exiting on failure.
+pub fn read_opam_file(filename: &str) -> (String, OpamAST) {
+    let (source, (ast, _)) = read_opam_file_with_spans(filename);
+    (source, ast)
+}
+
+pub fn read_opam_file_with_spans(filename: &str) -> (String, (OpamAST, SourceMap)) {
+    let source =
+        fs::read_to_string(filename).unwrap_or_else(|e| fatal(format!("{}: {}", filename, e)));
+    match parse_str_with_spans(&source) {
+        Ok(parsed) => (source, parsed),
+        Err(e) => {
+            report_error(filename, &source, &e);
+            exit(1);
+        }
+    }
+}
+
 struct Pos {
     line: usize,
     col: usize,
@@ -0,0 +1,57 @@
+use std::process::exit;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use colored::*;
+
+use opam_file_format::checksum::verify_archive;
+
+use crate::utils::{fatal, read_opam_file};
+
+pub fn subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("verify-sources")
+        .about("Checks a local archive against the checksums of the url section of an opam file")
+        .arg(
+            Arg::with_name("OPAM")
+                .help("Package definition holding the url section")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("ARCHIVE")
+                .help("Source archive to check")
+                .required(true),
+        )
+}
+
+pub fn run(matches: &ArgMatches) {
+    let opam = matches.value_of("OPAM").unwrap();
+    let archive = matches.value_of("ARCHIVE").unwrap();
+    let (_, ast) = read_opam_file(opam);
+    let verifications =
+        verify_archive(&ast, archive).unwrap_or_else(|e| fatal(format!("{}: {}", opam, e)));
+
+    let mut failed = 0;
+    for verification in &verifications {
+        let algorithm = verification.expected.algorithm.name();
+        if verification.is_ok() {
+            println!("{} {}", algorithm, "ok".green().bold());
+        } else {
+            failed += 1;
+            println!(
+                "{} {}: expected {}, found {}",
+                algorithm,
+                "mismatch".red().bold(),
+                verification.expected.digest,
+                verification.actual
+            );
+        }
+    }
+    if failed > 0 {
+        println!(
+            "{}",
+            format!("{} of {} checksums failed", failed, verifications.len())
+                .red()
+                .bold()
+        );
+        exit(1);
+    }
+}
@@ -0,0 +1,94 @@
+use std::env;
+use std::fs;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use opam_file_format::borrowed::{self, Arena};
+use opam_file_format::{lexer, parser};
+
+const SAMPLE: &str = r#"opam-version: "2.0"
+name: "craml"
+version: "1.0.0"
+synopsis: "A CRAM-testing framework for testing command line applications"
+description: """
+CRAM is a is functional testing framework for command line
+applications. \"craml\" is freely inspired by the Mercurial
+[unified test format](https://www.mercurial-scm.org/wiki/UnifiedTests)."""
+maintainer: "Thomas Gazagnaire <thomas@gazagnaire.org>"
+authors: ["Thomas Gazagnaire <thomas@gazagnaire.org"]
+license: "ISC"
+homepage: "https://github.com/realworldocaml/craml"
+bug-reports: "https://github.com/realworldocaml/craml/issues"
+depends: [
+  "ocaml" {>= "4.02.3"}
+  "dune" {build & >= "1.0"}
+  "fmt" {>= "0.8.0"}
+  "astring"
+  "logs"
+  "cmdliner" {>= "1.0.0" & < "2.0.0"}
+  "alcotest" {with-test}
+]
+build: [
+  ["dune" "subst"] {pinned}
+  ["dune" "build" "-p" name "-j" jobs]
+  ["dune" "runtest" "-p" name] {with-test & os != "macos"}
+]
+dev-repo: "git+https://github.com/realworldocaml/craml.git"
+url {
+  src:
+    "https://github.com/realworldocaml/craml/releases/download/1.0.0/craml-1.0.0.tbz"
+  checksum: "md5=328d4d6bb137054894b215b3e10d95ca"
+}
+"#;
+
+fn owned(input: &str) {
+    let tokens = lexer::lex(input).unwrap();
+    black_box(parser::parse(tokens.into_iter()).unwrap());
+}
+
+fn borrowed(input: &str) {
+    let arena = Arena::new();
+    black_box(borrowed::parse(input, &arena).unwrap());
+}
+
+fn fields(input: &str) {
+    let arena = Arena::new();
+    black_box(borrowed::parse_fields(input, &["name", "version", "depends"], &arena).unwrap());
+}
+
+// Besides the bundled sample, `OPAM_FILES` may point to a list of opam files, e.g. produced by
+// `find ./opam-repository -name opam > opam-files`, to compare the parsers on a whole repository.
+fn corpus() -> Option<Vec<String>> {
+    let list = fs::read_to_string(env::var("OPAM_FILES").ok()?).unwrap();
+    Some(
+        list.lines()
+            .map(|filename| fs::read_to_string(filename).unwrap())
+            .collect(),
+    )
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sample");
+    group.bench_function("owned", |b| b.iter(|| owned(black_box(SAMPLE))));
+    group.bench_function("borrowed", |b| b.iter(|| borrowed(black_box(SAMPLE))));
+    group.bench_function("fields", |b| b.iter(|| fields(black_box(SAMPLE))));
+    group.finish();
+
+    if let Some(files) = corpus() {
+        let mut group = c.benchmark_group("repository");
+        group.sample_size(10);
+        group.bench_function("owned", |b| {
+            b.iter(|| files.iter().for_each(|file| owned(file)))
+        });
+        group.bench_function("borrowed", |b| {
+            b.iter(|| files.iter().for_each(|file| borrowed(file)))
+        });
+        group.bench_function("fields", |b| {
+            b.iter(|| files.iter().for_each(|file| fields(file)))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,445 @@
+//! Zero-copy variant of the AST.
+//!
+//! Identifiers and strings without escapes borrow from the source text, and nodes are allocated
+//! in an [`Arena`] instead of being boxed one by one. This is meant for bulk scanning, where
+//! most of the parsed tree is thrown away right after a few fields are inspected.
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+use logos::{Logos, SpannedIter};
+use typed_arena::Arena as TypedArena;
+
+use crate::error::Error;
+use crate::lexer::{BorrowedToken, Envop, Logop, Pfxop, Relop, Span};
+use crate::parser;
+
+pub struct Arena<'a> {
+    values: TypedArena<Value<'a>>,
+    items: TypedArena<Item<'a>>,
+}
+
+impl<'a> Arena<'a> {
+    pub fn new() -> Self {
+        Arena {
+            values: TypedArena::new(),
+            items: TypedArena::new(),
+        }
+    }
+}
+
+impl Default for Arena<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OpamAST<'a> {
+    pub items: &'a [Item<'a>],
+}
+
+#[derive(Debug, Clone)]
+pub enum Value<'a> {
+    Bool(bool),
+    Int(i64),
+    String(Cow<'a, str>),
+    Relop(Relop, &'a Value<'a>, &'a Value<'a>),
+    PrefixRelop(Relop, &'a Value<'a>),
+    Logop(Logop, &'a Value<'a>, &'a Value<'a>),
+    Pfxop(Pfxop, &'a Value<'a>),
+    Ident(Cow<'a, str>),
+    List(&'a [Value<'a>]),
+    Group(&'a [Value<'a>]),
+    Option(&'a Value<'a>, &'a [Value<'a>]),
+    EnvBinding(&'a Value<'a>, Envop, &'a Value<'a>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Item<'a> {
+    Section {
+        kind: Cow<'a, str>,
+        name: Option<Cow<'a, str>>,
+        items: &'a [Item<'a>],
+    },
+    Variable {
+        name: Cow<'a, str>,
+        value: Value<'a>,
+    },
+}
+
+impl<'a> Item<'a> {
+    /// The field name of a variable, or the kind of a section.
+    pub fn key(&self) -> &str {
+        match self {
+            Item::Section { kind, .. } => kind,
+            Item::Variable { name, .. } => name,
+        }
+    }
+
+    pub fn to_owned_item(&self) -> parser::Item {
+        match self {
+            Item::Section { name, items, .. } => parser::Item::Section {
+                name: name.as_ref().map(|name| name.to_string()),
+                items: to_owned_items(items),
+            },
+            Item::Variable { value, .. } => parser::Item::Variable(value.to_owned_value()),
+        }
+    }
+}
+
+impl<'a> OpamAST<'a> {
+    /// Looks up a top-level item. Like the owned AST, a later duplicate wins.
+    pub fn get(&self, key: &str) -> Option<&'a Item<'a>> {
+        self.items.iter().rev().find(|item| item.key() == key)
+    }
+
+    pub fn to_owned_ast(&self) -> parser::OpamAST {
+        parser::OpamAST {
+            items: to_owned_items(self.items),
+        }
+    }
+}
+
+impl Value<'_> {
+    pub fn to_owned_value(&self) -> parser::Value {
+        let boxed = |v: &Value| Box::new(v.to_owned_value());
+        let boxed_all = |vs: &[Value]| vs.iter().map(boxed).collect();
+        match self {
+            Value::Bool(b) => parser::Value::Bool(*b),
+            Value::Int(i) => parser::Value::Int(*i),
+            Value::String(s) => parser::Value::String(s.to_string()),
+            Value::Relop(op, v1, v2) => parser::Value::Relop(*op, boxed(v1), boxed(v2)),
+            Value::PrefixRelop(op, v) => parser::Value::PrefixRelop(*op, boxed(v)),
+            Value::Logop(op, v1, v2) => parser::Value::Logop(*op, boxed(v1), boxed(v2)),
+            Value::Pfxop(op, v) => parser::Value::Pfxop(*op, boxed(v)),
+            Value::Ident(id) => parser::Value::Ident(id.to_string()),
+            Value::List(l) => parser::Value::List(boxed_all(l)),
+            Value::Group(l) => parser::Value::Group(boxed_all(l)),
+            Value::Option(v, l) => parser::Value::Option(boxed(v), boxed_all(l)),
+            Value::EnvBinding(v1, op, v2) => parser::Value::EnvBinding(boxed(v1), *op, boxed(v2)),
+        }
+    }
+}
+
+fn to_owned_items(items: &[Item]) -> IndexMap<String, Box<parser::Item>> {
+    items
+        .iter()
+        .map(|item| (item.key().to_string(), Box::new(item.to_owned_item())))
+        .collect()
+}
+
+/// Parses `input` into a tree whose nodes live in `arena`.
+///
+/// This accepts the same grammar as [`parser::parse`], but is a hand-written recursive descent
+/// parser driving the lexer directly, so no intermediate token vector is built either.
+pub fn parse<'a>(input: &'a str, arena: &'a Arena<'a>) -> Result<OpamAST<'a>, Error> {
+    let mut parser = Parser::new(input, arena)?;
+    let items = parser.items()?;
+    match parser.current {
+        None => Ok(OpamAST { items }),
+        Some((_, span)) => Err(Error::Parse(Some(span), "expected `IDENT` or end of file")),
+    }
+}
+
+/// Parses only the top-level items of `input` whose key is in `fields`, sections being selected
+/// by their kind.
+///
+/// Other items are skipped at the token level: their brackets, braces and parentheses must
+/// balance, but no value is built for them and their structure isn't checked further. This is
+/// the fast path for scans that only need, say, `name`, `version` and `depends`.
+pub fn parse_fields<'a>(
+    input: &'a str,
+    fields: &[&str],
+    arena: &'a Arena<'a>,
+) -> Result<OpamAST<'a>, Error> {
+    let mut parser = Parser::new(input, arena)?;
+    while let Some((BorrowedToken::IDENT(id), _)) = parser.current {
+        if fields.contains(&id) {
+            let item = parser.item()?;
+            parser.item_stack.push(item);
+        } else {
+            parser.skip_item()?;
+        }
+    }
+    match parser.current {
+        None => Ok(OpamAST {
+            items: arena.items.alloc_extend(parser.item_stack.drain(..)),
+        }),
+        Some((_, span)) => Err(Error::Parse(Some(span), "expected `IDENT` or end of file")),
+    }
+}
+
+struct Parser<'a> {
+    tokens: SpannedIter<'a, BorrowedToken<'a>>,
+    current: Option<(BorrowedToken<'a>, Span)>,
+    arena: &'a Arena<'a>,
+    // Children are collected on these stacks and moved into the arena in one go, so building a
+    // list doesn't allocate a vector per node.
+    value_stack: Vec<Value<'a>>,
+    item_stack: Vec<Item<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    // A parser standing on the first token of `input`.
+    fn new(input: &'a str, arena: &'a Arena<'a>) -> Result<Self, Error> {
+        let mut parser = Parser {
+            tokens: BorrowedToken::lexer(input).spanned(),
+            current: None,
+            arena,
+            value_stack: vec![],
+            item_stack: vec![],
+        };
+        parser.advance()?;
+        Ok(parser)
+    }
+
+    fn advance(&mut self) -> Result<(), Error> {
+        self.current = loop {
+            match self.tokens.next() {
+                Some((BorrowedToken::COMMENT, _)) => continue,
+                Some((BorrowedToken::Error, range)) => {
+                    return Err(Error::Lex(Span {
+                        start: range.start,
+                        end: range.end,
+                    }))
+                }
+                Some((token, range)) => {
+                    break Some((
+                        token,
+                        Span {
+                            start: range.start,
+                            end: range.end,
+                        },
+                    ))
+                }
+                None => break None,
+            }
+        };
+        Ok(())
+    }
+
+    fn take(&mut self) -> Result<Option<(BorrowedToken<'a>, Span)>, Error> {
+        let token = self.current.take();
+        self.advance()?;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: BorrowedToken<'a>, msg: &'static str) -> Result<(), Error> {
+        match self.take()? {
+            Some((token, _)) if token == expected => Ok(()),
+            token => Err(unexpected(token, msg)),
+        }
+    }
+
+    fn alloc(&self, value: Value<'a>) -> &'a Value<'a> {
+        self.arena.values.alloc(value)
+    }
+
+    fn items(&mut self) -> Result<&'a [Item<'a>], Error> {
+        let base = self.item_stack.len();
+        while let Some((BorrowedToken::IDENT(_), _)) = self.current {
+            let item = self.item()?;
+            self.item_stack.push(item);
+        }
+        Ok(self.arena.items.alloc_extend(self.item_stack.drain(base..)))
+    }
+
+    fn item(&mut self) -> Result<Item<'a>, Error> {
+        let id = match self.take()? {
+            Some((BorrowedToken::IDENT(id), _)) => id,
+            token => return Err(unexpected(token, "expected `IDENT`")),
+        };
+        match self.take()? {
+            Some((BorrowedToken::COLON, _)) => Ok(Item::Variable {
+                name: Cow::Borrowed(id),
+                value: self.value()?,
+            }),
+            Some((BorrowedToken::LBRACE, _)) => {
+                let items = self.items()?;
+                self.expect(BorrowedToken::RBRACE, "expected `IDENT` or `RBRACE`")?;
+                Ok(Item::Section {
+                    kind: Cow::Borrowed(id),
+                    name: None,
+                    items,
+                })
+            }
+            Some((BorrowedToken::STRING(name), _)) => {
+                self.expect(BorrowedToken::LBRACE, "expected `LBRACE`")?;
+                let items = self.items()?;
+                self.expect(BorrowedToken::RBRACE, "expected `IDENT` or `RBRACE`")?;
+                Ok(Item::Section {
+                    kind: Cow::Borrowed(id),
+                    name: Some(name),
+                    items,
+                })
+            }
+            token => Err(unexpected(token, "expected `COLON`, `LBRACE`, or `STRING`")),
+        }
+    }
+
+    // Mirrors the precedence encoded by the `no_reduce` annotations of the LR grammar in
+    // `parser.rs`: options and logical operators extend to the right as far as possible.
+    fn value(&mut self) -> Result<Value<'a>, Error> {
+        let mut value = match self.take()? {
+            Some((BorrowedToken::PFXOP(op), _)) => {
+                let v = self.value()?;
+                return Ok(Value::Pfxop(op, self.alloc(v)));
+            }
+            Some((BorrowedToken::RELOP(op), _)) => {
+                let token = self.take()?;
+                let a = atom(token)?;
+                Value::PrefixRelop(op, self.alloc(a))
+            }
+            Some((BorrowedToken::LPAR, _)) => {
+                let vs = self.values()?;
+                self.expect(BorrowedToken::RPAR, "expected `RPAR`")?;
+                Value::Group(vs)
+            }
+            Some((BorrowedToken::LBRACKET, _)) => {
+                let vs = self.values()?;
+                self.expect(BorrowedToken::RBRACKET, "expected `RBRACKET`")?;
+                Value::List(vs)
+            }
+            token => {
+                let a1 = atom(token)?;
+                match self.current {
+                    Some((BorrowedToken::RELOP(op), _)) => {
+                        self.advance()?;
+                        let token = self.take()?;
+                        let a2 = atom(token)?;
+                        Value::Relop(op, self.alloc(a1), self.alloc(a2))
+                    }
+                    Some((BorrowedToken::ENVOP(op), _)) => {
+                        self.advance()?;
+                        let token = self.take()?;
+                        let a2 = atom(token)?;
+                        Value::EnvBinding(self.alloc(a1), op, self.alloc(a2))
+                    }
+                    _ => a1,
+                }
+            }
+        };
+        loop {
+            match self.current {
+                Some((BorrowedToken::LBRACE, _)) => {
+                    self.advance()?;
+                    let vs = self.values()?;
+                    self.expect(BorrowedToken::RBRACE, "expected `RBRACE`")?;
+                    value = Value::Option(self.alloc(value), vs);
+                }
+                Some((BorrowedToken::LOGOP(op), _)) => {
+                    self.advance()?;
+                    let v2 = self.value()?;
+                    return Ok(Value::Logop(op, self.alloc(value), self.alloc(v2)));
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn skip_item(&mut self) -> Result<(), Error> {
+        self.advance()?;
+        match self.take()? {
+            Some((BorrowedToken::COLON, _)) => self.skip_value(),
+            Some((BorrowedToken::LBRACE, _)) => self.skip_balanced('}'),
+            Some((BorrowedToken::STRING(_), _)) => {
+                self.expect(BorrowedToken::LBRACE, "expected `LBRACE`")?;
+                self.skip_balanced('}')
+            }
+            token => Err(unexpected(token, "expected `COLON`, `LBRACE`, or `STRING`")),
+        }
+    }
+
+    // Follows the shape of `value` without building anything: an operand, possibly followed by
+    // options, then an operator and another operand, and so on.
+    fn skip_value(&mut self) -> Result<(), Error> {
+        loop {
+            match self.take()? {
+                Some((BorrowedToken::PFXOP(_), _)) | Some((BorrowedToken::RELOP(_), _)) => continue,
+                Some((BorrowedToken::LPAR, _)) => self.skip_balanced(')')?,
+                Some((BorrowedToken::LBRACKET, _)) => self.skip_balanced(']')?,
+                token => {
+                    atom(token)?;
+                }
+            }
+            loop {
+                match self.current {
+                    Some((BorrowedToken::LBRACE, _)) => {
+                        self.advance()?;
+                        self.skip_balanced('}')?;
+                    }
+                    Some((BorrowedToken::LOGOP(_), _))
+                    | Some((BorrowedToken::RELOP(_), _))
+                    | Some((BorrowedToken::ENVOP(_), _)) => {
+                        self.advance()?;
+                        break;
+                    }
+                    _ => return Ok(()),
+                }
+            }
+        }
+    }
+
+    // Skips tokens up to the `close` matching an opening bracket that was just consumed.
+    fn skip_balanced(&mut self, close: char) -> Result<(), Error> {
+        let mut closing = vec![close];
+        while let Some(&expected) = closing.last() {
+            match self.take()? {
+                Some((BorrowedToken::LPAR, _)) => closing.push(')'),
+                Some((BorrowedToken::LBRACKET, _)) => closing.push(']'),
+                Some((BorrowedToken::LBRACE, _)) => closing.push('}'),
+                Some((token, span)) => {
+                    let found = match token {
+                        BorrowedToken::RPAR => ')',
+                        BorrowedToken::RBRACKET => ']',
+                        BorrowedToken::RBRACE => '}',
+                        _ => continue,
+                    };
+                    if found != expected {
+                        return Err(Error::Parse(Some(span), "unbalanced brackets"));
+                    }
+                    closing.pop();
+                }
+                None => return Err(Error::Parse(None, "unbalanced brackets")),
+            }
+        }
+        Ok(())
+    }
+
+    fn values(&mut self) -> Result<&'a [Value<'a>], Error> {
+        let base = self.value_stack.len();
+        loop {
+            match self.current {
+                None
+                | Some((BorrowedToken::RPAR, _))
+                | Some((BorrowedToken::RBRACKET, _))
+                | Some((BorrowedToken::RBRACE, _)) => break,
+                _ => {
+                    let value = self.value()?;
+                    self.value_stack.push(value);
+                }
+            }
+        }
+        Ok(self
+            .arena
+            .values
+            .alloc_extend(self.value_stack.drain(base..)))
+    }
+}
+
+fn atom<'a>(token: Option<(BorrowedToken<'a>, Span)>) -> Result<Value<'a>, Error> {
+    match token {
+        Some((BorrowedToken::IDENT(id), _)) => Ok(Value::Ident(Cow::Borrowed(id))),
+        Some((BorrowedToken::BOOL(b), _)) => Ok(Value::Bool(b)),
+        Some((BorrowedToken::INT(i), _)) => Ok(Value::Int(i)),
+        Some((BorrowedToken::STRING(s), _)) => Ok(Value::String(s)),
+        token => Err(unexpected(
+            token,
+            "expected `BOOL`, `IDENT`, `INT`, `LBRACKET`, `LPAR`, `PFXOP`, `RELOP`, or `STRING`",
+        )),
+    }
+}
+
+fn unexpected(token: Option<(BorrowedToken, Span)>, msg: &'static str) -> Error {
+    Error::Parse(token.map(|(_, span)| span), msg)
+}
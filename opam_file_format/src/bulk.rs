@@ -0,0 +1,95 @@
+//! Parsing many files at once on a thread pool.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use rayon::{ThreadPoolBuildError, ThreadPoolBuilder};
+
+use crate::error::Error;
+use crate::parser::{parse_str, OpamAST};
+
+#[derive(Debug)]
+pub enum FileError {
+    Io(io::Error),
+    /// The file was read but doesn't parse. The source is kept so that the error can be rendered.
+    Syntax {
+        source: String,
+        error: Error,
+    },
+}
+
+impl Display for FileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FileError::Io(e) => write!(f, "{}", e),
+            FileError::Syntax { error, .. } => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
+
+#[derive(Debug)]
+pub struct ParsedFile {
+    pub path: PathBuf,
+    pub result: Result<OpamAST, FileError>,
+}
+
+/// Applies `f` to every source on a pool of `jobs` threads, keeping the input order.
+///
+/// `jobs == 0` lets rayon pick the number of threads, which defaults to the number of CPUs.
+pub fn map_sources<S, T, F>(
+    sources: &[S],
+    jobs: usize,
+    f: F,
+) -> Result<Vec<T>, ThreadPoolBuildError>
+where
+    S: AsRef<str> + Sync,
+    T: Send,
+    F: Fn(&str) -> T + Sync,
+{
+    let pool = ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    Ok(pool.install(|| {
+        sources
+            .par_iter()
+            .map(|source| f(source.as_ref()))
+            .collect()
+    }))
+}
+
+/// Parses sources already loaded in memory.
+pub fn parse_sources<S>(
+    sources: &[S],
+    jobs: usize,
+) -> Result<Vec<Result<OpamAST, Error>>, ThreadPoolBuildError>
+where
+    S: AsRef<str> + Sync,
+{
+    map_sources(sources, jobs, parse_str)
+}
+
+/// Reads and parses every file in `paths`. A failing file doesn't stop the others, its error is
+/// reported in its own [`ParsedFile`].
+pub fn parse_files<P>(paths: &[P], jobs: usize) -> Result<Vec<ParsedFile>, ThreadPoolBuildError>
+where
+    P: AsRef<Path> + Sync,
+{
+    let pool = ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    Ok(pool.install(|| {
+        paths
+            .par_iter()
+            .map(|path| ParsedFile {
+                path: path.as_ref().to_path_buf(),
+                result: parse_file(path.as_ref()),
+            })
+            .collect()
+    }))
+}
+
+fn parse_file(path: &Path) -> Result<OpamAST, FileError> {
+    let source = fs::read_to_string(path).map_err(FileError::Io)?;
+    parse_str(&source).map_err(|error| FileError::Syntax { source, error })
+}
@@ -0,0 +1,270 @@
+//! Checksums of package sources, written `algo=hex` in `url` and `extra-source` sections.
+//!
+//! ```text
+//! url {
+//!   src: "https://example.com/foo-1.0.tar.gz"
+//!   checksum: ["md5=..." "sha256=..."]
+//! }
+//! ```
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use md5::Md5;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::error::FormatError;
+use crate::fields::{strings_field, Items};
+use crate::parser::{Item, OpamAST};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    Md5,
+    Sha256,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 3] = [
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha512,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|algorithm| algorithm.name() == name)
+    }
+
+    /// Length of a digest in hex digits.
+    pub fn hex_len(self) -> usize {
+        match self {
+            HashAlgorithm::Md5 => 32,
+            HashAlgorithm::Sha256 => 64,
+            HashAlgorithm::Sha512 => 128,
+        }
+    }
+}
+
+/// A checksum entry, with its digest in lowercase hex.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Checksum {
+    pub algorithm: HashAlgorithm,
+    pub digest: String,
+}
+
+impl Checksum {
+    pub fn parse(s: &str) -> Result<Self, FormatError> {
+        let error = |message: String| FormatError::new("checksum", message);
+        let (name, digest) = s
+            .find('=')
+            .map(|i| (&s[..i], &s[i + 1..]))
+            .ok_or_else(|| error(format!("expected algo=hex, found {}", s)))?;
+        let algorithm = HashAlgorithm::from_name(name)
+            .ok_or_else(|| error(format!("unknown hash algorithm {}", name)))?;
+        if !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error(format!("invalid hex digest {}", digest)));
+        }
+        if digest.len() != algorithm.hex_len() {
+            return Err(error(format!(
+                "expected {} hex digits for {}, found {}",
+                algorithm.hex_len(),
+                name,
+                digest.len()
+            )));
+        }
+        Ok(Checksum {
+            algorithm,
+            digest: digest.to_ascii_lowercase(),
+        })
+    }
+}
+
+impl FromStr for Checksum {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Checksum::parse(s)
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.algorithm.name(), self.digest)
+    }
+}
+
+/// The `checksum` field of a `url` or `extra-source` section, a single entry or a list.
+pub fn section_checksums(items: &Items) -> Result<Vec<Checksum>, FormatError> {
+    strings_field(items, "checksum")?
+        .iter()
+        .map(|s| Checksum::parse(s))
+        .collect()
+}
+
+/// Checksums of the `url` section of a package definition, empty if it has none.
+pub fn url_checksums(ast: &OpamAST) -> Result<Vec<Checksum>, FormatError> {
+    match ast.items.get("url").map(Box::as_ref) {
+        None => Ok(vec![]),
+        Some(Item::Section { items, .. }) => section_checksums(items),
+        Some(Item::Variable(_)) => {
+            Err(FormatError::new("url", "expected a section, found a field"))
+        }
+    }
+}
+
+/// Checksums of each `extra-source "name" {...}` section, by file name. See
+/// [`OpamAST::sections`] for the ASTs that keep all of them.
+pub fn extra_source_checksums(ast: &OpamAST) -> Result<Vec<(String, Vec<Checksum>)>, FormatError> {
+    ast.sections("extra-source")
+        .map(|(name, items)| {
+            let name = name.ok_or_else(|| FormatError::new("extra-source", "missing file name"))?;
+            Ok((name.to_string(), section_checksums(items)?))
+        })
+        .collect()
+}
+
+/// The outcome of checking a file against one checksum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub expected: Checksum,
+    /// Digest of the file, in lowercase hex.
+    pub actual: String,
+}
+
+impl Verification {
+    pub fn is_ok(&self) -> bool {
+        self.expected.digest == self.actual
+    }
+}
+
+enum Hasher {
+    Md5(Md5),
+    Sha256(Sha256),
+    Sha512(Sha512),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Md5 => Hasher::Md5(Md5::new()),
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finish(self) -> String {
+        let digest = match self {
+            Hasher::Md5(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.finalize().to_vec(),
+        };
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
+
+/// Checks everything read from `reader` against every checksum given, in a single pass.
+pub fn verify_reader<R: Read>(
+    mut reader: R,
+    checksums: &[Checksum],
+) -> io::Result<Vec<Verification>> {
+    let mut hashers: Vec<(HashAlgorithm, Hasher)> = HashAlgorithm::ALL
+        .iter()
+        .filter(|algorithm| {
+            checksums
+                .iter()
+                .any(|checksum| checksum.algorithm == **algorithm)
+        })
+        .map(|algorithm| (*algorithm, Hasher::new(*algorithm)))
+        .collect();
+
+    let mut buffer = [0; 64 * 1024];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        for (_, hasher) in &mut hashers {
+            hasher.update(&buffer[..read]);
+        }
+    }
+
+    let digests: Vec<(HashAlgorithm, String)> = hashers
+        .into_iter()
+        .map(|(algorithm, hasher)| (algorithm, hasher.finish()))
+        .collect();
+    Ok(checksums
+        .iter()
+        .map(|checksum| Verification {
+            expected: checksum.clone(),
+            actual: digests
+                .iter()
+                .find(|(algorithm, _)| *algorithm == checksum.algorithm)
+                .map(|(_, digest)| digest.clone())
+                .unwrap_or_default(),
+        })
+        .collect())
+}
+
+pub fn verify_file(
+    path: impl AsRef<Path>,
+    checksums: &[Checksum],
+) -> io::Result<Vec<Verification>> {
+    verify_reader(File::open(path)?, checksums)
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    Io(io::Error),
+    Format(FormatError),
+    /// The `url` section is missing or carries no checksum.
+    NoChecksum,
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Io(e) => write!(f, "{}", e),
+            VerifyError::Format(e) => write!(f, "{}", e),
+            VerifyError::NoChecksum => write!(f, "no checksum in the url section"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Checks a local archive against every checksum of the `url` section of a package definition.
+pub fn verify_archive(
+    ast: &OpamAST,
+    archive: impl AsRef<Path>,
+) -> Result<Vec<Verification>, VerifyError> {
+    let checksums = url_checksums(ast).map_err(VerifyError::Format)?;
+    if checksums.is_empty() {
+        return Err(VerifyError::NoChecksum);
+    }
+    verify_file(archive, &checksums).map_err(VerifyError::Io)
+}
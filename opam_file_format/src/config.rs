@@ -0,0 +1,156 @@
+//! Typed model of `<package>.config` files, which packages install to export variables.
+//!
+//! ```text
+//! opam-version: "2.0"
+//! variables {
+//!   path: "/usr/lib/foo"
+//!   native: true
+//! }
+//! ```
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+
+use crate::error::FormatError;
+use crate::fields::{list, string, string_field, string_value, Items};
+use crate::parser::{Item, OpamAST, Value};
+use crate::printer::OpamPrinter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariableContent {
+    Bool(bool),
+    String(String),
+    List(Vec<String>),
+}
+
+impl VariableContent {
+    fn from_value(value: &Value, field: &str) -> Result<Self, FormatError> {
+        match value {
+            Value::Bool(b) => Ok(VariableContent::Bool(*b)),
+            Value::String(s) => Ok(VariableContent::String(s.clone())),
+            Value::List(_) => list(value)
+                .into_iter()
+                .map(|value| string(value, field).map(String::from))
+                .collect::<Result<_, _>>()
+                .map(VariableContent::List),
+            _ => Err(FormatError::new(
+                field,
+                "expected a boolean, a string or a list of strings",
+            )),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            VariableContent::Bool(b) => Value::Bool(*b),
+            VariableContent::String(s) => Value::String(s.clone()),
+            VariableContent::List(l) => Value::List(
+                l.iter()
+                    .map(|s| Box::new(Value::String(s.clone())))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigFile {
+    pub opam_version: Option<String>,
+    pub variables: IndexMap<String, VariableContent>,
+    /// Files the package depends on, with their checksum, as `["path" "md5=..."]` pairs.
+    pub file_depends: Vec<(String, String)>,
+}
+
+impl ConfigFile {
+    pub fn from_ast(ast: &OpamAST) -> Result<Self, FormatError> {
+        let mut config = ConfigFile {
+            opam_version: string_field(&ast.items, "opam-version")?,
+            ..ConfigFile::default()
+        };
+        for (key, item) in &ast.items {
+            match (key.as_str(), item.as_ref()) {
+                ("opam-version", _) => (),
+                ("variables", Item::Section { name: None, items }) => {
+                    config.variables = variables(items)?;
+                }
+                ("file-depends", Item::Variable(value)) => {
+                    config.file_depends = list(value)
+                        .into_iter()
+                        .map(|value| file_depend(value, key))
+                        .collect::<Result<_, _>>()?;
+                }
+                _ => return Err(FormatError::new(key, "unknown field in a config file")),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn to_ast(&self) -> OpamAST {
+        let mut items = IndexMap::new();
+        if let Some(version) = &self.opam_version {
+            items.insert("opam-version".to_string(), string_value(version));
+        }
+        if !self.variables.is_empty() {
+            let variables = self
+                .variables
+                .iter()
+                .map(|(name, content)| (name.clone(), Box::new(Item::Variable(content.to_value()))))
+                .collect();
+            items.insert(
+                "variables".to_string(),
+                Box::new(Item::Section {
+                    name: None,
+                    items: variables,
+                }),
+            );
+        }
+        if !self.file_depends.is_empty() {
+            let values = self
+                .file_depends
+                .iter()
+                .map(|(path, checksum)| {
+                    Box::new(Value::List(vec![
+                        Box::new(Value::String(path.clone())),
+                        Box::new(Value::String(checksum.clone())),
+                    ]))
+                })
+                .collect();
+            items.insert(
+                "file-depends".to_string(),
+                Box::new(Item::Variable(Value::List(values))),
+            );
+        }
+        OpamAST { items }
+    }
+}
+
+fn variables(items: &Items) -> Result<IndexMap<String, VariableContent>, FormatError> {
+    items
+        .iter()
+        .map(|(name, item)| match item.as_ref() {
+            Item::Variable(value) => Ok((name.clone(), VariableContent::from_value(value, name)?)),
+            Item::Section { .. } => Err(FormatError::new(
+                name,
+                "expected a variable, found a section",
+            )),
+        })
+        .collect()
+}
+
+fn file_depend(value: &Value, field: &str) -> Result<(String, String), FormatError> {
+    match list(value).as_slice() {
+        [path, checksum] => Ok((
+            string(path, field)?.to_string(),
+            string(checksum, field)?.to_string(),
+        )),
+        _ => Err(FormatError::new(field, "expected a path and a checksum")),
+    }
+}
+
+/// Prints the config file in opam syntax.
+impl Display for ConfigFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", OpamPrinter::new(&self.to_ast()))
+    }
+}
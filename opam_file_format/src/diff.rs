@@ -0,0 +1,545 @@
+//! Semantic differences between two package definitions.
+//!
+//! Fields are compared by value, so that layout and comments don't matter, and sections are
+//! compared item by item. Package formulas such as `depends` are compared dependency by
+//! dependency, telling whether a version constraint was tightened or loosened, and commands
+//! such as `build` are compared command by command.
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+use serde_json::{json, Value as JsonValue};
+
+use crate::fields::Items;
+use crate::filter;
+use crate::lexer::{Logop, Pfxop};
+use crate::parser::{Item, OpamAST, Value};
+use crate::query::Match;
+use crate::schema::{Kind, Schema};
+use crate::version::compare_versions;
+
+/// How the versions a constraint allows changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Fewer versions are allowed.
+    Tightened,
+    /// More versions are allowed.
+    Loosened,
+    /// The same versions are allowed, the constraint being written differently.
+    Equivalent,
+    /// Some versions are no longer allowed and others are, or the filters changed.
+    Changed,
+}
+
+impl Direction {
+    pub fn name(self) -> &'static str {
+        match self {
+            Direction::Tightened => "tightened",
+            Direction::Loosened => "loosened",
+            Direction::Equivalent => "equivalent",
+            Direction::Changed => "changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// A field or section only in the new definition. Fields of sections are named
+    /// `section.field`.
+    Added {
+        field: String,
+        item: Item,
+    },
+    Removed {
+        field: String,
+        item: Item,
+    },
+    Modified {
+        field: String,
+        old: Item,
+        new: Item,
+    },
+    DependencyAdded {
+        field: String,
+        dependency: Value,
+    },
+    DependencyRemoved {
+        field: String,
+        dependency: Value,
+    },
+    /// The constraint on a dependency changed, `old` and `new` being the whole dependencies.
+    ConstraintChanged {
+        field: String,
+        name: String,
+        old: Value,
+        new: Value,
+        direction: Direction,
+    },
+    /// A command only in the new definition, `index` being its position there.
+    CommandAdded {
+        field: String,
+        index: usize,
+        command: Value,
+    },
+    /// A command only in the old definition, `index` being its position there.
+    CommandRemoved {
+        field: String,
+        index: usize,
+        command: Value,
+    },
+    CommandModified {
+        field: String,
+        index: usize,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl Change {
+    pub fn field(&self) -> &str {
+        match self {
+            Change::Added { field, .. }
+            | Change::Removed { field, .. }
+            | Change::Modified { field, .. }
+            | Change::DependencyAdded { field, .. }
+            | Change::DependencyRemoved { field, .. }
+            | Change::ConstraintChanged { field, .. }
+            | Change::CommandAdded { field, .. }
+            | Change::CommandRemoved { field, .. }
+            | Change::CommandModified { field, .. } => field,
+        }
+    }
+
+    /// The kind of change, as in the JSON output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Change::Added { .. } => "added",
+            Change::Removed { .. } => "removed",
+            Change::Modified { .. } => "modified",
+            Change::DependencyAdded { .. } => "dependency-added",
+            Change::DependencyRemoved { .. } => "dependency-removed",
+            Change::ConstraintChanged { .. } => "constraint-changed",
+            Change::CommandAdded { .. } => "command-added",
+            Change::CommandRemoved { .. } => "command-removed",
+            Change::CommandModified { .. } => "command-modified",
+        }
+    }
+
+    /// The change as a JSON object, values being written in opam syntax.
+    pub fn to_json(&self) -> JsonValue {
+        let mut json = json!({ "change": self.kind(), "field": self.field() });
+        let object = json.as_object_mut().unwrap();
+        let mut set = |key: &str, value: String| {
+            object.insert(key.to_string(), JsonValue::String(value));
+        };
+        match self {
+            Change::Added { field, item } => set("new", value_text(field, item)),
+            Change::Removed { field, item } => set("old", value_text(field, item)),
+            Change::Modified { field, old, new } => {
+                set("old", value_text(field, old));
+                set("new", value_text(field, new));
+            }
+            Change::DependencyAdded { dependency, .. } => set("new", dependency.to_string()),
+            Change::DependencyRemoved { dependency, .. } => set("old", dependency.to_string()),
+            Change::ConstraintChanged {
+                name,
+                old,
+                new,
+                direction,
+                ..
+            } => {
+                set("name", name.clone());
+                set("old", old.to_string());
+                set("new", new.to_string());
+                set("direction", direction.name().to_string());
+            }
+            Change::CommandAdded { command, .. } => set("new", command.to_string()),
+            Change::CommandRemoved { command, .. } => set("old", command.to_string()),
+            Change::CommandModified { old, new, .. } => {
+                set("old", old.to_string());
+                set("new", new.to_string());
+            }
+        }
+        if let Change::CommandAdded { index, .. }
+        | Change::CommandRemoved { index, .. }
+        | Change::CommandModified { index, .. } = self
+        {
+            object.insert("index".to_string(), json!(index));
+        }
+        json
+    }
+}
+
+/// Changes start with `+`, `-` or `~`. They take one line, sections aside.
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { field, item } => write!(f, "+ {}", item_text(field, item)),
+            Change::Removed { field, item } => write!(f, "- {}", item_text(field, item)),
+            Change::Modified { field, old, new } => match (old, new) {
+                (Item::Variable(old), Item::Variable(new)) => {
+                    write!(f, "~ {}: {} -> {}", field, old, new)
+                }
+                _ => write!(
+                    f,
+                    "~ {}\n  -> {}",
+                    item_text(field, old),
+                    item_text(field, new)
+                ),
+            },
+            Change::DependencyAdded { field, dependency } => {
+                write!(f, "+ {}: {}", field, dependency)
+            }
+            Change::DependencyRemoved { field, dependency } => {
+                write!(f, "- {}: {}", field, dependency)
+            }
+            Change::ConstraintChanged {
+                field,
+                old,
+                new,
+                direction,
+                ..
+            } => write!(f, "~ {}: {} -> {} ({})", field, old, new, direction.name()),
+            Change::CommandAdded {
+                field,
+                index,
+                command,
+            } => write!(f, "+ {}[{}]: {}", field, index, command),
+            Change::CommandRemoved {
+                field,
+                index,
+                command,
+            } => write!(f, "- {}[{}]: {}", field, index, command),
+            Change::CommandModified {
+                field,
+                index,
+                old,
+                new,
+            } => {
+                write!(f, "~ {}[{}]: {} -> {}", field, index, old, new)
+            }
+        }
+    }
+}
+
+/// Compares two package definitions, listing the changes from `old` to `new` in the order of
+/// the fields of `old`, then the fields only `new` has.
+pub fn diff(old: &OpamAST, new: &OpamAST) -> Vec<Change> {
+    let mut changes = vec![];
+    diff_items(&Schema::opam(), "", &old.items, &new.items, &mut changes);
+    changes
+}
+
+fn diff_items(schema: &Schema, prefix: &str, old: &Items, new: &Items, changes: &mut Vec<Change>) {
+    let path = |key: &str| format!("{}{}", prefix, key);
+    for (key, old_item) in old {
+        let field = path(key);
+        match (old_item.as_ref(), new.get(key).map(Box::as_ref)) {
+            (_, None) => changes.push(Change::Removed {
+                field,
+                item: *old_item.clone(),
+            }),
+            (old_item, Some(new_item)) if old_item == new_item => (),
+            (
+                Item::Section { name, items },
+                Some(Item::Section {
+                    name: new_name,
+                    items: new_items,
+                }),
+            ) if name == new_name => {
+                let section = match schema.get(key) {
+                    Some(Kind::Section(section)) => section.clone(),
+                    _ => Schema::new(key),
+                };
+                diff_items(&section, &format!("{}.", field), items, new_items, changes)
+            }
+            (Item::Variable(old_value), Some(Item::Variable(new_value))) => {
+                let before = changes.len();
+                match schema.get(key) {
+                    Some(Kind::Formula) => diff_formulas(&field, old_value, new_value, changes),
+                    Some(Kind::Commands) => diff_commands(&field, old_value, new_value, changes),
+                    _ => (),
+                }
+                // Other fields, and formulas changing in ways that aren't about dependencies,
+                // such as alternatives being introduced, are reported as a whole.
+                if changes.len() == before {
+                    changes.push(Change::Modified {
+                        field,
+                        old: Item::Variable(old_value.clone()),
+                        new: Item::Variable(new_value.clone()),
+                    })
+                }
+            }
+            (old_item, Some(new_item)) => changes.push(Change::Modified {
+                field,
+                old: old_item.clone(),
+                new: new_item.clone(),
+            }),
+        }
+    }
+    for (key, item) in new {
+        if !old.contains_key(key) {
+            changes.push(Change::Added {
+                field: path(key),
+                item: *item.clone(),
+            })
+        }
+    }
+}
+
+fn item_text(field: &str, item: &Item) -> String {
+    match item {
+        Item::Variable(value) => format!("{}: {}", field, value),
+        Item::Section { .. } => Match::Item { key: field, item }.to_string(),
+    }
+}
+
+// The value of a field, or the whole of a section.
+fn value_text(field: &str, item: &Item) -> String {
+    match item {
+        Item::Variable(value) => value.to_string(),
+        Item::Section { .. } => item_text(field, item),
+    }
+}
+
+// The elements of a list value, a single element possibly being written without brackets.
+fn elements(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::List(values) => values.iter().map(Box::as_ref).collect(),
+        value => vec![value],
+    }
+}
+
+// The dependencies of a formula by package name, alternatives being split into their packages.
+// A package may appear several times, e.g. once in each branch of an alternative.
+fn dependencies(formula: &Value) -> IndexMap<&str, Vec<&Value>> {
+    fn walk<'a>(value: &'a Value, dependencies: &mut IndexMap<&'a str, Vec<&'a Value>>) {
+        match value {
+            Value::Logop(_, v1, v2) => {
+                walk(v1, dependencies);
+                walk(v2, dependencies);
+            }
+            Value::Group(values) => values.iter().for_each(|value| walk(value, dependencies)),
+            Value::String(name) => dependencies.entry(name).or_default().push(value),
+            Value::Option(base, _) => {
+                if let Value::String(name) = base.as_ref() {
+                    dependencies.entry(name).or_default().push(value)
+                }
+            }
+            _ => (),
+        }
+    }
+    let mut dependencies = IndexMap::new();
+    elements(formula)
+        .into_iter()
+        .for_each(|value| walk(value, &mut dependencies));
+    dependencies
+}
+
+fn diff_formulas(field: &str, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    let (old, new) = (dependencies(old), dependencies(new));
+    for (name, old_dependencies) in &old {
+        let new_dependencies = new.get(name).map_or(&[][..], Vec::as_slice);
+        for (i, old_dependency) in old_dependencies.iter().enumerate() {
+            match new_dependencies.get(i) {
+                None => changes.push(Change::DependencyRemoved {
+                    field: field.to_string(),
+                    dependency: (*old_dependency).clone(),
+                }),
+                Some(new_dependency) if old_dependency == new_dependency => (),
+                Some(new_dependency) => changes.push(Change::ConstraintChanged {
+                    field: field.to_string(),
+                    name: name.to_string(),
+                    old: (*old_dependency).clone(),
+                    new: (*new_dependency).clone(),
+                    direction: direction(constraint(old_dependency), constraint(new_dependency)),
+                }),
+            }
+        }
+    }
+    for (name, new_dependencies) in &new {
+        let known = old.get(name).map_or(0, Vec::len);
+        for dependency in new_dependencies.iter().skip(known) {
+            changes.push(Change::DependencyAdded {
+                field: field.to_string(),
+                dependency: (*dependency).clone(),
+            })
+        }
+    }
+}
+
+fn constraint(dependency: &Value) -> Option<&Value> {
+    match dependency {
+        Value::Option(_, options) => options.first().map(Box::as_ref),
+        _ => None,
+    }
+}
+
+// Compares the versions two constraints allow. Versions are sampled at and around every version
+// the constraints mention, which covers all the intervals they can tell apart. Other atoms, such
+// as `build` or `os = "linux"`, are tried both true and false.
+fn direction(old: Option<&Value>, new: Option<&Value>) -> Direction {
+    let (old, new) = (
+        old.map(Value::with_precedence),
+        new.map(Value::with_precedence),
+    );
+    let (old, new) = (old.as_ref(), new.as_ref());
+    let mut versions = vec![];
+    let mut atoms = vec![];
+    for constraint in old.iter().chain(new.iter()) {
+        collect(constraint, &mut versions, &mut atoms);
+    }
+    if atoms.len() > 8 {
+        return Direction::Changed;
+    }
+    versions.sort_by(|a, b| compare_versions(a, b));
+    versions.dedup_by(|a, b| compare_versions(a, b) == Ordering::Equal);
+    let mut samples = vec!["~".to_string()];
+    for (i, version) in versions.iter().enumerate() {
+        samples.push(version.clone());
+        // Just above `version`: a letter sorts before anything but the end of a version and `~`.
+        let above = format!("{}A", version);
+        if versions
+            .get(i + 1)
+            .is_none_or(|next| compare_versions(&above, next) == Ordering::Less)
+        {
+            samples.push(above);
+        }
+    }
+    let (mut fewer, mut more) = (false, false);
+    for assignment in 0..1u32 << atoms.len() {
+        let env = |atom: &str| {
+            atoms
+                .iter()
+                .position(|a| a == atom)
+                .is_some_and(|i| assignment & 1 << i != 0)
+        };
+        for version in &samples {
+            let allows =
+                |constraint: Option<&Value>| constraint.is_none_or(|c| eval(c, version, &env));
+            match (allows(old), allows(new)) {
+                (true, false) => fewer = true,
+                (false, true) => more = true,
+                _ => (),
+            }
+        }
+    }
+    match (fewer, more) {
+        (true, false) => Direction::Tightened,
+        (false, true) => Direction::Loosened,
+        (false, false) => Direction::Equivalent,
+        (true, true) => Direction::Changed,
+    }
+}
+
+fn collect(constraint: &Value, versions: &mut Vec<String>, atoms: &mut Vec<String>) {
+    match constraint {
+        Value::PrefixRelop(_, version) if matches!(version.as_ref(), Value::String(_)) => {
+            if let Value::String(version) = version.as_ref() {
+                versions.push(version.clone())
+            }
+        }
+        Value::Logop(_, v1, v2) => {
+            collect(v1, versions, atoms);
+            collect(v2, versions, atoms);
+        }
+        Value::Pfxop(Pfxop::Not, value) => collect(value, versions, atoms),
+        Value::Group(values) => values
+            .iter()
+            .for_each(|value| collect(value, versions, atoms)),
+        Value::Bool(_) => (),
+        atom => {
+            let atom = atom.to_string();
+            if !atoms.contains(&atom) {
+                atoms.push(atom)
+            }
+        }
+    }
+}
+
+fn eval(constraint: &Value, version: &str, env: &impl Fn(&str) -> bool) -> bool {
+    match constraint {
+        Value::PrefixRelop(relop, value) => match value.as_ref() {
+            Value::String(bound) => filter::compare(*relop, version, bound),
+            _ => env(&constraint.to_string()),
+        },
+        Value::Logop(Logop::And, v1, v2) => eval(v1, version, env) && eval(v2, version, env),
+        Value::Logop(Logop::Or, v1, v2) => eval(v1, version, env) || eval(v2, version, env),
+        Value::Pfxop(Pfxop::Not, value) => !eval(value, version, env),
+        Value::Group(values) => values.iter().all(|value| eval(value, version, env)),
+        Value::Bool(b) => *b,
+        atom => env(&atom.to_string()),
+    }
+}
+
+fn diff_commands(field: &str, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    let (old, new) = (commands(old), commands(new));
+    // Longest common subsequence of the commands, then the unmatched commands between two
+    // matches are paired as modifications.
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = match old[i] == new[j] {
+                true => lengths[i + 1][j + 1] + 1,
+                false => lengths[i + 1][j].max(lengths[i][j + 1]),
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let (mut removed, mut added) = (vec![], vec![]);
+    let flush = |removed: &mut Vec<usize>, added: &mut Vec<usize>, changes: &mut Vec<Change>| {
+        for k in 0..removed.len().max(added.len()) {
+            changes.push(match (removed.get(k), added.get(k)) {
+                (Some(&i), Some(&j)) => Change::CommandModified {
+                    field: field.to_string(),
+                    index: j,
+                    old: old[i].clone(),
+                    new: new[j].clone(),
+                },
+                (Some(&i), None) => Change::CommandRemoved {
+                    field: field.to_string(),
+                    index: i,
+                    command: old[i].clone(),
+                },
+                (None, Some(&j)) => Change::CommandAdded {
+                    field: field.to_string(),
+                    index: j,
+                    command: new[j].clone(),
+                },
+                (None, None) => unreachable!(),
+            })
+        }
+        removed.clear();
+        added.clear();
+    };
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            flush(&mut removed, &mut added, changes);
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            removed.push(i);
+            i += 1;
+        } else {
+            added.push(j);
+            j += 1;
+        }
+    }
+    flush(&mut removed, &mut added, changes);
+}
+
+// The commands of a commands field, which may also be written as a single command.
+fn commands(value: &Value) -> Vec<Value> {
+    let is_command = |value: &Value| match value {
+        Value::List(_) => true,
+        Value::Option(value, _) => matches!(value.as_ref(), Value::List(_)),
+        _ => false,
+    };
+    let elements = elements(value);
+    if elements.iter().any(|element| is_command(element)) {
+        elements.into_iter().cloned().collect()
+    } else {
+        vec![value.clone()]
+    }
+}
@@ -0,0 +1,696 @@
+//! Programmatic changes to package definitions.
+//!
+//! The same operations are available on an [`OpamAST`], and on a [`Document`] that keeps the
+//! source text: there, each change is made by rewriting the affected text alone, so that the
+//! layout and comments of the rest of the file are kept, and the result is parsed again to keep
+//! the AST and spans in sync.
+//!
+//! Dependencies are the elements of a formula list such as `depends`, written `"name"` or
+//! `"name" {constraint}`. Elements combining several packages, such as `("a" | "b")`, are left
+//! alone.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::checksum::Checksum;
+use crate::error::{Error, FormatError};
+use crate::fields::Items;
+use crate::formatter::FIELD_ORDER;
+use crate::lexer::{Logop, Span};
+use crate::parser::{
+    parse_str_with_spans, section_kind, Item, ItemSpan, OpamAST, SourceMap, Value, ValueSpan,
+};
+
+#[derive(Debug)]
+pub enum EditError {
+    /// The field, dependency or command to change doesn't exist.
+    NotFound(String),
+    /// The dependency to add is already there.
+    Exists(String),
+    /// The field to change doesn't have the expected shape.
+    Format(FormatError),
+    /// The edited source doesn't parse. The document is left unchanged.
+    Syntax(Error),
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::NotFound(what) => write!(f, "{} not found", what),
+            EditError::Exists(what) => write!(f, "{} already exists", what),
+            EditError::Format(e) => write!(f, "{}", e),
+            EditError::Syntax(e) => write!(f, "edit produced invalid syntax: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+impl From<FormatError> for EditError {
+    fn from(e: FormatError) -> Self {
+        EditError::Format(e)
+    }
+}
+
+impl OpamAST {
+    /// Sets a top-level field. An existing field keeps its place, a new one is inserted where
+    /// the canonical field order puts it, extension and unknown fields going last.
+    pub fn set_field(&mut self, field: &str, value: Value) {
+        if let Some(item) = self.items.get_mut(field) {
+            **item = Item::Variable(value);
+            return;
+        }
+        let index = insertion_index(&self.items, field);
+        let rest: Vec<_> = self.items.drain(index..).collect();
+        self.items
+            .insert(field.to_string(), Box::new(Item::Variable(value)));
+        self.items.extend(rest);
+    }
+
+    /// Removes a top-level field or section, keeping the order of the others.
+    pub fn remove_field(&mut self, field: &str) -> Option<Item> {
+        self.items.shift_remove(field).map(|item| *item)
+    }
+
+    /// Appends `name {constraint}` to a formula field such as `depends`, creating the field if
+    /// needed.
+    pub fn add_dependency(
+        &mut self,
+        field: &str,
+        name: &str,
+        constraint: Option<Value>,
+    ) -> Result<(), EditError> {
+        let dependency = dependency(name, constraint);
+        let value = match self.items.get_mut(field) {
+            None => {
+                self.set_field(field, Value::List(vec![Box::new(dependency)]));
+                return Ok(());
+            }
+            Some(item) => variable_mut(field, item)?,
+        };
+        if find_dependency(value, name).is_some() {
+            return Err(EditError::Exists(format!(
+                "dependency \"{}\" in `{}`",
+                name, field
+            )));
+        }
+        push(value, dependency);
+        Ok(())
+    }
+
+    /// Removes the dependency on `name` from a formula field.
+    pub fn remove_dependency(&mut self, field: &str, name: &str) -> Result<(), EditError> {
+        let value = self.field_mut(field)?;
+        let index = find_dependency(value, name).ok_or_else(|| not_found(field, name))?;
+        match value {
+            Value::List(values) => {
+                values.remove(index);
+            }
+            value => *value = Value::List(vec![]),
+        }
+        Ok(())
+    }
+
+    /// Replaces the constraint of the dependency on `name`, removing it when `None`.
+    pub fn replace_dependency(
+        &mut self,
+        field: &str,
+        name: &str,
+        constraint: Option<Value>,
+    ) -> Result<(), EditError> {
+        let value = self.field_mut(field)?;
+        let index = find_dependency(value, name).ok_or_else(|| not_found(field, name))?;
+        *element_mut(value, index) = dependency(name, constraint);
+        Ok(())
+    }
+
+    /// Sets the checksum of the `url` section: an existing checksum with the same algorithm is
+    /// replaced, otherwise the new one is added.
+    pub fn set_checksum(&mut self, checksum: &Checksum) -> Result<(), EditError> {
+        let items = match self.items.get_mut("url").map(Box::as_mut) {
+            Some(Item::Section { items, .. }) => items,
+            Some(Item::Variable(_)) => {
+                return Err(FormatError::new("url", "expected a section").into())
+            }
+            None => return Err(EditError::NotFound("field `url`".to_string())),
+        };
+        let entry = Value::String(checksum.to_string());
+        let value = match items.get_mut("checksum") {
+            Some(item) => variable_mut("url.checksum", item)?,
+            None => {
+                let index = items.get_index_of("src").map_or(0, |i| i + 1);
+                let rest: Vec<_> = items.drain(index..).collect();
+                items.insert("checksum".to_string(), Box::new(Item::Variable(entry)));
+                items.extend(rest);
+                return Ok(());
+            }
+        };
+        match find_checksum(value, checksum) {
+            Some(index) => *element_mut(value, index) = entry,
+            None => push(value, entry),
+        }
+        Ok(())
+    }
+
+    /// Adds a filter to the `index`th command of a commands field such as `build`, combining it
+    /// with `&` with the filter already there.
+    pub fn add_command_filter(
+        &mut self,
+        field: &str,
+        index: usize,
+        filter: Value,
+    ) -> Result<(), EditError> {
+        let value = self.field_mut(field)?;
+        if !is_command_list(value) {
+            if index != 0 {
+                return Err(command_not_found(field, index));
+            }
+            let command = std::mem::replace(value, Value::List(vec![]));
+            *value = Value::List(vec![Box::new(Value::Option(
+                Box::new(command),
+                vec![Box::new(filter)],
+            ))]);
+            return Ok(());
+        }
+        let command = match value {
+            Value::List(commands) => commands
+                .get_mut(index)
+                .ok_or_else(|| command_not_found(field, index))?,
+            _ => unreachable!(),
+        };
+        match command.as_mut() {
+            Value::Option(_, options) => match options.as_mut_slice() {
+                [option] => {
+                    let old = std::mem::replace(option.as_mut(), Value::Bool(true));
+                    **option = and(old, filter);
+                }
+                _ => options.push(Box::new(filter)),
+            },
+            command => {
+                let old = std::mem::replace(command, Value::Bool(true));
+                *command = Value::Option(Box::new(old), vec![Box::new(filter)]);
+            }
+        }
+        Ok(())
+    }
+
+    fn field_mut(&mut self, field: &str) -> Result<&mut Value, EditError> {
+        match self.items.get_mut(field) {
+            Some(item) => variable_mut(field, item),
+            None => Err(EditError::NotFound(format!("field `{}`", field))),
+        }
+    }
+}
+
+/// A package definition together with its source text, edited in place.
+#[derive(Debug, Clone)]
+pub struct Document {
+    source: String,
+    ast: OpamAST,
+    spans: SourceMap,
+}
+
+impl Document {
+    pub fn parse(source: impl Into<String>) -> Result<Self, Error> {
+        let source = source.into();
+        let (ast, spans) = parse_str_with_spans(&source)?;
+        Ok(Document { source, ast, spans })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn ast(&self) -> &OpamAST {
+        &self.ast
+    }
+
+    pub fn spans(&self) -> &SourceMap {
+        &self.spans
+    }
+
+    pub fn into_source(self) -> String {
+        self.source
+    }
+
+    /// See [`OpamAST::set_field`]. The value of an existing field is replaced, a new field goes
+    /// on a line of its own.
+    pub fn set_field(&mut self, field: &str, value: &Value) -> Result<(), EditError> {
+        let edit = match (
+            self.ast.items.get(field).map(Box::as_ref),
+            self.spans.items.get(field),
+        ) {
+            (
+                Some(Item::Variable(_)),
+                Some(ItemSpan {
+                    value: Some(vs), ..
+                }),
+            ) => (vs.span, value.to_string()),
+            (Some(_), Some(span)) => (span.span, format!("{}: {}", field, value)),
+            _ => {
+                let index = insertion_index(&self.ast.items, field);
+                self.insert_item(&self.spans.items, index, format!("{}: {}", field, value))
+            }
+        };
+        self.apply(vec![edit])
+    }
+
+    /// See [`OpamAST::remove_field`]. Lines left empty are removed too.
+    pub fn remove_field(&mut self, field: &str) -> Result<(), EditError> {
+        let span = self
+            .spans
+            .items
+            .get(field)
+            .ok_or_else(|| EditError::NotFound(format!("field `{}`", field)))?;
+        let edit = (self.removal(span.span), String::new());
+        self.apply(vec![edit])
+    }
+
+    /// See [`OpamAST::add_dependency`]. The dependency is written after the last one, on its own
+    /// line if the others are.
+    pub fn add_dependency(
+        &mut self,
+        field: &str,
+        name: &str,
+        constraint: Option<&Value>,
+    ) -> Result<(), EditError> {
+        let text = dependency(name, constraint.cloned()).to_string();
+        let (value, span) = match self.field(field) {
+            Err(EditError::NotFound(_)) => {
+                return self.set_field(
+                    field,
+                    &Value::List(vec![Box::new(dependency(name, constraint.cloned()))]),
+                )
+            }
+            result => result?,
+        };
+        if find_dependency(value, name).is_some() {
+            return Err(EditError::Exists(format!(
+                "dependency \"{}\" in `{}`",
+                name, field
+            )));
+        }
+        let edit = self.push(value, span, text);
+        self.apply(vec![edit])
+    }
+
+    /// See [`OpamAST::remove_dependency`].
+    pub fn remove_dependency(&mut self, field: &str, name: &str) -> Result<(), EditError> {
+        let (value, span) = self.field(field)?;
+        let index = find_dependency(value, name).ok_or_else(|| not_found(field, name))?;
+        let edit = match value {
+            Value::List(_) => (self.removal(span.children[index].span), String::new()),
+            _ => (span.span, "[]".to_string()),
+        };
+        self.apply(vec![edit])
+    }
+
+    /// See [`OpamAST::replace_dependency`]. Only the constraint is rewritten.
+    pub fn replace_dependency(
+        &mut self,
+        field: &str,
+        name: &str,
+        constraint: Option<&Value>,
+    ) -> Result<(), EditError> {
+        let (value, span) = self.field(field)?;
+        let index = find_dependency(value, name).ok_or_else(|| not_found(field, name))?;
+        let (element, span) = element(value, span, index);
+        let text = constraint.map_or(String::new(), |constraint| format!(" {{{}}}", constraint));
+        self.apply(vec![self.replace_options(element, span, text)])
+    }
+
+    /// See [`OpamAST::set_checksum`].
+    pub fn set_checksum(&mut self, checksum: &Checksum) -> Result<(), EditError> {
+        let (items, spans) = match (
+            self.ast.items.get("url").map(Box::as_ref),
+            self.spans.items.get("url"),
+        ) {
+            (Some(Item::Section { items, .. }), Some(span)) => (items, span),
+            (Some(_), _) => return Err(FormatError::new("url", "expected a section").into()),
+            (None, _) => return Err(EditError::NotFound("field `url`".to_string())),
+        };
+        let text = Value::String(checksum.to_string()).to_string();
+        let edit = match (
+            items.get("checksum").map(Box::as_ref),
+            spans.items.get("checksum"),
+        ) {
+            (
+                Some(Item::Variable(value)),
+                Some(ItemSpan {
+                    value: Some(span), ..
+                }),
+            ) => match find_checksum(value, checksum) {
+                Some(index) => (element(value, span, index).1.span, text),
+                None => self.push(value, span, text),
+            },
+            (Some(_), _) => {
+                return Err(FormatError::new("url.checksum", "expected a string or a list").into())
+            }
+            (None, _) => match items.get_index_of("src") {
+                Some(index) => {
+                    self.insert_item(&spans.items, index + 1, format!("checksum: {}", text))
+                }
+                // An empty section: write the field inside the braces.
+                None if items.is_empty() => (
+                    Span {
+                        start: spans.span.end - 1,
+                        end: spans.span.end - 1,
+                    },
+                    format!(" checksum: {} ", text),
+                ),
+                None => self.insert_item(&spans.items, 0, format!("checksum: {}", text)),
+            },
+        };
+        self.apply(vec![edit])
+    }
+
+    /// See [`OpamAST::add_command_filter`].
+    pub fn add_command_filter(
+        &mut self,
+        field: &str,
+        index: usize,
+        filter: &Value,
+    ) -> Result<(), EditError> {
+        let (value, span) = self.field(field)?;
+        if !is_command_list(value) {
+            if index != 0 {
+                return Err(command_not_found(field, index));
+            }
+            let command = &self.source[span.span.start..span.span.end];
+            return self.apply(vec![(span.span, format!("[{} {{{}}}]", command, filter))]);
+        }
+        let (command, span) = match (value, span.children.get(index)) {
+            (Value::List(commands), Some(span)) if index < commands.len() => {
+                (commands[index].as_ref(), span)
+            }
+            _ => return Err(command_not_found(field, index)),
+        };
+        let edit = match command {
+            Value::Option(_, options) if options.len() == 1 => {
+                let combined = and((*options[0]).clone(), filter.clone());
+                self.replace_options(command, span, format!(" {{{}}}", combined))
+            }
+            Value::Option(..) => {
+                let last = span.children.last().unwrap().span;
+                (
+                    Span {
+                        start: last.end,
+                        end: last.end,
+                    },
+                    format!(" {}", filter),
+                )
+            }
+            _ => (
+                Span {
+                    start: span.span.end,
+                    end: span.span.end,
+                },
+                format!(" {{{}}}", filter),
+            ),
+        };
+        self.apply(vec![edit])
+    }
+
+    fn field(&self, field: &str) -> Result<(&Value, &ValueSpan), EditError> {
+        match (
+            self.ast.items.get(field).map(Box::as_ref),
+            self.spans.items.get(field),
+        ) {
+            (
+                Some(Item::Variable(value)),
+                Some(ItemSpan {
+                    value: Some(span), ..
+                }),
+            ) => Ok((value, span)),
+            (Some(_), _) => {
+                Err(FormatError::new(field, "expected a field, found a section").into())
+            }
+            (None, _) => Err(EditError::NotFound(format!("field `{}`", field))),
+        }
+    }
+
+    // Replaces the text of edited spans, then parses the result. Edits must not overlap.
+    fn apply(&mut self, mut edits: Vec<(Span, String)>) -> Result<(), EditError> {
+        edits.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+        let mut source = self.source.clone();
+        for (span, text) in edits {
+            source.replace_range(span.start..span.end, &text);
+        }
+        let (ast, spans) = parse_str_with_spans(&source).map_err(EditError::Syntax)?;
+        *self = Document { source, ast, spans };
+        Ok(())
+    }
+
+    // Inserts an item before the `index`th of `items`, or after the last one, on a line of its
+    // own with the indentation of its neighbour, or on the same line if the neighbour shares it
+    // with other items, as in `url { src: "..." }`.
+    fn insert_item(
+        &self,
+        items: &indexmap::IndexMap<String, ItemSpan>,
+        index: usize,
+        text: String,
+    ) -> (Span, String) {
+        let source = &self.source;
+        let rest = |end: usize| source[end..line_end(source, end)].trim_start();
+        match (
+            index.checked_sub(1).and_then(|i| items.get_index(i)),
+            items.get_index(index),
+        ) {
+            (Some((_, previous)), _)
+                if !(rest(previous.span.end).is_empty()
+                    || rest(previous.span.end).starts_with('#')) =>
+            {
+                let at = previous.span.end;
+                (Span { start: at, end: at }, format!(" {}", text))
+            }
+            (None, Some((_, next)))
+                if !source[line_start(source, next.span.start)..next.span.start]
+                    .trim()
+                    .is_empty() =>
+            {
+                let at = next.span.start;
+                (Span { start: at, end: at }, format!("{} ", text))
+            }
+            (Some((_, previous)), _) => {
+                let at = line_end(source, previous.span.end);
+                let indent = indentation(source, previous.span.start);
+                (Span { start: at, end: at }, format!("\n{}{}", indent, text))
+            }
+            (None, Some((_, next))) => {
+                let at = line_start(source, next.span.start);
+                let indent = indentation(source, next.span.start);
+                (Span { start: at, end: at }, format!("{}{}\n", indent, text))
+            }
+            (None, None) => {
+                let at = source.len();
+                let newline = if source.is_empty() || source.ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                };
+                (
+                    Span { start: at, end: at },
+                    format!("{}{}\n", newline, text),
+                )
+            }
+        }
+    }
+
+    // Appends an element to a list value, or makes a list of a single value.
+    fn push(&self, value: &Value, span: &ValueSpan, text: String) -> (Span, String) {
+        match (value, span.children.last()) {
+            (Value::List(_), Some(last)) => {
+                let at = Span {
+                    start: last.span.end,
+                    end: last.span.end,
+                };
+                let own_line = self.source
+                    [line_start(&self.source, last.span.start)..last.span.start]
+                    .trim()
+                    .is_empty();
+                match own_line {
+                    true => (
+                        at,
+                        format!("\n{}{}", indentation(&self.source, last.span.start), text),
+                    ),
+                    false => (at, format!(" {}", text)),
+                }
+            }
+            (Value::List(_), None) => (span.span, format!("[{}]", text)),
+            _ => (
+                span.span,
+                format!(
+                    "[{} {}]",
+                    &self.source[span.span.start..span.span.end],
+                    text
+                ),
+            ),
+        }
+    }
+
+    // Replaces the options of a value, from the end of the value itself to the closing brace.
+    fn replace_options(&self, value: &Value, span: &ValueSpan, text: String) -> (Span, String) {
+        let start = match value {
+            Value::Option(..) => span.children[0].span.end,
+            _ => span.span.end,
+        };
+        (
+            Span {
+                start,
+                end: span.span.end,
+            },
+            text,
+        )
+    }
+
+    // The span to remove to delete `span`: its whole line when nothing else is on it, otherwise
+    // the span and the blanks separating it from what precedes or follows.
+    fn removal(&self, span: Span) -> Span {
+        let source = &self.source;
+        let (start, end) = (line_start(source, span.start), line_end(source, span.end));
+        let before = &source[start..span.start];
+        let after = &source[span.end..end];
+        if before.trim().is_empty() && after.trim().is_empty() {
+            return Span {
+                start,
+                end: (end + 1).min(source.len()),
+            };
+        }
+        let blanks = |s: &str| s.len() - s.trim_start_matches([' ', '\t']).len();
+        match before.trim().is_empty() {
+            true => Span {
+                start: span.start,
+                end: span.end + blanks(after),
+            },
+            false => Span {
+                start: span.start - (before.len() - before.trim_end_matches([' ', '\t']).len()),
+                end: span.end,
+            },
+        }
+    }
+}
+
+// `old & filter`. A filter with alternatives is put in parentheses, as `&` binds tighter than `|`.
+fn and(old: Value, filter: Value) -> Value {
+    let filter = match filter.with_precedence() {
+        Value::Logop(Logop::Or, ..) => Value::Group(vec![Box::new(filter)]),
+        _ => filter,
+    };
+    Value::Logop(Logop::And, Box::new(old), Box::new(filter))
+}
+
+fn line_start(source: &str, position: usize) -> usize {
+    source[..position].rfind('\n').map_or(0, |i| i + 1)
+}
+
+fn line_end(source: &str, position: usize) -> usize {
+    source[position..]
+        .find('\n')
+        .map_or(source.len(), |i| position + i)
+}
+
+fn indentation(source: &str, position: usize) -> &str {
+    let line = &source[line_start(source, position)..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+// Where a new top-level field goes: before the first field that follows it in the canonical
+// order.
+fn insertion_index(items: &Items, field: &str) -> usize {
+    let rank = |key: &str| {
+        FIELD_ORDER
+            .iter()
+            .position(|field| *field == section_kind(key))
+    };
+    match rank(field) {
+        Some(own) => items
+            .keys()
+            .position(|key| rank(key).is_none_or(|other| other > own))
+            .unwrap_or(items.len()),
+        None => items.len(),
+    }
+}
+
+fn variable_mut<'a>(field: &str, item: &'a mut Item) -> Result<&'a mut Value, EditError> {
+    match item {
+        Item::Variable(value) => Ok(value),
+        Item::Section { .. } => {
+            Err(FormatError::new(field, "expected a field, found a section").into())
+        }
+    }
+}
+
+fn dependency(name: &str, constraint: Option<Value>) -> Value {
+    let name = Value::String(name.to_string());
+    match constraint {
+        Some(constraint) => Value::Option(Box::new(name), vec![Box::new(constraint)]),
+        None => name,
+    }
+}
+
+fn not_found(field: &str, name: &str) -> EditError {
+    EditError::NotFound(format!("dependency \"{}\" in `{}`", name, field))
+}
+
+fn command_not_found(field: &str, index: usize) -> EditError {
+    EditError::NotFound(format!("command {} of `{}`", index, field))
+}
+
+// The elements of a list value, a single element possibly being written without brackets.
+fn elements(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::List(values) => values.iter().map(Box::as_ref).collect(),
+        value => vec![value],
+    }
+}
+
+fn element<'a>(value: &'a Value, span: &'a ValueSpan, index: usize) -> (&'a Value, &'a ValueSpan) {
+    match value {
+        Value::List(values) => (&values[index], &span.children[index]),
+        value => (value, span),
+    }
+}
+
+fn element_mut(value: &mut Value, index: usize) -> &mut Value {
+    match value {
+        Value::List(values) => &mut values[index],
+        value => value,
+    }
+}
+
+fn push(value: &mut Value, element: Value) {
+    match value {
+        Value::List(values) => values.push(Box::new(element)),
+        value => {
+            let first = std::mem::replace(value, Value::List(vec![]));
+            *value = Value::List(vec![Box::new(first), Box::new(element)]);
+        }
+    }
+}
+
+fn find_dependency(value: &Value, name: &str) -> Option<usize> {
+    elements(value).into_iter().position(|element| {
+        let element: &Value = match element {
+            Value::Option(value, _) => value,
+            value => value,
+        };
+        matches!(element, Value::String(s) if s == name)
+    })
+}
+
+fn find_checksum(value: &Value, checksum: &Checksum) -> Option<usize> {
+    elements(value)
+        .into_iter()
+        .position(|element| match element {
+            Value::String(s) => s.split('=').next() == Some(checksum.algorithm.name()),
+            _ => false,
+        })
+}
+
+// Whether a commands field holds several commands, rather than the arguments of a single one.
+fn is_command_list(value: &Value) -> bool {
+    elements(value).into_iter().any(|element| match element {
+        Value::List(_) => true,
+        Value::Option(value, _) => matches!(value.as_ref(), Value::List(_)),
+        _ => false,
+    })
+}
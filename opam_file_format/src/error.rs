@@ -0,0 +1,64 @@
+use std::error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::lexer::Span;
+
+#[derive(Debug, Clone)]
+pub enum Error {
+    /// The lexer hit a character that can't start any token.
+    Lex(Span),
+    /// The parser hit an unexpected token, or the end of file when the span is `None`.
+    Parse(Option<Span>, &'static str),
+}
+
+impl Error {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Error::Lex(span) => Some(*span),
+            Error::Parse(span, _) => *span,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            Error::Lex(_) => "unexpected character",
+            Error::Parse(_, msg) => msg,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.span() {
+            Some(span) => write!(f, "{} at {}..{}", self.message(), span.start, span.end),
+            None => write!(f, "{} at end of file", self.message()),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// A file parses, but a field doesn't hold what its kind of file expects.
+#[derive(Debug, Clone)]
+pub struct FormatError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FormatError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        FormatError {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid field `{}`: {}", self.field, self.message)
+    }
+}
+
+impl error::Error for FormatError {}
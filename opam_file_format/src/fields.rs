@@ -0,0 +1,90 @@
+//! Helpers shared by the typed models to pull values out of an AST.
+use indexmap::IndexMap;
+
+use crate::error::FormatError;
+use crate::parser::{Item, Value};
+
+pub(crate) type Items = IndexMap<String, Box<Item>>;
+
+/// The value of a `field: value` item, or `None` when the field is absent.
+pub(crate) fn variable<'a>(
+    items: &'a Items,
+    field: &str,
+) -> Result<Option<&'a Value>, FormatError> {
+    match items.get(field).map(Box::as_ref) {
+        None => Ok(None),
+        Some(Item::Variable(value)) => Ok(Some(value)),
+        Some(Item::Section { .. }) => {
+            Err(FormatError::new(field, "expected a field, found a section"))
+        }
+    }
+}
+
+pub(crate) fn required<'a>(items: &'a Items, field: &str) -> Result<&'a Value, FormatError> {
+    variable(items, field)?.ok_or_else(|| FormatError::new(field, "missing field"))
+}
+
+pub(crate) fn string<'a>(value: &'a Value, field: &str) -> Result<&'a str, FormatError> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(FormatError::new(field, "expected a string")),
+    }
+}
+
+pub(crate) fn string_field(items: &Items, field: &str) -> Result<Option<String>, FormatError> {
+    variable(items, field)?
+        .map(|value| string(value, field).map(String::from))
+        .transpose()
+}
+
+/// The elements of a list. A single element may be written without brackets.
+pub(crate) fn list(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::List(values) => values.iter().map(Box::as_ref).collect(),
+        value => vec![value],
+    }
+}
+
+/// Splits `value {option}` into the value and its options.
+pub(crate) fn options(value: &Value) -> (&Value, Vec<&Value>) {
+    match value {
+        Value::Option(value, options) => (value, options.iter().map(Box::as_ref).collect()),
+        value => (value, vec![]),
+    }
+}
+
+/// Splits `value {filter}`, where the options hold at most one filter.
+pub(crate) fn filtered<'a>(
+    value: &'a Value,
+    field: &str,
+) -> Result<(&'a Value, Option<&'a Value>), FormatError> {
+    let (value, options) = options(value);
+    match options.as_slice() {
+        [] => Ok((value, None)),
+        [filter] => Ok((value, Some(filter))),
+        _ => Err(FormatError::new(field, "expected a single filter")),
+    }
+}
+
+pub(crate) fn strings(value: &Value, field: &str) -> Result<Vec<String>, FormatError> {
+    list(value)
+        .into_iter()
+        .map(|value| string(value, field).map(String::from))
+        .collect()
+}
+
+pub(crate) fn strings_field(items: &Items, field: &str) -> Result<Vec<String>, FormatError> {
+    variable(items, field)?.map_or(Ok(vec![]), |value| strings(value, field))
+}
+
+pub(crate) fn string_value(s: &str) -> Box<Item> {
+    Box::new(Item::Variable(Value::String(s.to_string())))
+}
+
+pub(crate) fn strings_value<S: AsRef<str>>(strings: &[S]) -> Box<Item> {
+    let values = strings
+        .iter()
+        .map(|s| Box::new(Value::String(s.as_ref().to_string())))
+        .collect();
+    Box::new(Item::Variable(Value::List(values)))
+}
@@ -0,0 +1,171 @@
+//! Evaluation of filters such as `os = "linux" & with-test` under an environment of variables.
+//!
+//! As in opam, values are strings or booleans, and comparisons compare versions. A filter
+//! referring to a variable the environment doesn't define is undefined, except when the rest
+//! decides the result, as in `false & undefined`; undefined filters count as false.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::lexer::{Logop, Pfxop, Relop};
+use crate::parser::Value;
+use crate::version::compare_versions;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterValue {
+    Bool(bool),
+    String(String),
+}
+
+impl FilterValue {
+    /// The value as a boolean: strings `"true"` and `"false"` convert, other strings don't.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FilterValue::Bool(b) => Some(*b),
+            FilterValue::String(s) if s == "true" => Some(true),
+            FilterValue::String(s) if s == "false" => Some(false),
+            FilterValue::String(_) => None,
+        }
+    }
+}
+
+impl Display for FilterValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterValue::Bool(b) => write!(f, "{}", b),
+            FilterValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<bool> for FilterValue {
+    fn from(b: bool) -> Self {
+        FilterValue::Bool(b)
+    }
+}
+
+impl From<&str> for FilterValue {
+    fn from(s: &str) -> Self {
+        FilterValue::String(s.to_string())
+    }
+}
+
+impl From<String> for FilterValue {
+    fn from(s: String) -> Self {
+        FilterValue::String(s)
+    }
+}
+
+/// Values of variables, such as `os`, `arch` or the `with-test` flag.
+#[derive(Debug, Clone, Default)]
+pub struct Env {
+    variables: HashMap<String, FilterValue>,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The dependency flags of `opam install`: build and post dependencies are included, test,
+    /// doc and dev setup ones aren't, and packages aren't development versions.
+    pub fn install() -> Self {
+        Env::new()
+            .set("build", true)
+            .set("post", true)
+            .set("with-test", false)
+            .set("with-doc", false)
+            .set("with-dev-setup", false)
+            .set("dev", false)
+    }
+
+    pub fn set(mut self, variable: impl Into<String>, value: impl Into<FilterValue>) -> Self {
+        self.insert(variable, value);
+        self
+    }
+
+    pub fn insert(&mut self, variable: impl Into<String>, value: impl Into<FilterValue>) {
+        self.variables.insert(variable.into(), value.into());
+    }
+
+    pub fn get(&self, variable: &str) -> Option<&FilterValue> {
+        self.variables.get(variable)
+    }
+
+    /// Evaluates a filter, `None` meaning undefined.
+    pub fn eval(&self, filter: &Value) -> Option<FilterValue> {
+        eval(filter, &|variable| self.get(variable).cloned())
+    }
+
+    /// Evaluates a filter to a boolean, undefined filters being false.
+    pub fn eval_bool(&self, filter: &Value) -> bool {
+        eval_bool(filter, &|variable| self.get(variable).cloned())
+    }
+}
+
+/// Evaluates a filter, looking variables up with `lookup`. `None` means undefined.
+pub fn eval(filter: &Value, lookup: &dyn Fn(&str) -> Option<FilterValue>) -> Option<FilterValue> {
+    eval_nested(&filter.with_precedence(), lookup)
+}
+
+// Evaluates a filter whose operators are nested by precedence.
+fn eval_nested(
+    filter: &Value,
+    lookup: &dyn Fn(&str) -> Option<FilterValue>,
+) -> Option<FilterValue> {
+    let bool = |value: &Value| eval_nested(value, lookup).and_then(|value| value.as_bool());
+    match filter {
+        Value::Bool(b) => Some(FilterValue::Bool(*b)),
+        Value::Int(i) => Some(FilterValue::String(i.to_string())),
+        Value::String(s) => Some(FilterValue::String(s.clone())),
+        Value::Ident(variable) => lookup(variable),
+        Value::Relop(relop, v1, v2) => {
+            let (v1, v2) = (eval_nested(v1, lookup)?, eval_nested(v2, lookup)?);
+            Some(FilterValue::Bool(compare(
+                *relop,
+                &v1.to_string(),
+                &v2.to_string(),
+            )))
+        }
+        Value::Logop(Logop::And, v1, v2) => match (bool(v1), bool(v2)) {
+            (Some(false), _) | (_, Some(false)) => Some(FilterValue::Bool(false)),
+            (Some(true), Some(true)) => Some(FilterValue::Bool(true)),
+            _ => None,
+        },
+        Value::Logop(Logop::Or, v1, v2) => match (bool(v1), bool(v2)) {
+            (Some(true), _) | (_, Some(true)) => Some(FilterValue::Bool(true)),
+            (Some(false), Some(false)) => Some(FilterValue::Bool(false)),
+            _ => None,
+        },
+        Value::Pfxop(Pfxop::Not, value) => bool(value).map(|b| FilterValue::Bool(!b)),
+        Value::Pfxop(Pfxop::Defined, value) => {
+            Some(FilterValue::Bool(eval_nested(value, lookup).is_some()))
+        }
+        Value::Group(values) => match values.as_slice() {
+            [value] => eval_nested(value, lookup),
+            _ => None,
+        },
+        Value::PrefixRelop(..) | Value::List(_) | Value::Option(..) | Value::EnvBinding(..) => None,
+    }
+}
+
+/// Evaluates a filter to a boolean, undefined filters being false.
+pub fn eval_bool(filter: &Value, lookup: &dyn Fn(&str) -> Option<FilterValue>) -> bool {
+    eval(filter, lookup)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Compares two filter values as versions, as opam does.
+pub fn compare(relop: Relop, v1: &str, v2: &str) -> bool {
+    let ordering = compare_versions(v1, v2);
+    match relop {
+        Relop::Eq => ordering == Ordering::Equal,
+        Relop::Neq => ordering != Ordering::Equal,
+        Relop::Geq => ordering != Ordering::Less,
+        Relop::Gt => ordering == Ordering::Greater,
+        Relop::Leq => ordering != Ordering::Greater,
+        Relop::Lt => ordering == Ordering::Less,
+    }
+}
@@ -0,0 +1,227 @@
+//! Versions of the opam file format, as declared by the `opam-version` field.
+//!
+//! The grammar is the same for all versions, so files are parsed alike whatever they declare, but
+//! fields, operators and variables were added over time, and some fields changed shape: 1.2 wraps
+//! `available` in a list and nests `depexts` by distribution tags. [`FormatVersion`] selects the
+//! schema matching a file and reports the constructs its declared version doesn't support. 2.1
+//! added nothing that is checked here, its rules are those of 2.0.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::error::{Error, FormatError};
+use crate::fields::{string, variable};
+use crate::lexer::{Pfxop, Span};
+use crate::parser::{
+    parse_str_with_spans, section_kind, Item, OpamAST, SourceMap, Value, ValueSpan,
+};
+use crate::schema::{Kind, Problem, Schema, Violation};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FormatVersion {
+    V1_2,
+    V2_0,
+    V2_1,
+    #[default]
+    V2_2,
+}
+
+/// Fields of package definitions that weren't there from the start, with the version that
+/// introduced them.
+const FIELDS_SINCE: [(&str, FormatVersion); 11] = [
+    ("synopsis", FormatVersion::V2_0),
+    ("description", FormatVersion::V2_0),
+    ("url", FormatVersion::V2_0),
+    ("extra-source", FormatVersion::V2_0),
+    ("run-test", FormatVersion::V2_0),
+    ("setenv", FormatVersion::V2_0),
+    ("build-env", FormatVersion::V2_0),
+    ("conflict-class", FormatVersion::V2_0),
+    ("pin-depends", FormatVersion::V2_0),
+    ("extra-files", FormatVersion::V2_0),
+    ("x-env-path-rewrite", FormatVersion::V2_2),
+];
+
+/// Variables of filters that weren't there from the start.
+const VARIABLES_SINCE: [(&str, FormatVersion); 3] = [
+    ("with-test", FormatVersion::V2_0),
+    ("with-doc", FormatVersion::V2_0),
+    ("with-dev-setup", FormatVersion::V2_2),
+];
+
+impl FormatVersion {
+    pub const ALL: [FormatVersion; 4] = [
+        FormatVersion::V1_2,
+        FormatVersion::V2_0,
+        FormatVersion::V2_1,
+        FormatVersion::V2_2,
+    ];
+
+    pub const LATEST: FormatVersion = FormatVersion::V2_2;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FormatVersion::V1_2 => "1.2",
+            FormatVersion::V2_0 => "2.0",
+            FormatVersion::V2_1 => "2.1",
+            FormatVersion::V2_2 => "2.2",
+        }
+    }
+
+    /// Reads `MAJOR.MINOR`, ignoring a patch version. Any 1.x version reads as 1.2, the last one,
+    /// and `2` as 2.0. Later versions than [`LATEST`](Self::LATEST) aren't known.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split('.');
+        match (parts.next(), parts.next()) {
+            (Some("1"), _) => Some(FormatVersion::V1_2),
+            (Some("2"), None) => Some(FormatVersion::V2_0),
+            (Some("2"), minor) => Self::ALL[1..]
+                .iter()
+                .copied()
+                .find(|version| Some(&version.name()[2..]) == minor),
+            _ => None,
+        }
+    }
+
+    /// The version `opam-version` declares, or the latest one when the field is missing. Callers
+    /// falling back to the latest version on error should report it, as [`parse_str`] does.
+    pub fn declared(ast: &OpamAST) -> Result<Self, FormatError> {
+        match variable(&ast.items, "opam-version")? {
+            None => Ok(Self::LATEST),
+            Some(value) => {
+                let version = string(value, "opam-version")?;
+                Self::parse(version).ok_or_else(|| {
+                    FormatError::new(
+                        "opam-version",
+                        format!("unsupported version \"{}\"", version),
+                    )
+                })
+            }
+        }
+    }
+
+    /// The schema of package definitions of this version.
+    pub fn schema(self) -> Schema {
+        let schema = Schema::opam();
+        match self {
+            FormatVersion::V1_2 => schema
+                .field("available", Kind::Any)
+                .field("depexts", Kind::Any),
+            FormatVersion::V2_0 | FormatVersion::V2_1 => schema,
+            FormatVersion::V2_2 => schema.field("x-env-path-rewrite", Kind::Any),
+        }
+    }
+
+    /// Checks a package definition against the schema of this version, then reports the fields,
+    /// operators and variables that only later versions support.
+    pub fn validate(self, ast: &OpamAST, spans: &SourceMap) -> Vec<Violation> {
+        let mut violations = self.schema().validate(ast, spans);
+        violations.extend(self.unsupported(ast, spans));
+        violations
+    }
+
+    /// The constructs of a package definition that only later versions support.
+    pub fn unsupported(self, ast: &OpamAST, spans: &SourceMap) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (key, item) in &ast.items {
+            let span = spans.items.get(key);
+            let since = FIELDS_SINCE
+                .iter()
+                .find(|(field, _)| *field == section_kind(key))
+                .map(|(_, since)| *since);
+            match since {
+                Some(since) if since > self => violations.push(Violation {
+                    field: key.clone(),
+                    problem: Problem::Unsupported {
+                        construct: "the field".to_string(),
+                        since,
+                    },
+                    span: span.map(|span| span.key),
+                }),
+                _ => (),
+            }
+            if let Item::Variable(value) = item.as_ref() {
+                let mut report = |construct, since, at: Option<Span>| {
+                    violations.push(Violation {
+                        field: key.clone(),
+                        problem: Problem::Unsupported { construct, since },
+                        span: at,
+                    })
+                };
+                self.walk(
+                    value,
+                    span.and_then(|span| span.value.as_ref()),
+                    &mut report,
+                );
+            }
+        }
+        violations
+    }
+
+    fn walk(
+        self,
+        value: &Value,
+        span: Option<&ValueSpan>,
+        report: &mut impl FnMut(String, FormatVersion, Option<Span>),
+    ) {
+        let here = span.map(|span| span.span);
+        match value {
+            Value::Pfxop(Pfxop::Defined, _) if self < FormatVersion::V2_0 => {
+                report("the `?` operator".to_string(), FormatVersion::V2_0, here)
+            }
+            Value::Ident(ident) => {
+                let since = VARIABLES_SINCE
+                    .iter()
+                    .find(|(variable, _)| variable == ident)
+                    .map(|(_, since)| *since);
+                match since {
+                    Some(since) if since > self => {
+                        report(format!("variable `{}`", ident), since, here)
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+        for (i, child) in value.children().into_iter().enumerate() {
+            self.walk(child, span.and_then(|span| span.children.get(i)), report);
+        }
+    }
+}
+
+impl Display for FormatVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A package definition read with the rules of the version it declares.
+#[derive(Debug, Clone)]
+pub struct Versioned {
+    pub ast: OpamAST,
+    pub spans: SourceMap,
+    pub version: FormatVersion,
+    /// Schema violations and constructs the version doesn't support. An unsupported
+    /// `opam-version` is reported here too, the latest version being assumed.
+    pub violations: Vec<Violation>,
+}
+
+/// Parses a package definition and validates it against its declared version.
+pub fn parse_str(source: &str) -> Result<Versioned, Error> {
+    let (ast, spans) = parse_str_with_spans(source)?;
+    let mut violations = vec![];
+    let version = FormatVersion::declared(&ast).unwrap_or_else(|_| {
+        violations.push(Violation {
+            field: "opam-version".to_string(),
+            problem: Problem::Expected("a supported opam version"),
+            span: spans.items.get("opam-version").map(|span| span.span),
+        });
+        FormatVersion::LATEST
+    });
+    violations.extend(version.validate(&ast, &spans));
+    Ok(Versioned {
+        ast,
+        spans,
+        version,
+        violations,
+    })
+}
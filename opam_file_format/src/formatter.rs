@@ -0,0 +1,478 @@
+//! Canonical layout of package definitions.
+//!
+//! Fields are sorted in the order opam itself uses, package formulas get one dependency per line
+//! with their options aligned, commands get one command per line, and other lists are wrapped
+//! only when they don't fit on a line. Strings and other literals are kept as written, and
+//! comments stay attached to the item or list element they precede, or follow on the same line.
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::error::Error;
+use crate::fields::Items;
+use crate::lexer::{comments, lex, Span};
+use crate::parser::{
+    collect, parse_items, section_kind, Item, ItemSpan, OpamAST, Value, ValueSpan,
+};
+use crate::printer::{envop_literal, logop_literal, pfxop_literal, relop_literal};
+
+const MAX_WIDTH: usize = 80;
+
+/// Canonical order of the fields of a package definition. Other fields follow in their original
+/// order, extension `x-` fields last.
+pub(crate) const FIELD_ORDER: [&str; 42] = [
+    "opam-version",
+    "name",
+    "version",
+    "synopsis",
+    "description",
+    "maintainer",
+    "authors",
+    "author",
+    "license",
+    "tags",
+    "homepage",
+    "doc",
+    "bug-reports",
+    "depends",
+    "depopts",
+    "conflicts",
+    "conflict-class",
+    "available",
+    "flags",
+    "setenv",
+    "build",
+    "run-test",
+    "build-test",
+    "build-doc",
+    "install",
+    "remove",
+    "substs",
+    "patches",
+    "build-env",
+    "features",
+    "messages",
+    "post-messages",
+    "depexts",
+    "libraries",
+    "syntax",
+    "ocaml-version",
+    "os",
+    "dev-repo",
+    "pin-depends",
+    "extra-files",
+    "url",
+    "extra-source",
+];
+
+pub(crate) const URL_ORDER: [&str; 4] = ["src", "checksum", "mirrors", "swhid"];
+
+/// Fields holding package formulas, printed one dependency per line.
+const FORMULA_FIELDS: [&str; 3] = ["depends", "depopts", "conflicts"];
+
+/// Formats a package definition.
+///
+/// Files repeating a field, or a section, are an error: the AST only keeps the last occurrence,
+/// so formatting would drop the others.
+pub fn format_str(source: &str) -> Result<String, Error> {
+    let tokens = lex(source).map_err(Error::Lex)?;
+    let (items, spans, repeated) = collect(parse_items(tokens.into_iter())?);
+    if let Some(span) = repeated {
+        return Err(Error::Parse(Some(span), "repeated field or section"));
+    }
+    let ast = OpamAST { items };
+    let comments = comments(source).map_err(Error::Lex)?;
+    let mut formatter = Formatter {
+        source,
+        comments: Comments::default(),
+        output: String::new(),
+    };
+    for comment in comments {
+        formatter.comments.attach(source, &spans, None, comment);
+    }
+    formatter.write_items(&ast, &spans);
+    Ok(formatter.output)
+}
+
+/// Whether a package definition is already formatted.
+pub fn is_formatted(source: &str) -> Result<bool, Error> {
+    Ok(format_str(source)? == source)
+}
+
+/// Comments, by the start offset of what they're attached to.
+#[derive(Default)]
+struct Comments {
+    /// Comments separated from the first item by a blank line, kept at the top of the file.
+    header: Vec<Span>,
+    before_item: HashMap<usize, Vec<Span>>,
+    after_item: HashMap<usize, Vec<Span>>,
+    before_element: HashMap<usize, Vec<Span>>,
+    after_element: HashMap<usize, Vec<Span>>,
+    /// Comments after the last item of a section or element of a list, by the start of the
+    /// section or list. Those at the end of the file have no owner.
+    closing: HashMap<Option<usize>, Vec<Span>>,
+}
+
+fn same_line(source: &str, start: usize, end: usize) -> bool {
+    !source[start..end].contains('\n')
+}
+
+impl Comments {
+    fn attach(
+        &mut self,
+        source: &str,
+        items: &IndexMap<String, ItemSpan>,
+        owner: Option<usize>,
+        comment: Span,
+    ) {
+        let mut spans: Vec<&ItemSpan> = items.values().collect();
+        spans.sort_by_key(|span| span.span.start);
+        if let Some(item) = spans
+            .iter()
+            .find(|item| item.span.start < comment.start && comment.end <= item.span.end)
+        {
+            return self.attach_inside(source, item, comment);
+        }
+        let previous = spans
+            .iter()
+            .rev()
+            .find(|item| item.span.end <= comment.start);
+        let next = spans.iter().find(|item| item.span.start >= comment.end);
+        match (previous, next) {
+            (Some(previous), _) if same_line(source, previous.span.end, comment.start) => self
+                .after_item
+                .entry(previous.span.start)
+                .or_default()
+                .push(comment),
+            (None, Some(next))
+                if owner.is_none() && source[comment.end..next.span.start].contains("\n\n") =>
+            {
+                self.header.push(comment)
+            }
+            (_, Some(next)) => self
+                .before_item
+                .entry(next.span.start)
+                .or_default()
+                .push(comment),
+            (_, None) => self.closing.entry(owner).or_default().push(comment),
+        }
+    }
+
+    fn attach_inside(&mut self, source: &str, item: &ItemSpan, comment: Span) {
+        // The value of a field ends with the field, the name of a section doesn't.
+        let value = match &item.value {
+            Some(value) if value.span.end == item.span.end => value,
+            _ => return self.attach(source, &item.items, Some(item.span.start), comment),
+        };
+        let is_list = source[value.span.start..].starts_with('[');
+        if !is_list || comment.start <= value.span.start || value.span.end < comment.end {
+            return self
+                .after_item
+                .entry(item.span.start)
+                .or_default()
+                .push(comment);
+        }
+        let elements = &value.children;
+        if let Some(element) = elements
+            .iter()
+            .find(|element| element.span.start < comment.start && comment.end <= element.span.end)
+        {
+            return self
+                .after_element
+                .entry(element.span.start)
+                .or_default()
+                .push(comment);
+        }
+        let previous = elements
+            .iter()
+            .rev()
+            .find(|element| element.span.end <= comment.start);
+        let next = elements
+            .iter()
+            .find(|element| element.span.start >= comment.end);
+        match (previous, next) {
+            (Some(previous), _) if same_line(source, previous.span.end, comment.start) => self
+                .after_element
+                .entry(previous.span.start)
+                .or_default()
+                .push(comment),
+            (_, Some(next)) => self
+                .before_element
+                .entry(next.span.start)
+                .or_default()
+                .push(comment),
+            (_, None) => self
+                .closing
+                .entry(Some(value.span.start))
+                .or_default()
+                .push(comment),
+        }
+    }
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    comments: Comments,
+    output: String,
+}
+
+impl<'a> Formatter<'a> {
+    fn text(&self, span: Span) -> &'a str {
+        &self.source[span.start..span.end]
+    }
+
+    fn write_comment_lines(&mut self, comments: Option<Vec<Span>>, indent: usize) {
+        for comment in comments.unwrap_or_default() {
+            let text = self.text(comment).trim_end();
+            self.output.push_str(&" ".repeat(indent));
+            self.output.push_str(text);
+            self.output.push('\n');
+        }
+    }
+
+    fn write_trailing_comments(&mut self, comments: Option<Vec<Span>>) {
+        for comment in comments.unwrap_or_default() {
+            let text = self.text(comment).trim_end();
+            self.output.push(' ');
+            self.output.push_str(text);
+        }
+    }
+
+    fn write_items(&mut self, ast: &OpamAST, spans: &IndexMap<String, ItemSpan>) {
+        let header = std::mem::take(&mut self.comments.header);
+        if !header.is_empty() {
+            self.write_comment_lines(Some(header), 0);
+            self.output.push('\n');
+        }
+        self.write_section_items(&ast.items, spans, &FIELD_ORDER, 0);
+        let closing = self.comments.closing.remove(&None);
+        self.write_comment_lines(closing, 0);
+    }
+
+    fn write_section_items(
+        &mut self,
+        items: &Items,
+        spans: &IndexMap<String, ItemSpan>,
+        order: &[&str],
+        indent: usize,
+    ) {
+        for (key, item) in sorted(items, order) {
+            match spans.get(key) {
+                Some(span) => self.write_item(key, item, span, indent),
+                None => continue,
+            }
+        }
+    }
+
+    fn write_item(&mut self, key: &str, item: &Item, span: &ItemSpan, indent: usize) {
+        let pad = " ".repeat(indent);
+        let before = self.comments.before_item.remove(&span.span.start);
+        self.write_comment_lines(before, indent);
+        match item {
+            Item::Variable(value) => {
+                self.output.push_str(&format!("{}{}: ", pad, key));
+                let value_span = span.value.as_ref().expect("field without a value span");
+                let column = indent + key.len() + 2;
+                self.write_field_value(key, value, value_span, column, indent);
+                let after = self.comments.after_item.remove(&span.span.start);
+                self.write_trailing_comments(after);
+                self.output.push('\n');
+            }
+            Item::Section { name, items } => {
+                let kind = section_kind(key);
+                match (name, &span.value) {
+                    (Some(_), Some(name)) => self.output.push_str(&format!(
+                        "{}{} {} {{",
+                        pad,
+                        kind,
+                        self.text(name.span)
+                    )),
+                    _ => self.output.push_str(&format!("{}{} {{", pad, kind)),
+                }
+                self.output.push('\n');
+                let order: &[&str] = if kind == "url" || kind == "extra-source" {
+                    &URL_ORDER
+                } else {
+                    &[]
+                };
+                self.write_section_items(items, &span.items, order, indent + 2);
+                let closing = self.comments.closing.remove(&Some(span.span.start));
+                self.write_comment_lines(closing, indent + 2);
+                self.output.push_str(&format!("{}}}", pad));
+                let after = self.comments.after_item.remove(&span.span.start);
+                self.write_trailing_comments(after);
+                self.output.push('\n');
+            }
+        }
+    }
+
+    fn has_comments(&self, list: &ValueSpan) -> bool {
+        self.comments.closing.contains_key(&Some(list.span.start))
+            || list.children.iter().any(|element| {
+                self.comments
+                    .before_element
+                    .contains_key(&element.span.start)
+                    || self
+                        .comments
+                        .after_element
+                        .contains_key(&element.span.start)
+            })
+    }
+
+    fn write_field_value(
+        &mut self,
+        key: &str,
+        value: &Value,
+        span: &ValueSpan,
+        column: usize,
+        indent: usize,
+    ) {
+        let values = match value {
+            Value::List(values) => values,
+            value => {
+                let text = self.inline(value, span);
+                self.output.push_str(&text);
+                return;
+            }
+        };
+        let elements: Vec<String> = values
+            .iter()
+            .zip(&span.children)
+            .map(|(value, span)| self.inline(value, span))
+            .collect();
+        let is_formula = FORMULA_FIELDS.contains(&key);
+        let inline = format!("[{}]", elements.join(" "));
+        let multiline = !values.is_empty()
+            && (is_formula
+                || column + inline.len() > MAX_WIDTH
+                || values.iter().any(|value| is_list(value))
+                || self.has_comments(span));
+        if !multiline {
+            self.output.push_str(&inline);
+            return;
+        }
+
+        // Align the options of dependencies.
+        let base_width = values
+            .iter()
+            .zip(&span.children)
+            .filter_map(|(value, span)| match value.as_ref() {
+                Value::Option(base, _) if is_formula => {
+                    Some(self.inline(base, &span.children[0]).len())
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+
+        let pad = " ".repeat(indent + 2);
+        self.output.push_str("[\n");
+        for (value, span) in values.iter().zip(&span.children) {
+            let before = self.comments.before_element.remove(&span.span.start);
+            self.write_comment_lines(before, indent + 2);
+            self.output.push_str(&pad);
+            match value.as_ref() {
+                Value::Option(base, options) if is_formula => {
+                    let base = self.inline(base, &span.children[0]);
+                    let options = self.inline_values(options, &span.children[1..]);
+                    self.output.push_str(&format!(
+                        "{:width$} {{{}}}",
+                        base,
+                        options,
+                        width = base_width
+                    ));
+                }
+                value => {
+                    let text = self.inline(value, span);
+                    self.output.push_str(&text);
+                }
+            }
+            let after = self.comments.after_element.remove(&span.span.start);
+            self.write_trailing_comments(after);
+            self.output.push('\n');
+        }
+        let closing = self.comments.closing.remove(&Some(span.span.start));
+        self.write_comment_lines(closing, indent + 2);
+        self.output.push_str(&format!("{}]", " ".repeat(indent)));
+    }
+
+    fn inline_values(&self, values: &[Box<Value>], spans: &[ValueSpan]) -> String {
+        values
+            .iter()
+            .zip(spans)
+            .map(|(value, span)| self.inline(value, span))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // Like the `Display` implementation of `Value`, but literals are copied from the source.
+    fn inline(&self, value: &Value, span: &ValueSpan) -> String {
+        let child = |i: usize| &span.children[i];
+        let operand = |value: &Value, span: &ValueSpan| match value {
+            Value::Logop(..) | Value::Pfxop(..) => format!("({})", self.inline(value, span)),
+            value => self.inline(value, span),
+        };
+        match value {
+            Value::Bool(_) | Value::Int(_) | Value::String(_) | Value::Ident(_) => {
+                self.text(span.span).to_string()
+            }
+            Value::Relop(op, v1, v2) => format!(
+                "{} {} {}",
+                self.inline(v1, child(0)),
+                relop_literal(*op),
+                self.inline(v2, child(1))
+            ),
+            Value::PrefixRelop(op, v) => {
+                format!("{} {}", relop_literal(*op), self.inline(v, child(0)))
+            }
+            Value::Logop(op, v1, v2) => format!(
+                "{} {} {}",
+                operand(v1, child(0)),
+                logop_literal(*op),
+                self.inline(v2, child(1))
+            ),
+            Value::Pfxop(op, v) => format!("{}{}", pfxop_literal(*op), self.inline(v, child(0))),
+            Value::List(values) => format!("[{}]", self.inline_values(values, &span.children)),
+            Value::Group(values) => format!("({})", self.inline_values(values, &span.children)),
+            Value::Option(v, options) => format!(
+                "{} {{{}}}",
+                operand(v, child(0)),
+                self.inline_values(options, &span.children[1..])
+            ),
+            Value::EnvBinding(v1, op, v2) => format!(
+                "{} {} {}",
+                self.inline(v1, child(0)),
+                envop_literal(*op),
+                self.inline(v2, child(1))
+            ),
+        }
+    }
+}
+
+fn is_list(value: &Value) -> bool {
+    match value {
+        Value::List(_) => true,
+        Value::Option(value, _) => is_list(value),
+        _ => false,
+    }
+}
+
+/// Items in canonical order: those named in `order` first, then the others in their original
+/// order, `x-` fields last.
+fn sorted<'a>(items: &'a Items, order: &[&str]) -> Vec<(&'a str, &'a Item)> {
+    let mut items: Vec<(usize, &'a str, &'a Item)> = items
+        .iter()
+        .enumerate()
+        .map(|(i, (key, item))| (i, key.as_str(), item.as_ref()))
+        .collect();
+    let rank = |key: &str| match order.iter().position(|field| *field == section_kind(key)) {
+        Some(position) => (0, position),
+        None if key.starts_with("x-") => (2, 0),
+        None => (1, 0),
+    };
+    items.sort_by_key(|(i, key, _)| (rank(key), *i));
+    items
+        .into_iter()
+        .map(|(_, key, item)| (key, item))
+        .collect()
+}
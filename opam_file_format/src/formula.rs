@@ -0,0 +1,353 @@
+//! Package formulas, such as `depends`, with their filters evaluated.
+//!
+//! Dependencies are written `"name" {options}`, where the options mix version constraints such
+//! as `>= "1.0"` and filters such as `build` or `os = "linux"`. Once the filters are evaluated,
+//! what's left is a [`VersionConstraint`] on each package, and dependencies whose filters are
+//! false are dropped, like opam does.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::filter::{self, FilterValue};
+use crate::lexer::{Logop, Pfxop, Relop};
+use crate::package::PackageName;
+use crate::parser::Value;
+use crate::printer::relop_literal;
+use crate::version::OpamVersion;
+
+/// The versions of a package a dependency allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionConstraint {
+    Any,
+    /// No version, as when the filter of a dependency is false.
+    Never,
+    Compare(Relop, OpamVersion),
+    And(Box<VersionConstraint>, Box<VersionConstraint>),
+    Or(Box<VersionConstraint>, Box<VersionConstraint>),
+    Not(Box<VersionConstraint>),
+}
+
+impl VersionConstraint {
+    pub fn and(self, other: VersionConstraint) -> VersionConstraint {
+        match (self, other) {
+            (VersionConstraint::Never, _) | (_, VersionConstraint::Never) => {
+                VersionConstraint::Never
+            }
+            (VersionConstraint::Any, c) | (c, VersionConstraint::Any) => c,
+            (c1, c2) => VersionConstraint::And(Box::new(c1), Box::new(c2)),
+        }
+    }
+
+    pub fn or(self, other: VersionConstraint) -> VersionConstraint {
+        match (self, other) {
+            (VersionConstraint::Any, _) | (_, VersionConstraint::Any) => VersionConstraint::Any,
+            (VersionConstraint::Never, c) | (c, VersionConstraint::Never) => c,
+            (c1, c2) => VersionConstraint::Or(Box::new(c1), Box::new(c2)),
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> VersionConstraint {
+        match self {
+            VersionConstraint::Any => VersionConstraint::Never,
+            VersionConstraint::Never => VersionConstraint::Any,
+            c => VersionConstraint::Not(Box::new(c)),
+        }
+    }
+
+    /// Reads the options of a dependency, evaluating filters with `lookup`. Versions may be
+    /// variables, as in `= version`; a constraint on an undefined variable is ignored.
+    pub fn from_options(
+        options: &[Box<Value>],
+        lookup: &dyn Fn(&str) -> Option<FilterValue>,
+    ) -> Self {
+        options
+            .iter()
+            .map(|option| Self::from_filter(&option.with_precedence(), lookup))
+            .fold(VersionConstraint::Any, VersionConstraint::and)
+    }
+
+    // Reads an option whose operators are nested by precedence.
+    fn from_filter(value: &Value, lookup: &dyn Fn(&str) -> Option<FilterValue>) -> Self {
+        match value {
+            Value::PrefixRelop(relop, version) => match filter::eval(version, lookup) {
+                Some(version) => {
+                    VersionConstraint::Compare(*relop, OpamVersion::new(version.to_string()))
+                }
+                None => VersionConstraint::Any,
+            },
+            Value::Logop(Logop::And, v1, v2) => {
+                Self::from_filter(v1, lookup).and(Self::from_filter(v2, lookup))
+            }
+            Value::Logop(Logop::Or, v1, v2) => {
+                Self::from_filter(v1, lookup).or(Self::from_filter(v2, lookup))
+            }
+            Value::Pfxop(Pfxop::Not, value) => Self::from_filter(value, lookup).not(),
+            Value::Group(values) if values.len() == 1 => Self::from_filter(&values[0], lookup),
+            filter => match filter::eval_bool(filter, lookup) {
+                true => VersionConstraint::Any,
+                false => VersionConstraint::Never,
+            },
+        }
+    }
+
+    pub fn matches(&self, version: &OpamVersion) -> bool {
+        match self {
+            VersionConstraint::Any => true,
+            VersionConstraint::Never => false,
+            VersionConstraint::Compare(relop, bound) => {
+                filter::compare(*relop, version.as_str(), bound.as_str())
+            }
+            VersionConstraint::And(c1, c2) => c1.matches(version) && c2.matches(version),
+            VersionConstraint::Or(c1, c2) => c1.matches(version) || c2.matches(version),
+            VersionConstraint::Not(c) => !c.matches(version),
+        }
+    }
+}
+
+impl Display for VersionConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let operand = |f: &mut Formatter<'_>, c: &VersionConstraint| match c {
+            VersionConstraint::And(..) | VersionConstraint::Or(..) => write!(f, "({})", c),
+            c => write!(f, "{}", c),
+        };
+        match self {
+            VersionConstraint::Any => write!(f, "true"),
+            VersionConstraint::Never => write!(f, "false"),
+            VersionConstraint::Compare(relop, version) => {
+                write!(f, "{} \"{}\"", relop_literal(*relop), version)
+            }
+            VersionConstraint::And(c1, c2) => {
+                operand(f, c1)?;
+                write!(f, " & ")?;
+                operand(f, c2)
+            }
+            VersionConstraint::Or(c1, c2) => {
+                operand(f, c1)?;
+                write!(f, " | ")?;
+                operand(f, c2)
+            }
+            VersionConstraint::Not(c) => {
+                write!(f, "!")?;
+                match c.as_ref() {
+                    VersionConstraint::Compare(..) => write!(f, "({})", c),
+                    c => operand(f, c),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: PackageName,
+    pub constraint: VersionConstraint,
+}
+
+impl Dependency {
+    pub fn matches(&self, name: &PackageName, version: &OpamVersion) -> bool {
+        self.name == *name && self.constraint.matches(version)
+    }
+
+    /// Reads a package atom as given on the opam command line: `name`, `name.version`, or a
+    /// name followed by a comparison such as `name>=version`.
+    pub fn from_atom(atom: &str) -> Option<Self> {
+        let split = atom.find(['.', '=', '!', '<', '>']);
+        let (name, rest) = match split {
+            Some(i) => (&atom[..i], &atom[i..]),
+            None => (atom, ""),
+        };
+        let (relop, version) = match rest {
+            "" => (Relop::Eq, ""),
+            rest => [
+                ("!=", Relop::Neq),
+                (">=", Relop::Geq),
+                ("<=", Relop::Leq),
+                (".", Relop::Eq),
+                ("=", Relop::Eq),
+                (">", Relop::Gt),
+                ("<", Relop::Lt),
+            ]
+            .iter()
+            .find_map(|(operator, relop)| Some((*relop, rest.strip_prefix(operator)?)))?,
+        };
+        if name.is_empty() || version.is_empty() != rest.is_empty() {
+            return None;
+        }
+        let constraint = match version {
+            "" => VersionConstraint::Any,
+            version => VersionConstraint::Compare(relop, OpamVersion::new(version)),
+        };
+        Some(Dependency {
+            name: PackageName::new(name),
+            constraint,
+        })
+    }
+}
+
+impl Display for Dependency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.constraint {
+            VersionConstraint::Any => write!(f, "\"{}\"", self.name),
+            constraint => write!(f, "\"{}\" {{{}}}", self.name, constraint),
+        }
+    }
+}
+
+/// A package formula. `And(vec![])` is the empty formula, which always holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Formula {
+    Atom(Dependency),
+    And(Vec<Formula>),
+    Or(Vec<Formula>),
+}
+
+impl Formula {
+    pub fn empty() -> Self {
+        Formula::And(vec![])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Formula::And(formulas) if formulas.is_empty())
+    }
+
+    /// Reads a formula field such as `depends`, evaluating filters with `lookup`. Dependencies
+    /// whose filter is false are dropped, along with the alternatives they leave empty.
+    pub fn from_value(value: &Value, lookup: &dyn Fn(&str) -> Option<FilterValue>) -> Self {
+        Self::from_nested(&value.with_precedence(), lookup)
+    }
+
+    // Reads a formula whose operators are nested by precedence.
+    fn from_nested(value: &Value, lookup: &dyn Fn(&str) -> Option<FilterValue>) -> Self {
+        match value {
+            Value::List(values) => {
+                Self::all(values.iter().map(|value| Self::from_nested(value, lookup)))
+            }
+            Value::Group(values) => {
+                Self::all(values.iter().map(|value| Self::from_nested(value, lookup)))
+            }
+            Value::Logop(Logop::And, v1, v2) => Self::all(vec![
+                Self::from_nested(v1, lookup),
+                Self::from_nested(v2, lookup),
+            ]),
+            Value::Logop(Logop::Or, v1, v2) => Self::any(vec![
+                Self::from_nested(v1, lookup),
+                Self::from_nested(v2, lookup),
+            ]),
+            Value::String(name) => Formula::Atom(Dependency {
+                name: PackageName::new(name.as_str()),
+                constraint: VersionConstraint::Any,
+            }),
+            Value::Option(name, options) => match name.as_ref() {
+                Value::String(name) => match VersionConstraint::from_options(options, lookup) {
+                    VersionConstraint::Never => Formula::empty(),
+                    constraint => Formula::Atom(Dependency {
+                        name: PackageName::new(name.as_str()),
+                        constraint,
+                    }),
+                },
+                _ => Formula::empty(),
+            },
+            _ => Formula::empty(),
+        }
+    }
+
+    // Conjunction of formulas, flattening nested conjunctions and dropping empty ones.
+    fn all(formulas: impl IntoIterator<Item = Formula>) -> Self {
+        let mut all = vec![];
+        for formula in formulas {
+            match formula {
+                Formula::And(formulas) => all.extend(formulas),
+                formula => all.push(formula),
+            }
+        }
+        match all.len() {
+            1 => all.pop().unwrap(),
+            _ => Formula::And(all),
+        }
+    }
+
+    // Disjunction of formulas, flattening nested disjunctions. Empty formulas are neutral, as in
+    // opam.
+    fn any(formulas: impl IntoIterator<Item = Formula>) -> Self {
+        let mut any = vec![];
+        for formula in formulas {
+            match formula {
+                Formula::Or(formulas) => any.extend(formulas),
+                formula if formula.is_empty() => (),
+                formula => any.push(formula),
+            }
+        }
+        match any.len() {
+            0 => Formula::empty(),
+            1 => any.pop().unwrap(),
+            _ => Formula::Or(any),
+        }
+    }
+
+    /// The dependencies the formula mentions, in order.
+    pub fn dependencies(&self) -> Vec<&Dependency> {
+        match self {
+            Formula::Atom(dependency) => vec![dependency],
+            Formula::And(formulas) | Formula::Or(formulas) => {
+                formulas.iter().flat_map(Formula::dependencies).collect()
+            }
+        }
+    }
+
+    /// The formula in opam syntax as an element of a list, conjunctions being written with `&`.
+    pub fn inline(&self) -> String {
+        let mut inline = String::new();
+        write_inline(&mut inline, self, None).unwrap();
+        inline
+    }
+
+    /// Whether the formula holds, `holds` telling whether a dependency is satisfied.
+    pub fn eval(&self, holds: &mut impl FnMut(&Dependency) -> bool) -> bool {
+        match self {
+            Formula::Atom(dependency) => holds(dependency),
+            Formula::And(formulas) => formulas.iter().all(|formula| formula.eval(holds)),
+            Formula::Or(formulas) => formulas.iter().any(|formula| formula.eval(holds)),
+        }
+    }
+}
+
+// Writes a formula with `&` and `|`, in parentheses when in an operand of the other operator.
+fn write_inline(f: &mut impl fmt::Write, formula: &Formula, parent: Option<&str>) -> fmt::Result {
+    let (formulas, operator) = match formula {
+        Formula::Atom(dependency) => return write!(f, "{}", dependency),
+        Formula::And(formulas) => (formulas, " & "),
+        Formula::Or(formulas) => (formulas, " | "),
+    };
+    let parenthesized = parent.is_some_and(|parent| parent != operator);
+    if parenthesized {
+        write!(f, "(")?;
+    }
+    for (i, formula) in formulas.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", operator)?;
+        }
+        write_inline(f, formula, Some(operator))?;
+    }
+    if parenthesized {
+        write!(f, ")")?;
+    }
+    Ok(())
+}
+
+/// The formula in opam syntax, as a list for conjunctions.
+impl Display for Formula {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let elements = match self {
+            Formula::And(formulas) => formulas.as_slice(),
+            formula => std::slice::from_ref(formula),
+        };
+        write!(f, "[")?;
+        for (i, formula) in elements.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write_inline(f, formula, None)?;
+        }
+        write!(f, "]")
+    }
+}
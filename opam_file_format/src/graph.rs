@@ -0,0 +1,306 @@
+//! Dependency graphs of sets of packages, such as a repository.
+//!
+//! Nodes are package versions. Each dependency of `depends`, `depopts` and `conflicts` links a
+//! package version to every version of the target package its constraint allows, once the
+//! filters are evaluated under an [`Env`]; dependencies no version satisfies are kept aside as
+//! unresolved.
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde_json::{json, Value as JsonValue};
+
+use crate::error::FormatError;
+use crate::fields::variable;
+use crate::filter::{Env, FilterValue};
+use crate::formula::{Dependency, Formula, VersionConstraint};
+use crate::lexer::Relop;
+use crate::package::PackageName;
+use crate::parser::OpamAST;
+use crate::version::OpamVersion;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    Depends,
+    Depopts,
+    Conflicts,
+}
+
+impl EdgeKind {
+    pub const ALL: [EdgeKind; 3] = [EdgeKind::Depends, EdgeKind::Depopts, EdgeKind::Conflicts];
+
+    /// The field the edge comes from.
+    pub fn field(self) -> &'static str {
+        match self {
+            EdgeKind::Depends => "depends",
+            EdgeKind::Depopts => "depopts",
+            EdgeKind::Conflicts => "conflicts",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Node {
+    pub name: PackageName,
+    pub version: OpamVersion,
+}
+
+impl Node {
+    /// The `name.version` atom of the node.
+    pub fn atom(&self) -> String {
+        format!("{}.{}", self.name, self.version)
+    }
+
+    /// The dependency on exactly this version.
+    pub fn dependency(&self) -> Dependency {
+        Dependency {
+            name: self.name.clone(),
+            constraint: VersionConstraint::Compare(Relop::Eq, self.version.clone()),
+        }
+    }
+}
+
+/// An edge between two nodes, given by their index in [`Graph::nodes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+/// A dependency no package version of the graph satisfies.
+#[derive(Debug, Clone)]
+pub struct Unresolved {
+    pub from: usize,
+    pub kind: EdgeKind,
+    pub dependency: Dependency,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub unresolved: Vec<Unresolved>,
+    versions: HashMap<PackageName, Vec<usize>>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    /// Builds the graph of package definitions, evaluating their filters under `env`. The
+    /// `name` and `version` variables are those of each package.
+    pub fn build(
+        packages: &[(PackageName, OpamVersion, OpamAST)],
+        env: &Env,
+    ) -> Result<Self, FormatError> {
+        let mut graph = Graph::default();
+        for (name, version, _) in packages {
+            graph
+                .versions
+                .entry(name.clone())
+                .or_default()
+                .push(graph.nodes.len());
+            graph.nodes.push(Node {
+                name: name.clone(),
+                version: version.clone(),
+            });
+        }
+        graph.outgoing = vec![vec![]; graph.nodes.len()];
+        graph.incoming = vec![vec![]; graph.nodes.len()];
+        for (from, (name, version, ast)) in packages.iter().enumerate() {
+            let lookup = |variable: &str| match variable {
+                "name" => Some(FilterValue::String(name.to_string())),
+                "version" => Some(FilterValue::String(version.to_string())),
+                variable => env.get(variable).cloned(),
+            };
+            for kind in EdgeKind::ALL {
+                let formula = match variable(&ast.items, kind.field())? {
+                    Some(value) => Formula::from_value(value, &lookup),
+                    None => continue,
+                };
+                for dependency in formula.dependencies() {
+                    graph.link(from, kind, dependency);
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    fn link(&mut self, from: usize, kind: EdgeKind, dependency: &Dependency) {
+        let targets: Vec<usize> = self
+            .versions
+            .get(&dependency.name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&to| dependency.constraint.matches(&self.nodes[to].version))
+            .collect();
+        if targets.is_empty() {
+            self.unresolved.push(Unresolved {
+                from,
+                kind,
+                dependency: dependency.clone(),
+            });
+        }
+        for to in targets {
+            self.outgoing[from].push(self.edges.len());
+            self.incoming[to].push(self.edges.len());
+            self.edges.push(Edge { from, to, kind });
+        }
+    }
+
+    pub fn node(&self, name: &PackageName, version: &OpamVersion) -> Option<usize> {
+        self.versions(name)
+            .iter()
+            .copied()
+            .find(|&node| self.nodes[node].version == *version)
+    }
+
+    /// The nodes of the versions of a package.
+    pub fn versions(&self, name: &PackageName) -> &[usize] {
+        self.versions.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// The edges leaving a node.
+    pub fn dependencies(&self, node: usize) -> impl Iterator<Item = &Edge> + '_ {
+        self.outgoing[node]
+            .iter()
+            .map(move |&edge| &self.edges[edge])
+    }
+
+    /// The edges reaching a node.
+    pub fn dependents(&self, node: usize) -> impl Iterator<Item = &Edge> + '_ {
+        self.incoming[node]
+            .iter()
+            .map(move |&edge| &self.edges[edge])
+    }
+
+    /// The package versions that depend on `node` through `depends` edges, directly or, when
+    /// `transitive`, through other packages. The result is sorted and excludes `node` itself
+    /// unless it's part of a cycle.
+    pub fn reverse_dependencies(&self, node: usize, transitive: bool) -> Vec<usize> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            for edge in self
+                .dependents(node)
+                .filter(|edge| edge.kind == EdgeKind::Depends)
+            {
+                if !seen[edge.from] {
+                    seen[edge.from] = true;
+                    if transitive {
+                        stack.push(edge.from);
+                    }
+                }
+            }
+        }
+        (0..self.nodes.len()).filter(|&node| seen[node]).collect()
+    }
+
+    /// The cycles of `depends` edges: groups of package versions that all depend on each other,
+    /// directly or not, including versions depending on themselves.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        // Tarjan's algorithm, iteratively so that long chains don't overflow the stack.
+        const UNVISITED: usize = usize::MAX;
+        let successors = |node: usize| -> Vec<usize> {
+            self.dependencies(node)
+                .filter(|edge| edge.kind == EdgeKind::Depends)
+                .map(|edge| edge.to)
+                .collect()
+        };
+        let mut index = vec![UNVISITED; self.nodes.len()];
+        let mut lowlink = vec![0; self.nodes.len()];
+        let mut on_stack = vec![false; self.nodes.len()];
+        let mut stack = vec![];
+        let mut next_index = 0;
+        let mut cycles = vec![];
+        for root in 0..self.nodes.len() {
+            if index[root] != UNVISITED {
+                continue;
+            }
+            let mut calls = vec![(root, successors(root), 0)];
+            index[root] = next_index;
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            while let Some((node, pending, position)) = calls.last_mut() {
+                let node = *node;
+                if let Some(&successor) = pending.get(*position) {
+                    *position += 1;
+                    if index[successor] == UNVISITED {
+                        index[successor] = next_index;
+                        lowlink[successor] = next_index;
+                        next_index += 1;
+                        stack.push(successor);
+                        on_stack[successor] = true;
+                        calls.push((successor, successors(successor), 0));
+                    } else if on_stack[successor] {
+                        lowlink[node] = lowlink[node].min(index[successor]);
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some((parent, _, _)) = calls.last() {
+                    lowlink[*parent] = lowlink[*parent].min(lowlink[node]);
+                }
+                if lowlink[node] == index[node] {
+                    let mut component = vec![];
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 || successors(node).contains(&node) {
+                        component.sort_unstable();
+                        cycles.push(component);
+                    }
+                }
+            }
+        }
+        cycles.sort();
+        cycles
+    }
+
+    /// The graph in Graphviz DOT syntax. `depopts` edges are dashed, `conflicts` edges red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dependencies {\n");
+        for node in &self.nodes {
+            writeln!(dot, "  \"{}\";", node.atom()).unwrap();
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                EdgeKind::Depends => "",
+                EdgeKind::Depopts => " [style=dashed]",
+                EdgeKind::Conflicts => " [color=red, arrowhead=tee]",
+            };
+            let (from, to) = (&self.nodes[edge.from], &self.nodes[edge.to]);
+            writeln!(dot, "  \"{}\" -> \"{}\"{};", from.atom(), to.atom(), style).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The graph as JSON, nodes being referred to by their `name.version` atom.
+    pub fn to_json(&self) -> JsonValue {
+        let atom = |node: usize| self.nodes[node].atom();
+        json!({
+            "nodes": self.nodes.iter().map(|node| json!({
+                "name": node.name.as_str(),
+                "version": node.version.as_str(),
+            })).collect::<Vec<_>>(),
+            "edges": self.edges.iter().map(|edge| json!({
+                "from": atom(edge.from),
+                "to": atom(edge.to),
+                "kind": edge.kind.field(),
+            })).collect::<Vec<_>>(),
+            "unresolved": self.unresolved.iter().map(|unresolved| json!({
+                "from": atom(unresolved.from),
+                "kind": unresolved.kind.field(),
+                "dependency": unresolved.dependency.to_string(),
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
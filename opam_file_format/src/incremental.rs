@@ -0,0 +1,289 @@
+//! Incremental reparsing, for editors parsing a document again after every change.
+//!
+//! A [`Tree`] holds the top-level items of a document along with its text. After an edit, only
+//! the items around the changed text are lexed and parsed again, while the others are moved
+//! along and keep their [`NodeId`]. Reparsed items that come out the same keep theirs too.
+//!
+//! While the document doesn't parse, the tree keeps the items before and after the damaged text,
+//! and the items the damage replaced, so that they are reused once it is fixed.
+use logos::Logos;
+
+use crate::error::Error;
+use crate::lexer::{Span, Token};
+use crate::parser::{parse_items, Item, ItemSpan, KeyedItem, OpamAST, SourceMap, ValueSpan};
+
+/// A change to the text: the bytes in `range` are replaced with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Span,
+    pub text: String,
+}
+
+/// Identity of a top-level item, kept across edits that leave the item alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(u64);
+
+/// A top-level field or section, keyed as in [`OpamAST::items`].
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub id: NodeId,
+    pub key: String,
+    pub item: Item,
+    pub span: ItemSpan,
+}
+
+// Text that doesn't parse, lying between nodes.
+#[derive(Debug, Clone)]
+struct Damage {
+    span: Span,
+    /// The error of the whole document, as [`crate::parse_str`] reports it.
+    error: Error,
+    /// Nodes the damaged text used to hold, matched against the items it holds once fixed.
+    nodes: Vec<Node>,
+}
+
+/// A parsed document, updated by [`Tree::edit`].
+#[derive(Debug, Clone)]
+pub struct Tree {
+    text: String,
+    nodes: Vec<Node>,
+    damage: Option<Damage>,
+    next_id: u64,
+}
+
+impl Tree {
+    /// Parses a whole document. A document that doesn't parse gives a tree with an
+    /// [`error`](Tree::error).
+    pub fn parse(text: impl Into<String>) -> Self {
+        let tree = Tree {
+            text: String::new(),
+            nodes: vec![],
+            damage: None,
+            next_id: 0,
+        };
+        tree.edit(&TextEdit {
+            range: Span { start: 0, end: 0 },
+            text: text.into(),
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The top-level items in source order, repeated keys included. While the document doesn't
+    /// parse, the items around the error.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Why the document doesn't parse.
+    pub fn error(&self) -> Option<&Error> {
+        self.damage.as_ref().map(|damage| &damage.error)
+    }
+
+    /// The AST and source map [`parse_str_with_spans`](crate::parser::parse_str_with_spans)
+    /// gives for the text.
+    pub fn ast(&self) -> Result<(OpamAST, SourceMap), Error> {
+        if let Some(damage) = &self.damage {
+            return Err(damage.error.clone());
+        }
+        let mut ast = OpamAST {
+            items: Default::default(),
+        };
+        let mut spans = SourceMap::default();
+        for node in &self.nodes {
+            spans.items.insert(node.key.clone(), node.span.clone());
+            ast.items
+                .insert(node.key.clone(), Box::new(node.item.clone()));
+        }
+        Ok((ast, spans))
+    }
+
+    /// Applies an edit, parsing the changed items again.
+    ///
+    /// # Panics
+    ///
+    /// Like [`String::replace_range`], if the range is out of the text or doesn't lie on
+    /// character boundaries.
+    pub fn edit(mut self, edit: &TextEdit) -> Self {
+        let Span { mut start, mut end } = edit.range;
+        self.text.replace_range(start..end, &edit.text);
+        let delta = edit.text.len() as isize - (end - start) as isize;
+        let mut damage = self.damage.take();
+        if let Some(damage) = &damage {
+            start = start.min(damage.span.start);
+            end = end.max(damage.span.end);
+        }
+
+        // The item before the edit is parsed again too, as the new text may continue its value.
+        let first = self
+            .nodes
+            .iter()
+            .position(|node| node.span.span.end >= start)
+            .unwrap_or(self.nodes.len())
+            .saturating_sub(1);
+        let from = match first {
+            0 => 0,
+            _ => self.nodes[first].span.span.start,
+        };
+        let mut old: Vec<Node> = self.nodes.drain(first..).collect();
+
+        // Lex from there until the text lines up with a following item again, and parse.
+        let mut lexer = Token::lexer(&self.text[from..])
+            .spanned()
+            .map(|(token, span)| {
+                let span = Span {
+                    start: from + span.start,
+                    end: from + span.end,
+                };
+                (token, span)
+            })
+            .peekable();
+        let mut tokens = vec![];
+        let mut lex_error = None;
+        let mut depth = 0;
+        let mut aligned = None;
+        let mut outcome = None;
+        for (i, node) in old.iter().enumerate() {
+            if node.span.span.start < end {
+                continue;
+            }
+            let at = shift(node.span.span.start, delta);
+            while let Some((token, span)) = lexer.next_if(|(_, span)| span.start < at) {
+                push(&mut tokens, &mut lex_error, &mut depth, token, span);
+            }
+            if lexer.peek().map(|(_, span)| span.start) != Some(at) {
+                continue;
+            }
+            aligned.get_or_insert(i);
+            if let Some(span) = lex_error {
+                outcome = Some((i, Err(Error::Lex(span))));
+                break;
+            }
+            // Items with unbalanced brackets can't end here.
+            if depth != 0 {
+                continue;
+            }
+            match parse_items(tokens.iter().cloned()) {
+                // The items may go on past this one.
+                Err(Error::Parse(None, _)) => (),
+                result => {
+                    outcome = Some((i, result));
+                    break;
+                }
+            }
+        }
+        let (suffix, result) = match outcome {
+            Some(outcome) => outcome,
+            None => {
+                for (token, span) in lexer {
+                    push(&mut tokens, &mut lex_error, &mut depth, token, span);
+                }
+                let result = match lex_error {
+                    Some(span) => Err(Error::Lex(span)),
+                    None => parse_items(tokens.into_iter()),
+                };
+                match result {
+                    Ok(items) => (old.len(), Ok(items)),
+                    Err(e) => (aligned.unwrap_or(old.len()), Err(e)),
+                }
+            }
+        };
+
+        let rest: Vec<Node> = old
+            .drain(suffix..)
+            .map(|mut node| {
+                shift_item(&mut node.span, delta);
+                node
+            })
+            .collect();
+        if let Some(damage) = &mut damage {
+            old.append(&mut damage.nodes);
+        }
+        match result {
+            Ok(items) => {
+                for KeyedItem {
+                    key, item, span, ..
+                } in items
+                {
+                    let reused = old
+                        .iter()
+                        .position(|node| node.key == key && node.item == item)
+                        .map(|i| old.swap_remove(i).id);
+                    let id = reused.unwrap_or_else(|| self.new_id());
+                    self.nodes.push(Node {
+                        id,
+                        key,
+                        item,
+                        span,
+                    });
+                }
+            }
+            Err(error) => {
+                let end = rest
+                    .first()
+                    .map_or(self.text.len(), |node| node.span.span.start);
+                self.damage = Some(Damage {
+                    span: Span { start: from, end },
+                    error,
+                    nodes: old,
+                });
+            }
+        }
+        self.nodes.extend(rest);
+        self
+    }
+
+    fn new_id(&mut self) -> NodeId {
+        self.next_id += 1;
+        NodeId(self.next_id)
+    }
+}
+
+// Adds a lexed token to the tokens to parse, keeping track of lex errors and bracket depth.
+fn push(
+    tokens: &mut Vec<(Token, Span)>,
+    lex_error: &mut Option<Span>,
+    depth: &mut isize,
+    token: Token,
+    span: Span,
+) {
+    match token {
+        Token::COMMENT => return,
+        Token::Error => {
+            lex_error.get_or_insert(span);
+        }
+        Token::LBRACE | Token::LBRACKET | Token::LPAR => *depth += 1,
+        Token::RBRACE | Token::RBRACKET | Token::RPAR => *depth -= 1,
+        _ => (),
+    }
+    tokens.push((token, span));
+}
+
+fn shift(offset: usize, delta: isize) -> usize {
+    (offset as isize + delta) as usize
+}
+
+fn shift_span(span: &mut Span, delta: isize) {
+    span.start = shift(span.start, delta);
+    span.end = shift(span.end, delta);
+}
+
+fn shift_value(span: &mut ValueSpan, delta: isize) {
+    shift_span(&mut span.span, delta);
+    for child in &mut span.children {
+        shift_value(child, delta);
+    }
+}
+
+fn shift_item(span: &mut ItemSpan, delta: isize) {
+    shift_span(&mut span.span, delta);
+    shift_span(&mut span.key, delta);
+    if let Some(value) = &mut span.value {
+        shift_value(value, delta);
+    }
+    for item in span.items.values_mut() {
+        shift_item(item, delta);
+    }
+}
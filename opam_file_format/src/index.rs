@@ -0,0 +1,94 @@
+//! Reading a repository from its `index.tar.gz` archive, as served by opam repositories, without
+//! extracting it to disk.
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use tar::Archive;
+
+use crate::package::{split_atom, PackageName};
+use crate::parser::OpamAST;
+use crate::repository::{parse_source, RepoFile, RepositoryError};
+use crate::version::OpamVersion;
+
+#[derive(Debug, Clone)]
+pub struct Index {
+    pub repo: Option<RepoFile>,
+    /// Package definitions, sorted by name and version.
+    pub packages: Vec<(PackageName, OpamVersion, OpamAST)>,
+}
+
+pub fn read_index(path: impl AsRef<Path>) -> Result<Index, RepositoryError> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| RepositoryError::Io(path.to_path_buf(), e))?;
+    read_index_from(file).map_err(|e| match e {
+        // Errors on the archive itself carry an empty path, point them to the archive instead.
+        RepositoryError::Io(entry, e) if entry.as_os_str().is_empty() => {
+            RepositoryError::Io(path.to_path_buf(), e)
+        }
+        e => e,
+    })
+}
+
+/// Reads a gzipped tarball laid out like a repository checkout.
+pub fn read_index_from<R: Read>(reader: R) -> Result<Index, RepositoryError> {
+    let archive_error = |e| RepositoryError::Io(PathBuf::new(), e);
+    let mut archive = Archive::new(GzDecoder::new(reader));
+    let mut index = Index {
+        repo: None,
+        packages: vec![],
+    };
+    for entry in archive.entries().map_err(archive_error)? {
+        let mut entry = entry.map_err(archive_error)?;
+        let path = entry.path().map_err(archive_error)?.into_owned();
+        let kind = match entry_kind(&path) {
+            Some(kind) => kind,
+            None => continue,
+        };
+        let mut source = String::new();
+        entry
+            .read_to_string(&mut source)
+            .map_err(|e| RepositoryError::Io(path.clone(), e))?;
+        let ast = parse_source(&path, source)?;
+        match kind {
+            EntryKind::Repo => {
+                let repo =
+                    RepoFile::from_ast(&ast).map_err(|e| RepositoryError::Format(path, e))?;
+                index.repo = Some(repo);
+            }
+            EntryKind::Opam(name, version) => index.packages.push((name, version, ast)),
+        }
+    }
+    index
+        .packages
+        .sort_by(|(name_a, version_a, _), (name_b, version_b, _)| {
+            name_a.cmp(name_b).then_with(|| version_a.cmp(version_b))
+        });
+    Ok(index)
+}
+
+enum EntryKind {
+    Repo,
+    Opam(PackageName, OpamVersion),
+}
+
+fn entry_kind(path: &Path) -> Option<EntryKind> {
+    let components: Vec<&str> = path
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    match components.as_slice() {
+        ["repo"] => Some(EntryKind::Repo),
+        ["packages", dir_name, atom, "opam"] => {
+            let (name, version) = split_atom(atom)?;
+            if name.as_str() == *dir_name {
+                Some(EntryKind::Opam(name, version))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
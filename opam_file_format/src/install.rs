@@ -0,0 +1,198 @@
+//! Typed model of `<package>.install` files.
+//!
+//! ```text
+//! lib: [ "_build/foo.cma" "?_build/foo.cmxs" "META" {"META"} ]
+//! bin: [ "_build/main.exe" {"foo"} ]
+//! ```
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+
+use crate::error::FormatError;
+use crate::fields::{list, options, string};
+use crate::parser::{Item, OpamAST, Value};
+use crate::printer::OpamPrinter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstallSection {
+    Lib,
+    LibRoot,
+    Libexec,
+    LibexecRoot,
+    Bin,
+    Sbin,
+    Toplevel,
+    Share,
+    ShareRoot,
+    Etc,
+    Doc,
+    Stublibs,
+    Man,
+    Misc,
+}
+
+impl InstallSection {
+    pub const ALL: [InstallSection; 14] = [
+        InstallSection::Lib,
+        InstallSection::LibRoot,
+        InstallSection::Libexec,
+        InstallSection::LibexecRoot,
+        InstallSection::Bin,
+        InstallSection::Sbin,
+        InstallSection::Toplevel,
+        InstallSection::Share,
+        InstallSection::ShareRoot,
+        InstallSection::Etc,
+        InstallSection::Doc,
+        InstallSection::Stublibs,
+        InstallSection::Man,
+        InstallSection::Misc,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InstallSection::Lib => "lib",
+            InstallSection::LibRoot => "lib_root",
+            InstallSection::Libexec => "libexec",
+            InstallSection::LibexecRoot => "libexec_root",
+            InstallSection::Bin => "bin",
+            InstallSection::Sbin => "sbin",
+            InstallSection::Toplevel => "toplevel",
+            InstallSection::Share => "share",
+            InstallSection::ShareRoot => "share_root",
+            InstallSection::Etc => "etc",
+            InstallSection::Doc => "doc",
+            InstallSection::Stublibs => "stublibs",
+            InstallSection::Man => "man",
+            InstallSection::Misc => "misc",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|section| section.name() == name)
+    }
+}
+
+/// `"src"`, `"?src"` or `"src" {"dst"}`. A leading `?` marks a file that may be missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallEntry {
+    pub src: String,
+    pub optional: bool,
+    pub dst: Option<String>,
+}
+
+impl InstallEntry {
+    fn from_value(value: &Value, field: &str) -> Result<Self, FormatError> {
+        let (src, options) = options(value);
+        let src = string(src, field)?;
+        let (src, optional) = match src.strip_prefix('?') {
+            Some(src) => (src, true),
+            None => (src, false),
+        };
+        if src.is_empty() {
+            return Err(FormatError::new(field, "empty source path"));
+        }
+        let dst = match options.as_slice() {
+            [] => None,
+            [dst] => Some(string(dst, field)?.to_string()),
+            _ => return Err(FormatError::new(field, "expected a single destination")),
+        };
+        Ok(InstallEntry {
+            src: src.to_string(),
+            optional,
+            dst,
+        })
+    }
+
+    fn to_value(&self) -> Value {
+        let src = if self.optional {
+            format!("?{}", self.src)
+        } else {
+            self.src.clone()
+        };
+        match &self.dst {
+            Some(dst) => Value::Option(
+                Box::new(Value::String(src)),
+                vec![Box::new(Value::String(dst.clone()))],
+            ),
+            None => Value::String(src),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstallFile {
+    pub opam_version: Option<String>,
+    pub sections: IndexMap<InstallSection, Vec<InstallEntry>>,
+}
+
+impl InstallFile {
+    pub fn from_ast(ast: &OpamAST) -> Result<Self, FormatError> {
+        let mut install = InstallFile::default();
+        for (key, item) in &ast.items {
+            let value = match item.as_ref() {
+                Item::Variable(value) => value,
+                Item::Section { .. } => {
+                    return Err(FormatError::new(
+                        key,
+                        "unexpected section in an install file",
+                    ))
+                }
+            };
+            if key == "opam-version" {
+                install.opam_version = Some(string(value, key)?.to_string());
+                continue;
+            }
+            let section = InstallSection::from_name(key)
+                .ok_or_else(|| FormatError::new(key, "unknown install section"))?;
+            let entries = list(value)
+                .into_iter()
+                .map(|value| InstallEntry::from_value(value, key))
+                .collect::<Result<Vec<_>, _>>()?;
+            if section == InstallSection::Misc
+                && entries
+                    .iter()
+                    .any(|entry| !matches!(&entry.dst, Some(dst) if dst.starts_with('/')))
+            {
+                return Err(FormatError::new(
+                    key,
+                    "misc files need an absolute destination",
+                ));
+            }
+            install.sections.insert(section, entries);
+        }
+        Ok(install)
+    }
+
+    pub fn to_ast(&self) -> OpamAST {
+        let mut items = IndexMap::new();
+        if let Some(version) = &self.opam_version {
+            items.insert(
+                "opam-version".to_string(),
+                Box::new(Item::Variable(Value::String(version.clone()))),
+            );
+        }
+        for (section, entries) in &self.sections {
+            let values = entries
+                .iter()
+                .map(|entry| Box::new(entry.to_value()))
+                .collect();
+            items.insert(
+                section.name().to_string(),
+                Box::new(Item::Variable(Value::List(values))),
+            );
+        }
+        OpamAST { items }
+    }
+}
+
+/// Prints the install file in opam syntax.
+impl Display for InstallFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", OpamPrinter::new(&self.to_ast()))
+    }
+}
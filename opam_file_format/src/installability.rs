@@ -0,0 +1,92 @@
+//! Installability of every package version of a repository, as `opam admin check
+//! --installability` reports it.
+//!
+//! A version is installable when the [`Solver`] finds a solution installing it, possibly along
+//! with other packages that must be installed with it, such as a given compiler. Comparing the
+//! reports of two snapshots of a repository tells which versions a change broke.
+use rayon::prelude::*;
+use rayon::{ThreadPoolBuildError, ThreadPoolBuilder};
+use serde_json::{json, Value as JsonValue};
+
+use crate::formula::Dependency;
+use crate::graph::Node;
+use crate::solver::Solver;
+
+/// Whether each package version is installable, sorted by name and version.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub packages: Vec<(Node, bool)>,
+}
+
+impl Report {
+    /// Checks every package version of the solver on a pool of `jobs` threads, each along with
+    /// the dependencies of `with`. `jobs == 0` lets rayon pick the number of threads.
+    pub fn check(
+        solver: &Solver,
+        with: &[Dependency],
+        jobs: usize,
+    ) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(jobs).build()?;
+        let mut packages: Vec<(Node, bool)> = pool.install(|| {
+            solver
+                .nodes()
+                .par_iter()
+                .map(|node| {
+                    let mut request = vec![node.dependency()];
+                    request.extend_from_slice(with);
+                    (node.clone(), solver.satisfiable(&request))
+                })
+                .collect()
+        });
+        packages.sort_by(|(n1, _), (n2, _)| {
+            n1.name
+                .cmp(&n2.name)
+                .then_with(|| n1.version.cmp(&n2.version))
+        });
+        Ok(Report { packages })
+    }
+
+    pub fn installable(&self, node: &Node) -> Option<bool> {
+        self.packages
+            .binary_search_by(|(other, _)| {
+                other
+                    .name
+                    .cmp(&node.name)
+                    .then_with(|| other.version.cmp(&node.version))
+            })
+            .ok()
+            .map(|i| self.packages[i].1)
+    }
+
+    pub fn uninstallable(&self) -> impl Iterator<Item = &Node> + '_ {
+        self.packages
+            .iter()
+            .filter(|(_, installable)| !installable)
+            .map(|(node, _)| node)
+    }
+
+    /// The versions that are uninstallable here but were installable in `old`, or didn't
+    /// exist there.
+    pub fn broken_since(&self, old: &Report) -> Vec<&Node> {
+        self.uninstallable()
+            .filter(|node| old.installable(node) != Some(false))
+            .collect()
+    }
+
+    /// The versions that are installable here but weren't in `old`.
+    pub fn fixed_since(&self, old: &Report) -> Vec<&Node> {
+        self.packages
+            .iter()
+            .filter(|(node, installable)| *installable && old.installable(node) == Some(false))
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// The uninstallable versions as JSON, by their `name.version` atom.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "checked": self.packages.len(),
+            "uninstallable": self.uninstallable().map(Node::atom).collect::<Vec<_>>(),
+        })
+    }
+}
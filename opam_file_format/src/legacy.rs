@@ -0,0 +1,424 @@
+//! opam 1.x package metadata, split between `opam`, `descr` and `url` files, and its upgrade to
+//! a single opam 2.0 file.
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use indexmap::IndexMap;
+
+use crate::error::FormatError;
+use crate::fields::{
+    list, options, string, string_value, strings_field, strings_value, variable, Items,
+};
+use crate::lexer::{Logop, Relop};
+use crate::parser::{section_key, Item, OpamAST, Value};
+use crate::repository::{parse_source, RepositoryError};
+
+/// A `descr` file: a synopsis on the first line, followed by the long description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Descr {
+    pub synopsis: String,
+    pub description: Option<String>,
+}
+
+impl Descr {
+    pub fn parse(text: &str) -> Self {
+        let mut lines = text.splitn(2, '\n');
+        let synopsis = lines.next().unwrap_or("").trim().to_string();
+        let description = lines
+            .next()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .map(String::from);
+        Descr {
+            synopsis,
+            description,
+        }
+    }
+}
+
+/// Kind of source given in a legacy `url` file, by the name of the field holding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegacySource {
+    Archive,
+    Http,
+    Local,
+    Git,
+    Darcs,
+    Hg,
+}
+
+impl LegacySource {
+    const FIELDS: [(&'static str, LegacySource); 6] = [
+        ("archive", LegacySource::Archive),
+        ("http", LegacySource::Http),
+        ("local", LegacySource::Local),
+        ("git", LegacySource::Git),
+        ("darcs", LegacySource::Darcs),
+        ("hg", LegacySource::Hg),
+    ];
+}
+
+/// A `url` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyUrl {
+    pub kind: LegacySource,
+    pub address: String,
+    pub mirrors: Vec<String>,
+    /// Checksums as written, either `algo=hex` or a bare md5 hex digest.
+    pub checksum: Vec<String>,
+}
+
+impl LegacyUrl {
+    pub fn from_ast(ast: &OpamAST) -> Result<Self, FormatError> {
+        let mut sources = LegacySource::FIELDS
+            .iter()
+            .filter_map(|(field, kind)| ast.items.get(*field).map(|item| (*field, *kind, item)));
+        let (field, kind, item) = sources
+            .next()
+            .ok_or_else(|| FormatError::new("archive", "missing source field"))?;
+        if let Some((other, _, _)) = sources.next() {
+            return Err(FormatError::new(
+                other,
+                format!("conflicts with `{}`", field),
+            ));
+        }
+        let address = match item.as_ref() {
+            Item::Variable(value) => string(value, field)?.to_string(),
+            Item::Section { .. } => {
+                return Err(FormatError::new(field, "expected a field, found a section"))
+            }
+        };
+        Ok(LegacyUrl {
+            kind,
+            address,
+            mirrors: strings_field(&ast.items, "mirrors")?,
+            checksum: strings_field(&ast.items, "checksum")?,
+        })
+    }
+
+    /// The address in opam 2.0 `src` syntax, with the VCS given as a transport prefix.
+    pub fn src(&self) -> String {
+        let prefix = match self.kind {
+            LegacySource::Archive | LegacySource::Http => return self.address.clone(),
+            LegacySource::Local if self.address.starts_with('/') => "file://",
+            LegacySource::Local => return self.address.clone(),
+            LegacySource::Git if self.address.starts_with("git://") => return self.address.clone(),
+            LegacySource::Git => "git+",
+            LegacySource::Darcs => "darcs+",
+            LegacySource::Hg => "hg+",
+        };
+        format!("{}{}", prefix, self.address)
+    }
+
+    /// Checksums in `algo=hex` form. opam 1.x only knew md5, written without prefix.
+    pub fn checksums(&self) -> Vec<String> {
+        self.checksum
+            .iter()
+            .map(|checksum| {
+                if checksum.contains('=') {
+                    checksum.clone()
+                } else {
+                    format!("md5={}", checksum)
+                }
+            })
+            .collect()
+    }
+
+    fn to_section(&self) -> Item {
+        let mut items: Items = IndexMap::new();
+        items.insert("src".to_string(), string_value(&self.src()));
+        if !self.mirrors.is_empty() {
+            items.insert("mirrors".to_string(), strings_value(&self.mirrors));
+        }
+        let checksums = self.checksums();
+        match checksums.as_slice() {
+            [] => (),
+            [checksum] => {
+                items.insert("checksum".to_string(), string_value(checksum));
+            }
+            checksums => {
+                items.insert("checksum".to_string(), strings_value(checksums));
+            }
+        }
+        Item::Section { name: None, items }
+    }
+}
+
+/// Merges opam 1.x package metadata into a single opam 2.0 definition.
+///
+/// Like `opam admin upgrade`, this:
+/// - sets `opam-version` to `2.0`,
+/// - moves the `descr` file to `synopsis` and `description`, and the `url` file to a `url`
+///   section,
+/// - turns `ocaml-version`, and the `ocaml-version` constraints of `available`, into a dependency
+///   on the `ocaml` package, other uses of the `ocaml-version` variable becoming `ocaml:version`,
+/// - moves `build-test` and `build-doc` commands to `build` with `with-test` and `with-doc`
+///   filters, and renames the `test` and `doc` dependency flags the same way,
+/// - unwraps `available` from its list.
+///
+/// Other fields are kept as they are.
+pub fn upgrade(
+    opam: &OpamAST,
+    descr: Option<&Descr>,
+    url: Option<&LegacyUrl>,
+) -> Result<OpamAST, FormatError> {
+    let old = &opam.items;
+    let mut items: Items = IndexMap::new();
+    items.insert("opam-version".to_string(), string_value("2.0"));
+    for field in &["name", "version"] {
+        if let Some(item) = old.get(*field) {
+            items.insert(field.to_string(), item.clone());
+        }
+    }
+    if let Some(descr) = descr {
+        if !old.contains_key("synopsis") && !descr.synopsis.is_empty() {
+            items.insert("synopsis".to_string(), string_value(&descr.synopsis));
+        }
+        if let (false, Some(description)) = (old.contains_key("description"), &descr.description) {
+            items.insert("description".to_string(), string_value(description));
+        }
+    }
+
+    let mut ocaml_constraints = match variable(old, "ocaml-version")? {
+        Some(constraint) => list(constraint).into_iter().cloned().collect(),
+        None => vec![],
+    };
+    for (key, item) in old {
+        match key.as_str() {
+            "opam-version" | "name" | "version" | "ocaml-version" | "build-test" | "build-doc" => {}
+            "depends" | "depopts" => {
+                items.insert(key.clone(), Box::new(map_variable(item, rename_flags)));
+            }
+            "available" => match item.as_ref() {
+                Item::Variable(value) => {
+                    let (available, constraints) = split_ocaml_version(unwrap_available(value));
+                    ocaml_constraints.extend(constraints);
+                    if let Some(available) = available {
+                        items.insert(key.clone(), Box::new(Item::Variable(available)));
+                    }
+                }
+                Item::Section { .. } => {
+                    items.insert(key.clone(), item.clone());
+                }
+            },
+            _ => {
+                items.insert(key.clone(), item.clone());
+            }
+        }
+    }
+
+    if !ocaml_constraints.is_empty() {
+        let ocaml = Value::Option(
+            Box::new(Value::String("ocaml".to_string())),
+            vec![Box::new(conjunction(ocaml_constraints.iter().collect()))],
+        );
+        push_to_list(&mut items, "depends", ocaml);
+    }
+    for (field, filter) in &[("build-test", "with-test"), ("build-doc", "with-doc")] {
+        if let Some(commands) = variable(old, field)? {
+            if let Some(Item::Variable(build)) = items.get_mut("build").map(Box::as_mut) {
+                *build = Value::List(
+                    commands_of(build)
+                        .into_iter()
+                        .cloned()
+                        .map(Box::new)
+                        .collect(),
+                );
+            }
+            for command in commands_of(commands) {
+                let command = Value::Option(
+                    Box::new(command.clone()),
+                    vec![Box::new(Value::Ident(filter.to_string()))],
+                );
+                push_to_list(&mut items, "build", command);
+            }
+        }
+    }
+
+    if let Some(url) = url {
+        if !old.contains_key("url") {
+            items.insert(section_key("url", None), Box::new(url.to_section()));
+        }
+    }
+    Ok(OpamAST { items })
+}
+
+/// Reads `opam`, and `descr` and `url` if present, from a package directory and upgrades them.
+pub fn upgrade_dir(dir: &Path) -> Result<OpamAST, RepositoryError> {
+    let read = |name: &str| -> Result<Option<String>, RepositoryError> {
+        let path = dir.join(name);
+        match fs::read_to_string(&path) {
+            Ok(source) => Ok(Some(source)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RepositoryError::Io(path, e)),
+        }
+    };
+    let opam_path = dir.join("opam");
+    let opam_source = read("opam")?
+        .ok_or_else(|| RepositoryError::Io(opam_path.clone(), io::ErrorKind::NotFound.into()))?;
+    let opam = parse_source(&opam_path, opam_source)?;
+    let descr = read("descr")?.map(|text| Descr::parse(&text));
+    let url = match read("url")? {
+        Some(source) => {
+            let path = dir.join("url");
+            let ast = parse_source(&path, source)?;
+            Some(LegacyUrl::from_ast(&ast).map_err(|e| RepositoryError::Format(path, e))?)
+        }
+        None => None,
+    };
+    upgrade(&opam, descr.as_ref(), url.as_ref()).map_err(|e| RepositoryError::Format(opam_path, e))
+}
+
+fn map_variable(item: &Item, f: fn(&Value) -> Value) -> Item {
+    match item {
+        Item::Variable(value) => Item::Variable(f(value)),
+        item => item.clone(),
+    }
+}
+
+fn push_to_list(items: &mut Items, field: &str, value: Value) {
+    let item = items
+        .entry(field.to_string())
+        .or_insert_with(|| Box::new(Item::Variable(Value::List(vec![]))));
+    if let Item::Variable(existing) = item.as_mut() {
+        match existing {
+            Value::List(values) => values.push(Box::new(value)),
+            single => *single = Value::List(vec![Box::new(single.clone()), Box::new(value)]),
+        }
+    }
+}
+
+// A list of commands may be written as a single command, e.g. `build: [make "all"]`.
+fn commands_of(value: &Value) -> Vec<&Value> {
+    let is_command = |value: &Value| matches!(options(value).0, Value::List(_));
+    match value {
+        Value::List(values) if values.iter().all(|value| is_command(value)) => list(value),
+        value => vec![value],
+    }
+}
+
+fn conjunction(values: Vec<&Value>) -> Value {
+    let mut values = values.into_iter().rev().cloned();
+    let last = values.next().unwrap_or(Value::Bool(true));
+    values.fold(last, |acc, value| {
+        Value::Logop(Logop::And, Box::new(value), Box::new(acc))
+    })
+}
+
+// opam 1.2 wrote `available` as a list of filters that must all hold. The filter is returned with
+// its operators nested by precedence, so that its conjuncts can be told apart, and alternatives
+// joined with `&` are grouped.
+pub(crate) fn unwrap_available(value: &Value) -> Value {
+    match value {
+        Value::List(values) if values.len() > 1 => {
+            let values: Vec<Value> = values
+                .iter()
+                .map(|value| match value.with_precedence() {
+                    value @ Value::Logop(Logop::Or, ..) => Value::Group(vec![Box::new(value)]),
+                    value => value,
+                })
+                .collect();
+            conjunction(values.iter().collect())
+        }
+        Value::List(values) => {
+            conjunction(values.iter().map(Box::as_ref).collect()).with_precedence()
+        }
+        value => value.with_precedence(),
+    }
+}
+
+// `{test}` and `{doc}` flags of opam 1.2 dependencies became the `with-test` and `with-doc`
+// variables.
+fn rename_flags(value: &Value) -> Value {
+    let boxed = |v: &Value| Box::new(rename_flags(v));
+    match value {
+        Value::Ident(id) if id == "test" => Value::Ident("with-test".to_string()),
+        Value::Ident(id) if id == "doc" => Value::Ident("with-doc".to_string()),
+        Value::List(values) => Value::List(values.iter().map(|v| boxed(v)).collect()),
+        Value::Group(values) => Value::Group(values.iter().map(|v| boxed(v)).collect()),
+        Value::Option(v, options) => {
+            Value::Option(boxed(v), options.iter().map(|v| boxed(v)).collect())
+        }
+        Value::Logop(op, v1, v2) => Value::Logop(*op, boxed(v1), boxed(v2)),
+        Value::Pfxop(op, v) => Value::Pfxop(*op, boxed(v)),
+        Value::Relop(op, v1, v2) => Value::Relop(*op, boxed(v1), boxed(v2)),
+        value => value.clone(),
+    }
+}
+
+// The `ocaml-version` variable of opam 1.2 filters. Its constraints in `available`, such as
+// `ocaml-version >= "4.02"`, became constraints on the `ocaml` dependency, and its other uses the
+// `ocaml:version` variable. Returns what is left of `available`, if anything, and the
+// constraints.
+fn split_ocaml_version(available: Value) -> (Option<Value>, Vec<Value>) {
+    let mut constraints = vec![];
+    let mut rest = vec![];
+    for value in conjuncts(available) {
+        match ocaml_version_constraint(&value) {
+            Some(constraint) => constraints.push(constraint),
+            None => rest.push(rename_ocaml_version(&value)),
+        }
+    }
+    let available = if rest.is_empty() {
+        None
+    } else {
+        Some(conjunction(rest.iter().collect()))
+    };
+    (available, constraints)
+}
+
+fn conjuncts(value: Value) -> Vec<Value> {
+    match value {
+        Value::Logop(Logop::And, v1, v2) => {
+            let mut values = conjuncts(*v1);
+            values.extend(conjuncts(*v2));
+            values
+        }
+        value => vec![value],
+    }
+}
+
+fn is_ocaml_version(value: &Value) -> bool {
+    matches!(value, Value::Ident(id) if id == "ocaml-version")
+}
+
+// `ocaml-version >= "4.02"` as the `>= "4.02"` constraint, the other way round too.
+fn ocaml_version_constraint(value: &Value) -> Option<Value> {
+    match value {
+        Value::Relop(op, v1, v2)
+            if is_ocaml_version(v1) && matches!(v2.as_ref(), Value::String(_)) =>
+        {
+            Some(Value::PrefixRelop(*op, v2.clone()))
+        }
+        Value::Relop(op, v1, v2)
+            if is_ocaml_version(v2) && matches!(v1.as_ref(), Value::String(_)) =>
+        {
+            let op = match op {
+                Relop::Geq => Relop::Leq,
+                Relop::Gt => Relop::Lt,
+                Relop::Leq => Relop::Geq,
+                Relop::Lt => Relop::Gt,
+                op => *op,
+            };
+            Some(Value::PrefixRelop(op, v1.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn rename_ocaml_version(value: &Value) -> Value {
+    let boxed = |v: &Value| Box::new(rename_ocaml_version(v));
+    match value {
+        value if is_ocaml_version(value) => Value::Ident("ocaml:version".to_string()),
+        Value::List(values) => Value::List(values.iter().map(|v| boxed(v)).collect()),
+        Value::Group(values) => Value::Group(values.iter().map(|v| boxed(v)).collect()),
+        Value::Option(v, options) => {
+            Value::Option(boxed(v), options.iter().map(|v| boxed(v)).collect())
+        }
+        Value::Logop(op, v1, v2) => Value::Logop(*op, boxed(v1), boxed(v2)),
+        Value::Pfxop(op, v) => Value::Pfxop(*op, boxed(v)),
+        Value::Relop(op, v1, v2) => Value::Relop(*op, boxed(v1), boxed(v2)),
+        value => value.clone(),
+    }
+}
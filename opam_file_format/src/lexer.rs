@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::{char, u32};
 
 use logos::{Lexer, Logos};
@@ -46,7 +47,7 @@ enum CommentToken {
     Error,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Relop {
     Eq,
     Neq,
@@ -56,13 +57,13 @@ pub enum Relop {
     Lt,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Pfxop {
     Not,
     Defined,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Envop {
     Eq,
     PlusEq,
@@ -72,13 +73,16 @@ pub enum Envop {
     EqColon,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Logop {
     And,
     Or,
 }
 
-fn parse_relop(lex: &mut Lexer<Token>) -> Option<Relop> {
+fn parse_relop<'a, T>(lex: &mut Lexer<'a, T>) -> Option<Relop>
+where
+    T: Logos<'a, Source = str>,
+{
     match lex.slice() {
         "=" => Some(Relop::Eq),
         "!=" => Some(Relop::Neq),
@@ -91,7 +95,10 @@ fn parse_relop(lex: &mut Lexer<Token>) -> Option<Relop> {
     }
 }
 
-fn parse_pfxop(lex: &mut Lexer<Token>) -> Option<Pfxop> {
+fn parse_pfxop<'a, T>(lex: &mut Lexer<'a, T>) -> Option<Pfxop>
+where
+    T: Logos<'a, Source = str>,
+{
     match lex.slice() {
         "!" => Some(Pfxop::Not),
         "?" => Some(Pfxop::Defined),
@@ -99,7 +106,10 @@ fn parse_pfxop(lex: &mut Lexer<Token>) -> Option<Pfxop> {
     }
 }
 
-fn parse_envop(lex: &mut Lexer<Token>) -> Option<Envop> {
+fn parse_envop<'a, T>(lex: &mut Lexer<'a, T>) -> Option<Envop>
+where
+    T: Logos<'a, Source = str>,
+{
     match lex.slice() {
         "=" => Some(Envop::Eq),
         "+=" => Some(Envop::PlusEq),
@@ -111,7 +121,10 @@ fn parse_envop(lex: &mut Lexer<Token>) -> Option<Envop> {
     }
 }
 
-fn parse_logop(lex: &mut Lexer<Token>) -> Option<Logop> {
+fn parse_logop<'a, T>(lex: &mut Lexer<'a, T>) -> Option<Logop>
+where
+    T: Logos<'a, Source = str>,
+{
     match lex.slice() {
         "&" => Some(Logop::And),
         "|" => Some(Logop::Or),
@@ -151,9 +164,20 @@ where
 }
 
 macro_rules! fn_parse_string {
-    ($func_name:ident, $token_type: ident) => {
-        fn $func_name(lex: &mut Lexer<Token>) -> Option<String> {
+    ($func_name:ident, $token_type: ident, $eos: expr) => {
+        fn $func_name<'a, T>(lex: &mut Lexer<'a, T>) -> Option<Cow<'a, str>>
+        where
+            T: Logos<'a, Source = str>,
+        {
             let remainder = lex.remainder();
+            // Strings without escapes or CRLFs are returned as a slice of the source.
+            if let Some(end) = remainder.find($eos) {
+                let raw = &remainder[..end];
+                if !raw.contains(&['\\', '\r'][..]) {
+                    lex.bump(end + $eos.len());
+                    return Some(Cow::Borrowed(raw));
+                }
+            }
             let mut string_lexer: Lexer<$token_type> = $token_type::lexer(remainder);
             let mut result = String::new();
             loop {
@@ -168,15 +192,26 @@ macro_rules! fn_parse_string {
                 };
             }
             lex.bump(string_lexer.span().end);
-            Some(String::from(result))
+            Some(Cow::Owned(result))
         }
     };
 }
 
-fn_parse_string!(parse_string, StringToken);
-fn_parse_string!(parse_string_triple, StringTripleToken);
+fn_parse_string!(parse_string, StringToken, "\"");
+fn_parse_string!(parse_string_triple, StringTripleToken, "\"\"\"");
+
+fn parse_string_owned(lex: &mut Lexer<Token>) -> Option<String> {
+    parse_string(lex).map(Cow::into_owned)
+}
+
+fn parse_string_triple_owned(lex: &mut Lexer<Token>) -> Option<String> {
+    parse_string_triple(lex).map(Cow::into_owned)
+}
 
-fn parse_comment(lex: &mut Lexer<Token>) -> bool {
+fn parse_comment<'a, T>(lex: &mut Lexer<'a, T>) -> bool
+where
+    T: Logos<'a, Source = str>,
+{
     let remainder = lex.remainder();
     let mut comment_lex: Lexer<CommentToken> = CommentToken::lexer(remainder);
     let mut counter = 1;
@@ -262,7 +297,10 @@ fn parse_id_lodash(input: &str) -> Option<usize> {
     }
 }
 
-fn match_ident(lodash: bool, lex: &mut Lexer<Token>) -> Option<String> {
+fn match_ident<'a, T>(lodash: bool, lex: &mut Lexer<'a, T>) -> Option<&'a str>
+where
+    T: Logos<'a, Source = str>,
+{
     // group1: (id|_)
     if !lodash {
         if let Some(pos) = parse_id(lex.remainder()) {
@@ -301,20 +339,24 @@ fn match_ident(lodash: bool, lex: &mut Lexer<Token>) -> Option<String> {
     if let Some(pos) = re_colon(&remainder[remaining_pos..]) {
         remaining_pos += pos;
     } else {
-        return Some(String::from(lex.slice()));
+        return Some(lex.slice());
     };
 
     // id
     if let Some(pos) = parse_id(&remainder[remaining_pos..]) {
         remaining_pos += pos;
     } else {
-        return Some(String::from(lex.slice()));
+        return Some(lex.slice());
     }
     lex.bump(remaining_pos);
-    Some(String::from(lex.slice()))
+    Some(lex.slice())
 }
 
-#[derive(Logos, Debug, PartialEq)]
+fn match_ident_owned(lodash: bool, lex: &mut Lexer<Token>) -> Option<String> {
+    match_ident(lodash, lex).map(String::from)
+}
+
+#[derive(Logos, Debug, Clone, PartialEq)]
 pub enum Token {
     #[token(":")]
     COLON,
@@ -330,8 +372,8 @@ pub enum Token {
     LPAR,
     #[token(")")]
     RPAR,
-    #[token("\"", parse_string)]
-    #[token("\"\"\"", parse_string_triple)]
+    #[token("\"", parse_string_owned)]
+    #[token("\"\"\"", parse_string_triple_owned)]
     STRING(String),
     #[token("(*", parse_comment)]
     #[regex(r"#[^\n]*")]
@@ -342,8 +384,8 @@ pub enum Token {
     #[regex(r"-?[0-9_]+", | lex | lex.slice().parse(), priority = 2)]
     INT(i64),
     // Some dirty hacks to work around logos bug
-    #[regex(r"[\s\S]", partial!(match_ident => false, _), priority = 0)]
-    #[token("_", partial!(match_ident=> true, _), priority = 3)]
+    #[regex(r"[\s\S]", partial!(match_ident_owned => false, _), priority = 0)]
+    #[token("_", partial!(match_ident_owned => true, _), priority = 3)]
     IDENT(String),
     #[regex(r"(!?=|[<>]=?|~)", parse_relop)]
     RELOP(Relop),
@@ -359,7 +401,53 @@ pub enum Token {
     Error,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Zero-copy counterpart of [`Token`]. Identifiers and strings without escapes borrow from the
+/// source instead of allocating.
+#[derive(Logos, Debug, PartialEq)]
+pub enum BorrowedToken<'a> {
+    #[token(":")]
+    COLON,
+    #[token("{")]
+    LBRACE,
+    #[token("}")]
+    RBRACE,
+    #[token("[")]
+    LBRACKET,
+    #[token("]")]
+    RBRACKET,
+    #[token("(")]
+    LPAR,
+    #[token(")")]
+    RPAR,
+    #[token("\"", parse_string)]
+    #[token("\"\"\"", parse_string_triple)]
+    STRING(Cow<'a, str>),
+    #[token("(*", parse_comment)]
+    #[regex(r"#[^\n]*")]
+    COMMENT,
+    #[token("true", | lex | lex.slice().parse())]
+    #[token("false", | lex | lex.slice().parse())]
+    BOOL(bool),
+    #[regex(r"-?[0-9_]+", | lex | lex.slice().parse(), priority = 2)]
+    INT(i64),
+    #[regex(r"[\s\S]", partial!(match_ident => false, _), priority = 0)]
+    #[token("_", partial!(match_ident => true, _), priority = 3)]
+    IDENT(&'a str),
+    #[regex(r"(!?=|[<>]=?|~)", parse_relop)]
+    RELOP(Relop),
+    #[regex(r"&|\|", parse_logop)]
+    LOGOP(Logop),
+    #[regex(r"!|\?", parse_pfxop)]
+    PFXOP(Pfxop),
+    #[regex(r"[\+?]=|=[\+?]=?", parse_envop)]
+    ENVOP(Envop),
+    #[regex(r"[ \t\r\n]", logos::skip)]
+    SKIP,
+    #[error]
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -381,3 +469,21 @@ pub fn lex(input: &str) -> Result<Vec<(Token, Span)>, Span> {
     }
     Ok(result)
 }
+
+/// Spans of the comments of `input`, which [`lex`] drops.
+pub fn comments(input: &str) -> Result<Vec<Span>, Span> {
+    let lexer: Lexer<Token> = Token::lexer(input);
+    let mut result = vec![];
+    for (token, span) in lexer.spanned() {
+        let span = Span {
+            start: span.start,
+            end: span.end,
+        };
+        match token {
+            Token::Error => return Err(span),
+            Token::COMMENT => result.push(span),
+            _ => (),
+        }
+    }
+    Ok(result)
+}
@@ -1,9 +1,41 @@
 #![deny(unsafe_code)]
 #![allow(unused_braces)]
+pub mod borrowed;
+pub mod bulk;
+pub mod checksum;
+pub mod config;
+pub mod diff;
+pub mod edit;
+pub mod error;
+mod fields;
+pub mod filter;
+pub mod format_version;
+pub mod formatter;
+pub mod formula;
+pub mod graph;
+pub mod incremental;
+pub mod index;
+pub mod install;
+pub mod installability;
+pub mod legacy;
 pub mod lexer;
+pub mod lint;
+pub mod merge;
+pub mod package;
 pub mod parser;
 pub mod printer;
+pub mod query;
+pub mod repository;
+pub mod schema;
+pub mod solver;
+pub mod spdx;
+pub mod switch;
+pub mod url;
+pub mod version;
 
+pub use error::Error;
 pub use lexer::lex;
-pub use parser::{OpamAST, parse};
-pub use printer::JsonPrinter;
\ No newline at end of file
+pub use package::PackageName;
+pub use parser::{OpamAST, parse, parse_str};
+pub use printer::{JsonPrinter, OpamPrinter};
+pub use version::OpamVersion;
\ No newline at end of file
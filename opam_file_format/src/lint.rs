@@ -0,0 +1,512 @@
+//! Checks on package definitions, in the spirit of `opam lint`.
+//!
+//! Findings carry the code `opam lint` uses for the same check, so that they can be silenced or
+//! looked up the same way.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::error::Error;
+use crate::format_version::FormatVersion;
+use crate::lexer::{Logop, Span};
+use crate::parser::{parse_str_with_spans, Item, ItemSpan, OpamAST, SourceMap, Value, ValueSpan};
+use crate::schema::{Kind, Problem, Schema};
+use crate::spdx::LicenseExpr;
+use crate::url::{dev_repo, url};
+
+/// Variables that dependency filters may use: dependency flags and global variables. Package
+/// variables such as `ocaml:version` are allowed too.
+const DEPENDS_VARIABLES: [&str; 13] = [
+    "build",
+    "post",
+    "dev",
+    "with-test",
+    "with-doc",
+    "with-dev-setup",
+    "version",
+    "arch",
+    "os",
+    "os-distribution",
+    "os-family",
+    "os-version",
+    "opam-version",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    pub code: u32,
+    pub level: Level,
+    pub message: String,
+    /// Where the offending item or value is. Missing fields have no span.
+    pub span: Option<Span>,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            Level::Warning => "warning",
+            Level::Error => "error",
+        };
+        write!(f, "{} {}: {}", level, self.code, self.message)
+    }
+}
+
+/// Parses and lints a package definition.
+pub fn lint_str(source: &str) -> Result<Vec<Lint>, Error> {
+    let (ast, spans) = parse_str_with_spans(source)?;
+    Ok(lint(&ast, &spans))
+}
+
+/// Lints a package definition, `spans` locating its items. Findings are sorted by code.
+pub fn lint(ast: &OpamAST, spans: &SourceMap) -> Vec<Lint> {
+    let version = FormatVersion::declared(ast).unwrap_or(FormatVersion::LATEST);
+    let schema = version.schema();
+    let mut linter = Linter {
+        ast,
+        spans,
+        version,
+        schema: &schema,
+        malformed: vec![],
+        lints: vec![],
+    };
+    linter.check_fields();
+    linter.check_opam_version();
+    linter.check_metadata();
+    linter.check_dependencies();
+    linter.check_sources();
+    linter.check_interpolations();
+    linter.lints.sort_by_key(|lint| lint.code);
+    linter.lints
+}
+
+struct Linter<'a> {
+    ast: &'a OpamAST,
+    spans: &'a SourceMap,
+    /// The declared version, whose rules apply.
+    version: FormatVersion,
+    schema: &'a Schema,
+    /// Top-level items the schema rejected, so that typed checks don't report them again.
+    malformed: Vec<String>,
+    lints: Vec<Lint>,
+}
+
+impl<'a> Linter<'a> {
+    fn report(&mut self, code: u32, level: Level, message: impl Into<String>, span: Option<Span>) {
+        self.lints.push(Lint {
+            code,
+            level,
+            message: message.into(),
+            span,
+        });
+    }
+
+    fn value(&self, field: &str) -> Option<&'a Value> {
+        match self.ast.items.get(field).map(Box::as_ref) {
+            Some(Item::Variable(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn item_span(&self, key: &str) -> Option<Span> {
+        self.spans.items.get(key).map(|span| span.span)
+    }
+
+    fn value_span(&self, field: &str) -> Option<&'a ValueSpan> {
+        self.spans
+            .items
+            .get(field)
+            .and_then(|span| span.value.as_ref())
+    }
+
+    fn string(&self, field: &str) -> Option<&'a str> {
+        match self.value(field) {
+            Some(Value::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn is_malformed(&self, key: &str) -> bool {
+        self.malformed.iter().any(|item| item == key)
+    }
+
+    fn check_fields(&mut self) {
+        for violation in self.version.validate(self.ast, self.spans) {
+            let item = violation
+                .field
+                .split('.')
+                .next()
+                .unwrap_or(&violation.field);
+            self.malformed.push(item.to_string());
+            match violation.problem {
+                Problem::UnknownField => self.report(
+                    3,
+                    Level::Error,
+                    format!("Invalid field '{}'", violation.field),
+                    violation.span,
+                ),
+                Problem::Expected(expected) => self.report(
+                    2,
+                    Level::Error,
+                    format!(
+                        "File format error in '{}': expected {}",
+                        violation.field, expected
+                    ),
+                    violation.span,
+                ),
+                Problem::Unsupported { construct, since } => self.report(
+                    2,
+                    Level::Error,
+                    format!(
+                        "File format error in '{}': {} requires opam-version \"{}\"",
+                        violation.field, construct, since
+                    ),
+                    violation.span,
+                ),
+            }
+        }
+        if self.ast.items.contains_key("ocaml-version") {
+            self.report(
+                32,
+                Level::Error,
+                "Field 'ocaml-version' is deprecated, use a dependency towards the 'ocaml' package instead",
+                self.item_span("ocaml-version"),
+            );
+        }
+        if self.ast.items.contains_key("os") {
+            self.report(
+                33,
+                Level::Error,
+                "Field 'os' is deprecated, use 'available' and the 'os' variable instead",
+                self.item_span("os"),
+            );
+        }
+        for field in &["build-test", "build-doc"] {
+            if self.ast.items.contains_key(*field) {
+                self.report(
+                    48,
+                    Level::Warning,
+                    format!(
+                        "Field '{}' is deprecated, use the 'with-test' and 'with-doc' filter variables \
+                         in 'build' and 'install', and the 'run-test' field",
+                        field
+                    ),
+                    self.item_span(field),
+                );
+            }
+        }
+    }
+
+    fn check_opam_version(&mut self) {
+        let version = match self.string("opam-version") {
+            Some(version) => version,
+            None => return,
+        };
+        let span = self.value_span("opam-version").map(|span| span.span);
+        let parts: Vec<&str> = version.split('.').collect();
+        match FormatVersion::parse(version) {
+            None => self.report(
+                21,
+                Level::Error,
+                format!(
+                    "Field 'opam-version' is \"{}\", validation assumes opam {} and may not be accurate",
+                    version,
+                    FormatVersion::LATEST
+                ),
+                span,
+            ),
+            Some(FormatVersion::V1_2) => self.report(
+                21,
+                Level::Error,
+                format!(
+                    "Field 'opam-version' is \"{}\", opam 2 only reads such files by upgrading them",
+                    version
+                ),
+                span,
+            ),
+            Some(_) => (),
+        }
+        if parts.len() > 2 {
+            self.report(
+                20,
+                Level::Warning,
+                "Field 'opam-version' refers to the patch version of opam, it should be of the form MAJOR.MINOR",
+                span,
+            );
+        }
+    }
+
+    fn check_metadata(&mut self) {
+        let schema = self.schema;
+        let fields = schema
+            .fields()
+            .filter(|(_, kind)| matches!(kind, Kind::String | Kind::Strings));
+        for (field, _) in fields {
+            let empty = match self.value(field) {
+                Some(Value::String(s)) => s.trim().is_empty(),
+                Some(Value::List(values)) => values.is_empty(),
+                _ => false,
+            };
+            if empty {
+                self.report(
+                    22,
+                    Level::Error,
+                    format!("Field '{}' is present but empty; remove or fill it", field),
+                    self.item_span(field),
+                );
+            }
+        }
+
+        match self.value("maintainer") {
+            None => self.report(23, Level::Error, "Missing field 'maintainer'", None),
+            Some(maintainer) => {
+                let old_default = maintainer
+                    .children()
+                    .into_iter()
+                    .chain(std::iter::once(maintainer))
+                    .any(|v| matches!(v, Value::String(s) if s == "contact@ocamlpro.com"));
+                if old_default {
+                    self.report(
+                        24,
+                        Level::Error,
+                        "Field 'maintainer' has the old default value",
+                        self.item_span("maintainer"),
+                    );
+                }
+            }
+        }
+        if self.value("authors").is_none() && self.value("author").is_none() {
+            self.report(25, Level::Warning, "Missing field 'authors'", None);
+        }
+        for (code, field) in &[(35, "homepage"), (36, "bug-reports"), (37, "dev-repo")] {
+            if self.value(field).is_none() {
+                self.report(
+                    *code,
+                    Level::Warning,
+                    format!("Missing field '{}'", field),
+                    None,
+                );
+            }
+        }
+
+        if let Some(synopsis) = self.string("synopsis") {
+            if synopsis.starts_with(|c: char| c.is_lowercase())
+                || synopsis.trim_end().ends_with('.')
+            {
+                self.report(
+                    47,
+                    Level::Warning,
+                    "Synopsis should start with a capital and not end with a dot",
+                    self.value_span("synopsis").map(|span| span.span),
+                );
+            }
+        }
+        let blank = |s: Option<&str>| s.is_none_or(|s| s.trim().is_empty());
+        if blank(self.string("synopsis")) && blank(self.string("description")) {
+            self.report(
+                57,
+                Level::Error,
+                "Synopsis and description must not be both empty",
+                None,
+            );
+        }
+
+        if let (Some(license), Some(span)) = (self.value("license"), self.value_span("license")) {
+            for (value, span) in list_with_spans(license, span) {
+                if let Value::String(license) = value {
+                    if let Err(e) = LicenseExpr::parse_checked(license) {
+                        self.report(
+                            62,
+                            Level::Warning,
+                            format!(
+                                "License '{}' doesn't adhere to the SPDX standard: {}",
+                                license, e.message
+                            ),
+                            Some(span.span),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_dependencies(&mut self) {
+        for field in &["depends", "depopts"] {
+            let (value, span) = match (self.value(field), self.value_span(field)) {
+                (Some(value), Some(span)) => (value, span),
+                _ => continue,
+            };
+            let mut variables = vec![];
+            formula_variables(value, span, &mut variables);
+            for (name, span) in variables {
+                if name.contains(':') || DEPENDS_VARIABLES.contains(&name) {
+                    continue;
+                }
+                // The `test` and `doc` flags of opam 1.2 became variables.
+                let hint = match name {
+                    "test" | "doc" => format!(", use 'with-{}' instead", name),
+                    _ => String::new(),
+                };
+                self.report(
+                    29,
+                    Level::Error,
+                    format!(
+                        "Filter of field '{}' uses '{}', which is not a dependency flag or global variable{}",
+                        field, name, hint
+                    ),
+                    Some(span),
+                );
+            }
+        }
+
+        if let (Some(conflicts), Some(span)) =
+            (self.value("conflicts"), self.value_span("conflicts"))
+        {
+            for (value, span) in list_with_spans(conflicts, span) {
+                if has_conjunction(value) {
+                    self.report(
+                        43,
+                        Level::Error,
+                        "Conjunction used in 'conflicts' field, only '|' is allowed",
+                        Some(span.span),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_sources(&mut self) {
+        match dev_repo(self.ast) {
+            Ok(Some(dev_repo)) if !dev_repo.kind.is_vcs() => self.report(
+                42,
+                Level::Error,
+                "Field 'dev-repo' doesn't use version control, use URLs of the form \"git+https://\", \
+                 \"hg+https://\" or \"git://\"",
+                self.value_span("dev-repo").map(|span| span.span),
+            ),
+            Ok(_) => (),
+            Err(_) if self.is_malformed("dev-repo") => (),
+            Err(e) => self.report(
+                2,
+                Level::Error,
+                format!("File format error in 'dev-repo': {}", e.message),
+                self.value_span("dev-repo").map(|span| span.span),
+            ),
+        }
+        match url(self.ast) {
+            Ok(Some(url)) if url.checksum.is_empty() && !url.src.kind.is_vcs() => self.report(
+                59,
+                Level::Warning,
+                "Field 'url' has no checksum",
+                self.item_span("url"),
+            ),
+            Ok(_) => (),
+            Err(_) if self.is_malformed("url") => (),
+            Err(e) => self.report(
+                2,
+                Level::Error,
+                format!("File format error in 'url': {}", e),
+                self.item_span("url"),
+            ),
+        }
+    }
+
+    fn check_interpolations(&mut self) {
+        let mut unclosed = vec![];
+        for (key, item) in &self.ast.items {
+            if let (Item::Variable(value), Some(span)) = (item.as_ref(), self.spans.items.get(key))
+            {
+                unclosed_interpolations(value, span, &mut unclosed);
+            }
+        }
+        for span in unclosed {
+            self.report(
+                28,
+                Level::Error,
+                "Unclosed variable interpolation in string",
+                Some(span),
+            );
+        }
+    }
+}
+
+/// Elements of a value that may be a single element or a list, with their spans.
+fn list_with_spans<'a>(value: &'a Value, span: &'a ValueSpan) -> Vec<(&'a Value, &'a ValueSpan)> {
+    match value {
+        Value::List(_) => value.children().into_iter().zip(&span.children).collect(),
+        value => vec![(value, span)],
+    }
+}
+
+/// Variables used in the filters of a package formula such as `"foo" {>= "1.0" & with-test}`.
+fn formula_variables<'a>(value: &'a Value, span: &ValueSpan, variables: &mut Vec<(&'a str, Span)>) {
+    match value {
+        Value::Option(..) => {
+            let children = value.children().into_iter().zip(&span.children);
+            for (i, (child, span)) in children.enumerate() {
+                if i == 0 {
+                    formula_variables(child, span, variables);
+                } else {
+                    filter_variables(child, span, variables);
+                }
+            }
+        }
+        Value::List(_) | Value::Group(_) | Value::Logop(..) | Value::Pfxop(..) => {
+            for (child, span) in value.children().into_iter().zip(&span.children) {
+                formula_variables(child, span, variables);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn filter_variables<'a>(value: &'a Value, span: &ValueSpan, variables: &mut Vec<(&'a str, Span)>) {
+    match value {
+        Value::Ident(name) => variables.push((name, span.span)),
+        value => {
+            for (child, span) in value.children().into_iter().zip(&span.children) {
+                filter_variables(child, span, variables);
+            }
+        }
+    }
+}
+
+fn has_conjunction(value: &Value) -> bool {
+    match value {
+        Value::Logop(Logop::And, ..) => true,
+        Value::Option(value, _) => has_conjunction(value),
+        value => value.children().into_iter().any(has_conjunction),
+    }
+}
+
+fn unclosed_interpolations(value: &Value, span: &ItemSpan, unclosed: &mut Vec<Span>) {
+    fn walk(value: &Value, span: &ValueSpan, unclosed: &mut Vec<Span>) {
+        match value {
+            Value::String(s) => {
+                let mut rest = s.as_str();
+                while let Some(start) = rest.find("%{") {
+                    rest = &rest[start + 2..];
+                    match rest.find("}%") {
+                        Some(end) => rest = &rest[end + 2..],
+                        None => {
+                            unclosed.push(span.span);
+                            return;
+                        }
+                    }
+                }
+            }
+            value => {
+                for (child, span) in value.children().into_iter().zip(&span.children) {
+                    walk(child, span, unclosed);
+                }
+            }
+        }
+    }
+    if let Some(span) = &span.value {
+        walk(value, span, unclosed);
+    }
+}
@@ -0,0 +1,280 @@
+//! Three-way merges of package definitions, e.g. to rebase a local overlay onto an upstream
+//! update.
+//!
+//! Fields are merged one by one, and so are the items of sections and the dependencies of
+//! formulas such as `depends`: a change made on one side only is taken, and changes made on
+//! both sides conflict unless they are the same. Conflicting parts keep our side in the merged
+//! definition, which [`OpamPrinter`](crate::printer::OpamPrinter) prints back.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+
+use crate::fields::Items;
+use crate::parser::{Item, OpamAST, Value};
+use crate::schema::{Kind, Schema};
+
+#[derive(Debug, Clone)]
+pub enum Conflict {
+    /// A field changed differently on both sides, `None` meaning removed. Fields of sections
+    /// are named `section.field`.
+    Field {
+        field: String,
+        base: Option<Item>,
+        ours: Option<Item>,
+        theirs: Option<Item>,
+    },
+    /// A dependency of a formula changed differently on both sides.
+    Dependency {
+        field: String,
+        name: String,
+        base: Option<Value>,
+        ours: Option<Value>,
+        theirs: Option<Value>,
+    },
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fn side<T: Display>(value: &Option<T>) -> String {
+            value.as_ref().map_or("absent".to_string(), T::to_string)
+        }
+        fn item(item: &Option<Item>) -> String {
+            match item {
+                Some(Item::Variable(value)) => value.to_string(),
+                Some(Item::Section { .. }) => "a section".to_string(),
+                None => "absent".to_string(),
+            }
+        }
+        match self {
+            Conflict::Field {
+                field,
+                base,
+                ours,
+                theirs,
+            } => write!(
+                f,
+                "conflict in `{}`: base {}, ours {}, theirs {}",
+                field,
+                item(base),
+                item(ours),
+                item(theirs)
+            ),
+            Conflict::Dependency {
+                field,
+                name,
+                base,
+                ours,
+                theirs,
+            } => write!(
+                f,
+                "conflict in `{}` on \"{}\": base {}, ours {}, theirs {}",
+                field,
+                name,
+                side(base),
+                side(ours),
+                side(theirs)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Merged {
+    /// The merged definition, conflicting parts being taken from our side.
+    pub ast: OpamAST,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Merged {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merges the changes `ours` and `theirs` made to `base`.
+pub fn merge(base: &OpamAST, ours: &OpamAST, theirs: &OpamAST) -> Merged {
+    let mut conflicts = vec![];
+    let items = merge_items(
+        &Schema::opam(),
+        "",
+        &base.items,
+        &ours.items,
+        &theirs.items,
+        &mut conflicts,
+    );
+    Merged {
+        ast: OpamAST { items },
+        conflicts,
+    }
+}
+
+// The value of a part changed on one side only, or the same way on both, `None` meaning absent.
+// `Err` when both sides changed it differently.
+fn three_way<T: PartialEq + Clone>(
+    base: Option<&T>,
+    ours: Option<&T>,
+    theirs: Option<&T>,
+) -> Result<Option<T>, ()> {
+    if ours == theirs || base == theirs {
+        Ok(ours.cloned())
+    } else if base == ours {
+        Ok(theirs.cloned())
+    } else {
+        Err(())
+    }
+}
+
+// Merges keyed parts, keeping our order. Parts only they added go after the part preceding them
+// on their side.
+fn merge_keyed<K: Hash + Eq + Clone, T>(
+    base: &IndexMap<K, T>,
+    ours: &IndexMap<K, T>,
+    theirs: &IndexMap<K, T>,
+    mut resolve: impl FnMut(&K, Option<&T>, Option<&T>, Option<&T>) -> Option<T>,
+) -> IndexMap<K, T> {
+    let mut merged: Vec<(K, T)> = vec![];
+    for (key, value) in ours {
+        if let Some(value) = resolve(key, base.get(key), Some(value), theirs.get(key)) {
+            merged.push((key.clone(), value));
+        }
+    }
+    for (i, (key, value)) in theirs.iter().enumerate() {
+        if ours.contains_key(key) {
+            continue;
+        }
+        if let Some(value) = resolve(key, base.get(key), None, Some(value)) {
+            let position = (0..i)
+                .rev()
+                .filter_map(|j| theirs.get_index(j))
+                .find_map(|(previous, _)| merged.iter().position(|(key, _)| key == previous))
+                .map_or(0, |position| position + 1);
+            merged.insert(position, (key.clone(), value));
+        }
+    }
+    merged.into_iter().collect()
+}
+
+fn merge_items(
+    schema: &Schema,
+    prefix: &str,
+    base: &Items,
+    ours: &Items,
+    theirs: &Items,
+    conflicts: &mut Vec<Conflict>,
+) -> Items {
+    merge_keyed(base, ours, theirs, |key, base, ours, theirs| {
+        let (base, ours, theirs) = (
+            base.map(Box::as_ref),
+            ours.map(Box::as_ref),
+            theirs.map(Box::as_ref),
+        );
+        if let Ok(merged) = three_way(base, ours, theirs) {
+            return merged.map(Box::new);
+        }
+        let field = format!("{}{}", prefix, key);
+        let merged = match (base, ours, theirs) {
+            (
+                None | Some(Item::Section { .. }),
+                Some(Item::Section { name, items: ours }),
+                Some(Item::Section { items: theirs, .. }),
+            ) => {
+                let empty = Items::new();
+                let base = match base {
+                    Some(Item::Section { items, .. }) => items,
+                    _ => &empty,
+                };
+                let section = match schema.get(key) {
+                    Some(Kind::Section(section)) => section.clone(),
+                    _ => Schema::new(key.as_str()),
+                };
+                let prefix = format!("{}.", field);
+                Some(Item::Section {
+                    name: name.clone(),
+                    items: merge_items(&section, &prefix, base, ours, theirs, conflicts),
+                })
+            }
+            (_, Some(Item::Variable(ours)), Some(Item::Variable(theirs)))
+                if matches!(schema.get(key), Some(Kind::Formula)) =>
+            {
+                let base = match base {
+                    Some(Item::Variable(base)) => Some(base),
+                    _ => None,
+                };
+                Some(Item::Variable(merge_formulas(
+                    &field, base, ours, theirs, conflicts,
+                )))
+            }
+            _ => {
+                conflicts.push(Conflict::Field {
+                    field,
+                    base: base.cloned(),
+                    ours: ours.cloned(),
+                    theirs: theirs.cloned(),
+                });
+                ours.cloned()
+            }
+        };
+        merged.map(Box::new)
+    })
+}
+
+// The elements of a formula keyed by package name. Elements that aren't a single package, such
+// as alternatives, and repeated packages are keyed by their text.
+fn dependencies(formula: Option<&Value>) -> IndexMap<String, Value> {
+    let mut dependencies = IndexMap::new();
+    let elements: Vec<&Value> = match formula {
+        Some(Value::List(values)) => values.iter().map(Box::as_ref).collect(),
+        Some(value) => vec![value],
+        None => vec![],
+    };
+    for element in elements {
+        let name = match element {
+            Value::String(name) => Some(name),
+            Value::Option(base, _) => match base.as_ref() {
+                Value::String(name) => Some(name),
+                _ => None,
+            },
+            _ => None,
+        };
+        let key = match name {
+            Some(name) if !dependencies.contains_key(name) => name.clone(),
+            _ => element.to_string(),
+        };
+        dependencies.insert(key, element.clone());
+    }
+    dependencies
+}
+
+fn merge_formulas(
+    field: &str,
+    base: Option<&Value>,
+    ours: &Value,
+    theirs: &Value,
+    conflicts: &mut Vec<Conflict>,
+) -> Value {
+    let (base, ours, theirs) = (
+        dependencies(base),
+        dependencies(Some(ours)),
+        dependencies(Some(theirs)),
+    );
+    let merged = merge_keyed(&base, &ours, &theirs, |name, base, ours, theirs| {
+        three_way(base, ours, theirs).unwrap_or_else(|_| {
+            conflicts.push(Conflict::Dependency {
+                field: field.to_string(),
+                name: name.clone(),
+                base: base.cloned(),
+                ours: ours.cloned(),
+                theirs: theirs.cloned(),
+            });
+            ours.cloned()
+        })
+    });
+    Value::List(
+        merged
+            .into_iter()
+            .map(|(_, value)| Box::new(value))
+            .collect(),
+    )
+}
@@ -0,0 +1,167 @@
+//! Package names and the typed model of package definitions.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+
+use crate::error::FormatError;
+use crate::fields::{string_field, strings_field, variable};
+use crate::format_version::FormatVersion;
+use crate::legacy::unwrap_available;
+use crate::parser::{section_kind, Item, OpamAST, Value};
+use crate::spdx::{LicenseExpr, SpdxError};
+use crate::url::{dev_repo, extra_sources, url, OpamUrl, Url};
+use crate::version::OpamVersion;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PackageName(String);
+
+impl PackageName {
+    pub fn new(name: impl Into<String>) -> Self {
+        PackageName(name.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for PackageName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for PackageName {
+    fn from(name: &str) -> Self {
+        PackageName::new(name)
+    }
+}
+
+/// Splits a `name.version` atom, as used for package directories and in switch states.
+///
+/// Package names can't contain dots, so the version starts after the first one.
+pub fn split_atom(atom: &str) -> Option<(PackageName, OpamVersion)> {
+    let pos = atom.find('.')?;
+    let (name, version) = (&atom[..pos], &atom[pos + 1..]);
+    if name.is_empty() || version.is_empty() {
+        None
+    } else {
+        Some((PackageName::new(name), OpamVersion::new(version)))
+    }
+}
+
+/// A package definition, as found in `opam` files.
+///
+/// Metadata is typed, while formulas, filters and commands are kept as values. Fields without a
+/// dedicated member, including `x-` extension fields, are kept in `extra`. Values are read with
+/// the rules of the declared `opam-version`, so that 1.2 `available: [filter]` reads as a filter.
+#[derive(Debug, Clone, Default)]
+pub struct Package {
+    pub opam_version: Option<String>,
+    /// The format version `opam_version` declares, the latest one when it's missing or not
+    /// supported, which [`lint`](crate::lint::lint) reports.
+    pub format_version: FormatVersion,
+    pub name: Option<PackageName>,
+    pub version: Option<OpamVersion>,
+    pub synopsis: Option<String>,
+    pub description: Option<String>,
+    pub maintainer: Vec<String>,
+    pub authors: Vec<String>,
+    /// License expressions as written, see [`Package::licenses`].
+    pub license: Vec<String>,
+    pub homepage: Vec<String>,
+    pub doc: Vec<String>,
+    pub bug_reports: Vec<String>,
+    pub tags: Vec<String>,
+    pub dev_repo: Option<OpamUrl>,
+    pub depends: Option<Value>,
+    pub depopts: Option<Value>,
+    pub conflicts: Option<Value>,
+    pub available: Option<Value>,
+    pub build: Option<Value>,
+    pub install: Option<Value>,
+    pub url: Option<Url>,
+    pub extra_sources: Vec<(String, Url)>,
+    pub extra: IndexMap<String, Box<Item>>,
+}
+
+impl Package {
+    // Fields with a member of their own.
+    const FIELDS: [&'static str; 22] = [
+        "opam-version",
+        "name",
+        "version",
+        "synopsis",
+        "description",
+        "maintainer",
+        "authors",
+        "author",
+        "license",
+        "homepage",
+        "doc",
+        "bug-reports",
+        "tags",
+        "dev-repo",
+        "depends",
+        "depopts",
+        "conflicts",
+        "available",
+        "build",
+        "install",
+        "url",
+        "extra-source",
+    ];
+
+    pub fn from_ast(ast: &OpamAST) -> Result<Self, FormatError> {
+        let items = &ast.items;
+        let format_version = FormatVersion::declared(ast).unwrap_or(FormatVersion::LATEST);
+        let value = |field| variable(items, field).map(|value| value.cloned());
+        let authors = match strings_field(items, "authors")? {
+            authors if authors.is_empty() => strings_field(items, "author")?,
+            authors => authors,
+        };
+        let mut extra = IndexMap::new();
+        for (key, item) in items {
+            if !Package::FIELDS.contains(&section_kind(key)) {
+                extra.insert(key.clone(), item.clone());
+            }
+        }
+        Ok(Package {
+            opam_version: string_field(items, "opam-version")?,
+            format_version,
+            name: string_field(items, "name")?.map(PackageName::new),
+            version: string_field(items, "version")?.map(OpamVersion::new),
+            synopsis: string_field(items, "synopsis")?,
+            description: string_field(items, "description")?,
+            maintainer: strings_field(items, "maintainer")?,
+            authors,
+            license: strings_field(items, "license")?,
+            homepage: strings_field(items, "homepage")?,
+            doc: strings_field(items, "doc")?,
+            bug_reports: strings_field(items, "bug-reports")?,
+            tags: strings_field(items, "tags")?,
+            dev_repo: dev_repo(ast)?,
+            depends: value("depends")?,
+            depopts: value("depopts")?,
+            conflicts: value("conflicts")?,
+            available: match format_version {
+                FormatVersion::V1_2 => value("available")?.as_ref().map(unwrap_available),
+                _ => value("available")?,
+            },
+            build: value("build")?,
+            install: value("install")?,
+            url: url(ast)?,
+            extra_sources: extra_sources(ast)?,
+            extra,
+        })
+    }
+
+    /// The `license` field as SPDX expressions, checked against the SPDX license list.
+    pub fn licenses(&self) -> Vec<Result<LicenseExpr, SpdxError>> {
+        self.license
+            .iter()
+            .map(|license| LicenseExpr::parse_checked(license))
+            .collect()
+    }
+}
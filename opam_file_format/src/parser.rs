@@ -1,15 +1,20 @@
 use indexmap::{indexmap, IndexMap};
 use plex::parser;
 
+use crate::borrowed;
+use crate::error::Error;
+use crate::fields::Items;
 use crate::lexer;
+use crate::lexer::{Logop, Pfxop};
 use crate::lexer::Token::*;
+use crate::printer::quote;
 
 #[derive(Debug, Clone)]
 pub struct OpamAST {
     pub items: IndexMap<String, Box<Item>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Bool(bool),
     Int(i64),
@@ -25,7 +30,7 @@ pub enum Value {
     EnvBinding(Box<Value>, lexer::Envop, Box<Value>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Item {
     Section {
         name: Option<String>,
@@ -34,6 +39,207 @@ pub enum Item {
     Variable(Value),
 }
 
+/// Source locations of the items of an [`OpamAST`], keyed like the items themselves.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    pub items: IndexMap<String, ItemSpan>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ItemSpan {
+    /// The whole item, from its name to the end of its value or closing brace.
+    pub span: lexer::Span,
+    /// The field or section name.
+    pub key: lexer::Span,
+    /// The value of a field, or the name of a named section.
+    pub value: Option<ValueSpan>,
+    /// Items of a section.
+    pub items: IndexMap<String, ItemSpan>,
+}
+
+/// Location of a value, with the locations of its sub-values in the order they appear in
+/// [`Value`]: elements of lists and groups, the value then its options for `Option`, and the
+/// operands of operators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueSpan {
+    pub span: lexer::Span,
+    pub children: Vec<ValueSpan>,
+}
+
+impl ValueSpan {
+    fn leaf(span: lexer::Span) -> Self {
+        ValueSpan {
+            span,
+            children: vec![],
+        }
+    }
+}
+
+impl OpamAST {
+    /// Iterates over the top-level sections of the given kind, with their names.
+    ///
+    /// An AST built by [`parse`] has a single section of each kind, the last one of the source.
+    /// Build it with [`parse_with_spans`] to get all of them.
+    pub fn sections<'a>(
+        &'a self,
+        kind: &'a str,
+    ) -> impl Iterator<Item = (Option<&'a str>, &'a IndexMap<String, Box<Item>>)> + 'a {
+        self.items
+            .iter()
+            .filter(move |(key, _)| section_kind(key) == kind)
+            .filter_map(|(_, item)| match item.as_ref() {
+                Item::Section { name, items } => Some((name.as_deref(), items)),
+                Item::Variable(_) => None,
+            })
+    }
+}
+
+impl Value {
+    /// Sub-values, in the order of [`ValueSpan::children`].
+    pub fn children(&self) -> Vec<&Value> {
+        match self {
+            Value::Bool(_) | Value::Int(_) | Value::String(_) | Value::Ident(_) => vec![],
+            Value::Relop(_, v1, v2) | Value::Logop(_, v1, v2) | Value::EnvBinding(v1, _, v2) => {
+                vec![v1, v2]
+            }
+            Value::PrefixRelop(_, v) | Value::Pfxop(_, v) => vec![v],
+            Value::List(values) | Value::Group(values) => values.iter().map(Box::as_ref).collect(),
+            Value::Option(v, options) => std::iter::once(v.as_ref())
+                .chain(options.iter().map(Box::as_ref))
+                .collect(),
+        }
+    }
+
+    /// The value with its logical operators nested by opam's precedence, for evaluation.
+    ///
+    /// The grammar lets `!`, `?`, `&` and `|` extend as far right as possible, so `!a & b` is
+    /// parsed as `Pfxop(Not, Logop(And, a, b))` and `a & b | c` as `Logop(And, a, Logop(Or, b,
+    /// c))`, which is how the printers write them back. In opam, prefix operators bind tighter
+    /// than `&`, which binds tighter than `|`: this returns `(!a) & b` and `(a & b) | c`.
+    /// Values already nested this way are returned unchanged.
+    pub fn with_precedence(&self) -> Value {
+        let boxed = |value: &Value| Box::new(value.with_precedence());
+        let all = |values: &[Box<Value>]| values.iter().map(|value| boxed(value)).collect();
+        match self {
+            Value::Logop(..) | Value::Pfxop(..) => {
+                let mut operands = vec![];
+                let mut ops = vec![];
+                chain(self, &mut operands, &mut ops);
+                let mut operands = operands.into_iter().map(|(prefixes, operand)| {
+                    let mut value = operand.with_precedence();
+                    for op in prefixes.into_iter().rev() {
+                        if let Value::Logop(..) = value {
+                            value = Value::Group(vec![Box::new(value)]);
+                        }
+                        value = Value::Pfxop(op, Box::new(value));
+                    }
+                    value
+                });
+                // Conjunctions, separated by `|`.
+                let mut terms = vec![vec![operands.next().unwrap()]];
+                for (op, operand) in ops.into_iter().zip(operands) {
+                    match op {
+                        Logop::And => terms.last_mut().unwrap().push(operand),
+                        Logop::Or => terms.push(vec![operand]),
+                    }
+                }
+                let nest = |op: Logop, values: Vec<Value>| {
+                    values
+                        .into_iter()
+                        .rev()
+                        .reduce(|right, left| Value::Logop(op, Box::new(left), Box::new(right)))
+                        .unwrap()
+                };
+                let terms = terms
+                    .into_iter()
+                    .map(|values| nest(Logop::And, values))
+                    .collect();
+                nest(Logop::Or, terms)
+            }
+            Value::Bool(_) | Value::Int(_) | Value::String(_) | Value::Ident(_) => self.clone(),
+            Value::Relop(op, v1, v2) => Value::Relop(*op, boxed(v1), boxed(v2)),
+            Value::PrefixRelop(op, v) => Value::PrefixRelop(*op, boxed(v)),
+            Value::List(values) => Value::List(all(values)),
+            Value::Group(values) => Value::Group(all(values)),
+            Value::Option(v, options) => Value::Option(boxed(v), all(options)),
+            Value::EnvBinding(v1, op, v2) => Value::EnvBinding(boxed(v1), *op, boxed(v2)),
+        }
+    }
+}
+
+// Splits the right spine of logical and prefix operators, which reads as written, into its
+// operands with the prefix operators before each, and the operators between them. Left operands
+// were parenthesised or built, so they're operands as a whole.
+fn chain<'a>(
+    mut value: &'a Value,
+    operands: &mut Vec<(Vec<Pfxop>, &'a Value)>,
+    ops: &mut Vec<Logop>,
+) {
+    let mut prefixes = vec![];
+    loop {
+        match value {
+            Value::Pfxop(op, v) => {
+                prefixes.push(*op);
+                value = v;
+            }
+            Value::Logop(op, v1, v2) => {
+                operands.push((std::mem::take(&mut prefixes), v1));
+                ops.push(*op);
+                value = v2;
+            }
+            value => return operands.push((prefixes, value)),
+        }
+    }
+}
+
+/// Key of a section in its parent's items, as built by [`parse_with_spans`].
+///
+/// Named sections such as `extra-source "a.patch" {...}` may appear several times, so their key
+/// is the kind followed by the name quoted and escaped as in opam syntax, e.g.
+/// `extra-source "a.patch"`. Unnamed sections are keyed by kind, which is how [`parse`] keys
+/// every section.
+pub fn section_key(kind: &str, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{} {}", kind, quote(name)),
+        None => kind.to_string(),
+    }
+}
+
+/// The kind of a section from its key, see [`section_key`]. Other keys are returned as they are.
+pub fn section_kind(key: &str) -> &str {
+    key.split(' ').next().unwrap_or(key)
+}
+
+/// An item with its key and location.
+#[derive(Debug)]
+pub(crate) struct KeyedItem {
+    pub key: String,
+    pub item: Item,
+    pub span: ItemSpan,
+    /// The name of an item of the section repeating the key of an earlier one, at any depth. The
+    /// section only keeps the last of them.
+    pub repeated: Option<lexer::Span>,
+}
+
+/// Items keyed as in the AST, a repeated key keeping the place of its first occurrence and the
+/// value of its last, and the name of the first repeated item, at any depth.
+pub(crate) fn collect(
+    list: Vec<KeyedItem>,
+) -> (Items, IndexMap<String, ItemSpan>, Option<lexer::Span>) {
+    let (mut items, mut spans, mut repeated) = (indexmap![], indexmap![], None);
+    for item in list {
+        let key = item.span.key;
+        repeated = repeated.or(item.repeated);
+        if spans.insert(item.key.clone(), item.span).is_some() {
+            repeated = repeated.or(Some(key));
+        }
+        items.insert(item.key, Box::new(item.item));
+    }
+    (items, spans, repeated)
+}
+
+// Builds the AST alone, for `parse`. Sections are keyed by kind, a later section replacing an
+// earlier one of the same kind.
 parser! {
     fn parse_(lexer::Token, lexer::Span);
 
@@ -122,8 +328,164 @@ parser! {
         }
     }
 }
-pub fn parse<I: Iterator<Item = (lexer::Token, lexer::Span)>>(
-    i: I,
-) -> Result<OpamAST, (Option<(lexer::Token, lexer::Span)>, &'static str)> {
+
+// The same grammar, also building the locations of items and values, for `parse_with_spans`.
+// Clippy already reports the lints of the code plex generates for `parse_`.
+#[allow(clippy::ptr_arg, clippy::redundant_closure_call)]
+mod spanned {
+    use super::*;
+
+    parser! {
+        pub(super) fn parse_spanned_(lexer::Token, lexer::Span);
+
+        (a, b){
+            lexer::Span{start: a.start, end: b.end}
+        }
+
+        main: Vec<KeyedItem> {
+            items[itms] => itms
+        }
+
+        items: Vec<KeyedItem> {
+            => vec![],
+            items[mut itms] item[itm] => {
+                itms.push(itm);
+                itms
+            }
+        }
+
+        item: KeyedItem {
+            ident[(id, key)] COLON value[(v, value)] => {
+                let span = ItemSpan{span: span!(), key, value: Some(value), items: indexmap![]};
+                KeyedItem{key: id, item: Item::Variable(v), span, repeated: None}
+            },
+            ident[(id, key)] LBRACE items[itms] RBRACE => {
+                let (v, spans, repeated) = collect(itms);
+                let span = ItemSpan{span: span!(), key, value: None, items: spans};
+                KeyedItem{key: id, item: Item::Section{name: None, items: v}, span, repeated}
+            },
+            ident[(id, key)] string[(str, name)] LBRACE items[itms] RBRACE => {
+                let (v, spans, repeated) = collect(itms);
+                let span = ItemSpan{span: span!(), key, value: Some(name), items: spans};
+                let key = section_key(&id, Some(&str));
+                KeyedItem{key, item: Item::Section{name: Some(str), items: v}, span, repeated}
+            }
+        }
+
+        ident: (String, lexer::Span) {
+            IDENT(id) => (id, span!())
+        }
+
+        string: (String, ValueSpan) {
+            STRING(str) => (str, ValueSpan::leaf(span!()))
+        }
+
+        value: (Value, ValueSpan) {
+            #[no_reduce(ENVOP, RELOP)]
+            atom[a] => {
+                a
+            },
+            LPAR values[(v, spans)] RPAR => {
+                (Value::Group(v), ValueSpan{span: span!(), children: spans})
+            },
+            LBRACKET values[(v, spans)] RBRACKET => {
+                (Value::List(v), ValueSpan{span: span!(), children: spans})
+            },
+            value[(v, s)] LBRACE values[(vs, mut spans)] RBRACE => {
+                spans.insert(0, s);
+                (Value::Option(Box::new(v), vs), ValueSpan{span: span!(), children: spans})
+            },
+            #[no_reduce(LBRACE, LOGOP)]
+            value[l] LOGOP(op) value[r] => {
+                let ((v1, s1), (v2, s2)) = (l, r);
+                (Value::Logop(op, Box::new(v1), Box::new(v2)), ValueSpan{span: span!(), children: vec![s1, s2]})
+            },
+            atom[l] RELOP(op) atom[r] => {
+                let ((a1, s1), (a2, s2)) = (l, r);
+                (Value::Relop(op, Box::new(a1), Box::new(a2)), ValueSpan{span: span!(), children: vec![s1, s2]})
+            },
+            atom[l] ENVOP(op) atom[r] => {
+                let ((a1, s1), (a2, s2)) = (l, r);
+                (Value::EnvBinding(Box::new(a1), op, Box::new(a2)), ValueSpan{span: span!(), children: vec![s1, s2]})
+            },
+            #[no_reduce(LBRACE, LOGOP)]
+            PFXOP(op) value[(v, s)] => {
+                (Value::Pfxop(op, Box::new(v)), ValueSpan{span: span!(), children: vec![s]})
+            },
+            RELOP(op) atom[(a, s)] => {
+                (Value::PrefixRelop(op, Box::new(a)), ValueSpan{span: span!(), children: vec![s]})
+            }
+        }
+
+        values: (Vec<Box<Value>>, Vec<ValueSpan>) {
+            => (vec![], vec![]),
+            values[(mut vs, mut spans)] value[(v, s)] => {
+                vs.push(Box::new(v));
+                spans.push(s);
+                (vs, spans)
+            }
+        }
+
+        atom: (Value, ValueSpan) {
+            IDENT(id) => {
+                (Value::Ident(id), ValueSpan::leaf(span!()))
+            },
+            BOOL(b) => {
+                (Value::Bool(b), ValueSpan::leaf(span!()))
+            },
+            INT(i) => {
+                (Value::Int(i), ValueSpan::leaf(span!()))
+            },
+            STRING(str) => {
+                (Value::String(str), ValueSpan::leaf(span!()))
+            }
+        }
+    }
+}
+
+/// A syntax error: the unexpected token, `None` at the end of the input, and what was expected.
+pub type ParseError = (Option<(lexer::Token, lexer::Span)>, &'static str);
+
+pub fn parse<I: Iterator<Item = (lexer::Token, lexer::Span)>>(i: I) -> Result<OpamAST, ParseError> {
     parse_(i)
 }
+
+/// Like [`parse`], also returning where each item and value was found in the source.
+///
+/// Named sections are keyed with [`section_key`] rather than by kind, so that each section of a
+/// kind is kept, where [`parse`] keeps the last one.
+pub fn parse_with_spans<I: Iterator<Item = (lexer::Token, lexer::Span)>>(
+    i: I,
+) -> Result<(OpamAST, SourceMap), ParseError> {
+    let (items, spans, _) = collect(spanned::parse_spanned_(i)?);
+    Ok((OpamAST { items }, SourceMap { items: spans }))
+}
+
+/// Parses the top-level items in the order they appear, keeping repeated ones, which
+/// [`parse_with_spans`] merges into the first occurrence.
+pub(crate) fn parse_items<I: Iterator<Item = (lexer::Token, lexer::Span)>>(
+    i: I,
+) -> Result<Vec<KeyedItem>, Error> {
+    spanned::parse_spanned_(i)
+        .map_err(|(token, msg)| Error::Parse(token.map(|(_, span)| span), msg))
+}
+
+/// Lexes and parses `input` in one go.
+pub fn parse_str(input: &str) -> Result<OpamAST, Error> {
+    let tokens = lexer::lex(input).map_err(Error::Lex)?;
+    parse(tokens.into_iter()).map_err(|(token, msg)| Error::Parse(token.map(|(_, span)| span), msg))
+}
+
+/// Like [`parse_str`], also returning where each item and value was found in the source.
+pub fn parse_str_with_spans(input: &str) -> Result<(OpamAST, SourceMap), Error> {
+    let tokens = lexer::lex(input).map_err(Error::Lex)?;
+    parse_with_spans(tokens.into_iter())
+        .map_err(|(token, msg)| Error::Parse(token.map(|(_, span)| span), msg))
+}
+
+/// Parses only the top-level fields and sections of `input` listed in `fields`, skipping over
+/// the others without building their values. See [`borrowed::parse_fields`].
+pub fn parse_str_fields(input: &str, fields: &[&str]) -> Result<OpamAST, Error> {
+    let arena = borrowed::Arena::new();
+    Ok(borrowed::parse_fields(input, fields, &arena)?.to_owned_ast())
+}
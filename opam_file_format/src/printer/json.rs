@@ -65,7 +65,7 @@ impl JsonPrinter<'_> {
             .collect()
     }
 
-    fn serialize_item(key: String, item: &Item) -> (String, JsonValue) {
+    pub(crate) fn serialize_item(key: String, item: &Item) -> (String, JsonValue) {
         match item {
             Item::Section { name, items } => {
                 let mut items = Self::serialize_items(items);
@@ -78,7 +78,7 @@ impl JsonPrinter<'_> {
         }
     }
 
-    fn serialize_value(value: &Value) -> JsonValue {
+    pub(crate) fn serialize_value(value: &Value) -> JsonValue {
         match value {
             Value::Bool(b) => JsonValue::Bool(b.clone()),
             Value::Int(i) => JsonValue::Number(Number::from(i.clone())),
@@ -1,3 +1,6 @@
 pub use self::json::JsonPrinter;
+pub use self::opam::OpamPrinter;
+pub(crate) use self::opam::{envop_literal, logop_literal, pfxop_literal, quote, relop_literal};
 
 mod json;
+mod opam;
@@ -0,0 +1,189 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+
+use crate::lexer::{Envop, Logop, Pfxop, Relop};
+use crate::parser::{section_kind, Item, OpamAST, Value};
+
+const MAX_WIDTH: usize = 80;
+
+/// Prints an AST back to opam syntax.
+///
+/// Top-level lists that don't fit on a line, or that contain lists, are printed one element per
+/// line. Everything else is printed inline.
+pub struct OpamPrinter<'a> {
+    ast: &'a OpamAST,
+}
+
+impl Display for OpamPrinter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_items(f, &self.ast.items, 0)
+    }
+}
+
+impl OpamPrinter<'_> {
+    pub fn new(ast: &OpamAST) -> OpamPrinter<'_> {
+        OpamPrinter { ast }
+    }
+}
+
+fn write_items(
+    f: &mut Formatter<'_>,
+    items: &IndexMap<String, Box<Item>>,
+    indent: usize,
+) -> fmt::Result {
+    items
+        .iter()
+        .try_for_each(|(key, item)| write_item(f, key, item, indent))
+}
+
+fn write_item(f: &mut Formatter<'_>, key: &str, item: &Item, indent: usize) -> fmt::Result {
+    let pad = " ".repeat(indent);
+    match item {
+        Item::Variable(value) => {
+            write!(f, "{}{}: ", pad, key)?;
+            write_field_value(f, key.len() + indent + 2, value, indent)?;
+            writeln!(f)
+        }
+        Item::Section { name, items } => {
+            match name {
+                Some(name) => writeln!(f, "{}{} {} {{", pad, section_kind(key), quote(name))?,
+                None => writeln!(f, "{}{} {{", pad, key)?,
+            }
+            write_items(f, items, indent + 2)?;
+            writeln!(f, "{}}}", pad)
+        }
+    }
+}
+
+fn write_field_value(
+    f: &mut Formatter<'_>,
+    column: usize,
+    value: &Value,
+    indent: usize,
+) -> fmt::Result {
+    match value {
+        Value::List(values) if !fits(column, value) || values.iter().any(|v| is_list(v)) => {
+            writeln!(f, "[")?;
+            for value in values {
+                writeln!(f, "{}  {}", " ".repeat(indent), value)?;
+            }
+            write!(f, "{}]", " ".repeat(indent))
+        }
+        value => write!(f, "{}", value),
+    }
+}
+
+fn fits(column: usize, value: &Value) -> bool {
+    column + value.to_string().len() <= MAX_WIDTH
+}
+
+fn is_list(value: &Value) -> bool {
+    match value {
+        Value::List(_) => true,
+        Value::Option(value, _) => is_list(value),
+        _ => false,
+    }
+}
+
+/// Quotes and escapes a string. Strings spanning several lines use triple quotes.
+pub(crate) fn quote(s: &str) -> String {
+    let escaped = s.replace('\\', "\\\\").replace('"', "\\\"");
+    if s.contains('\n') {
+        format!("\"\"\"{}\"\"\"", escaped)
+    } else {
+        format!("\"{}\"", escaped)
+    }
+}
+
+pub(crate) fn relop_literal(op: Relop) -> &'static str {
+    match op {
+        Relop::Eq => "=",
+        Relop::Neq => "!=",
+        Relop::Geq => ">=",
+        Relop::Gt => ">",
+        Relop::Leq => "<=",
+        Relop::Lt => "<",
+    }
+}
+
+pub(crate) fn logop_literal(op: Logop) -> &'static str {
+    match op {
+        Logop::And => "&",
+        Logop::Or => "|",
+    }
+}
+
+pub(crate) fn pfxop_literal(op: Pfxop) -> &'static str {
+    match op {
+        Pfxop::Not => "!",
+        Pfxop::Defined => "?",
+    }
+}
+
+pub(crate) fn envop_literal(op: Envop) -> &'static str {
+    match op {
+        Envop::Eq => "=",
+        Envop::PlusEq => "+=",
+        Envop::EqPlus => "=+",
+        Envop::EqPlusEq => "=+=",
+        Envop::ColonEq => ":=",
+        Envop::EqColon => "=:",
+    }
+}
+
+// Logical and prefix operators extend as far right as possible when parsed, so they need
+// parentheses when something follows them.
+fn write_operand(f: &mut Formatter<'_>, value: &Value) -> fmt::Result {
+    match value {
+        Value::Logop(..) | Value::Pfxop(..) => write!(f, "({})", value),
+        value => write!(f, "{}", value),
+    }
+}
+
+fn write_values(f: &mut Formatter<'_>, values: &[Box<Value>]) -> fmt::Result {
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", value)?;
+    }
+    Ok(())
+}
+
+/// Values are displayed inline in opam syntax.
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::String(s) => write!(f, "{}", quote(s)),
+            Value::Relop(op, v1, v2) => write!(f, "{} {} {}", v1, relop_literal(*op), v2),
+            Value::PrefixRelop(op, v) => write!(f, "{} {}", relop_literal(*op), v),
+            Value::Logop(op, v1, v2) => {
+                write_operand(f, v1)?;
+                write!(f, " {} {}", logop_literal(*op), v2)
+            }
+            Value::Pfxop(op, v) => write!(f, "{}{}", pfxop_literal(*op), v),
+            Value::Ident(id) => write!(f, "{}", id),
+            Value::List(values) => {
+                write!(f, "[")?;
+                write_values(f, values)?;
+                write!(f, "]")
+            }
+            Value::Group(values) => {
+                write!(f, "(")?;
+                write_values(f, values)?;
+                write!(f, ")")
+            }
+            Value::Option(v, options) => {
+                write_operand(f, v)?;
+                write!(f, " {{")?;
+                write_values(f, options)?;
+                write!(f, "}}")
+            }
+            Value::EnvBinding(v1, op, v2) => write!(f, "{} {} {}", v1, envop_literal(*op), v2),
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Paths into an AST, such as `url.src`, `depends[*].name` or
+//! `extra-source["patch.diff"].checksum`.
+//!
+//! A query starts with a field or section name, followed by steps:
+//!
+//! - `.field` goes into a section. On a `value {options}`, `.name` and `.value` pick the value,
+//!   `.options` and `.filter` its options.
+//! - `[N]` is the Nth element of a list, counting from 0. As in opam, a value written without
+//!   brackets is a list of one element, so `[0]` is the value itself.
+//! - `[*]` is every element of a list, `&` and `|` formulas and parenthesised groups being split
+//!   into their atoms, or every item of a section. Negations such as `!"pkg"` and groups with
+//!   options such as `("a" | "b") {build}` are atoms themselves, so that the operator or options
+//!   that apply to their elements aren't lost.
+//! - `["text"]` keeps the sections named `text`, or the atoms, as `[*]` splits them, that are the
+//!   string `text`, options aside. It doesn't look into negations and groups with options either.
+//!
+//! Sections are matched by kind, so `extra-source` matches all `extra-source "name" {...}`.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use indexmap::indexmap;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::fields::Items;
+use crate::parser::{section_kind, Item, OpamAST, Value};
+use crate::printer::{JsonPrinter, OpamPrinter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Field(String),
+    Index(usize),
+    All,
+    Named(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    /// Byte offset in the query.
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid query at {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// What a query points to: an item of the AST, or a value within one.
+#[derive(Debug, Clone, Copy)]
+pub enum Match<'a> {
+    Item { key: &'a str, item: &'a Item },
+    Value(&'a Value),
+}
+
+impl<'a> Match<'a> {
+    /// The matched value, or the value of the matched field.
+    pub fn value(&self) -> Option<&'a Value> {
+        match *self {
+            Match::Item {
+                item: Item::Variable(value),
+                ..
+            }
+            | Match::Value(value) => Some(value),
+            Match::Item { .. } => None,
+        }
+    }
+
+    /// The match as JSON, sections being objects as in [`JsonPrinter`].
+    pub fn to_json(&self) -> JsonValue {
+        match self.value() {
+            Some(value) => JsonPrinter::serialize_value(value),
+            None => match self {
+                Match::Item { key, item } => {
+                    let (_, json) = JsonPrinter::serialize_item(key.to_string(), item);
+                    json
+                }
+                Match::Value(_) => JsonValue::Object(Map::new()),
+            },
+        }
+    }
+}
+
+/// Values are printed inline, sections as they would appear in a file.
+impl Display for Match<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Match::Item {
+                key,
+                item: item @ Item::Section { .. },
+            } => {
+                let ast = OpamAST {
+                    items: indexmap! { key.to_string() => Box::new((*item).clone()) },
+                };
+                write!(f, "{}", OpamPrinter::new(&ast).to_string().trim_end())
+            }
+            _ => match self.value() {
+                Some(value) => write!(f, "{}", value),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+// Where evaluation stands: the top-level items, or a match.
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Items(&'a Items),
+    Match(Match<'a>),
+}
+
+impl Query {
+    pub fn parse(s: &str) -> Result<Self, QueryError> {
+        let mut parser = QueryParser { s, position: 0 };
+        let mut steps = vec![Step::Field(parser.name()?)];
+        while let Some(c) = parser.peek() {
+            match c {
+                '.' => {
+                    parser.position += 1;
+                    steps.push(Step::Field(parser.name()?));
+                }
+                '[' => {
+                    parser.position += 1;
+                    steps.push(parser.selector()?);
+                    parser.expect(']')?;
+                }
+                c => return Err(parser.error(format!("unexpected `{}`", c))),
+            }
+        }
+        Ok(Query { steps })
+    }
+
+    /// Evaluates the query, returning the matches in the order of the source.
+    pub fn eval<'a>(&self, ast: &'a OpamAST) -> Vec<Match<'a>> {
+        let mut nodes = vec![Node::Items(&ast.items)];
+        for step in &self.steps {
+            nodes = nodes
+                .into_iter()
+                .flat_map(|node| apply(step, node))
+                .collect();
+        }
+        nodes
+            .into_iter()
+            .filter_map(|node| match node {
+                Node::Match(m) => Some(m),
+                Node::Items(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Query::parse(s)
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            match step {
+                Step::Field(name) if i == 0 => write!(f, "{}", name)?,
+                Step::Field(name) => write!(f, ".{}", name)?,
+                Step::Index(index) => write!(f, "[{}]", index)?,
+                Step::All => write!(f, "[*]")?,
+                Step::Named(name) => write!(f, "[{:?}]", name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses and evaluates `query` against `ast`.
+pub fn query<'a>(ast: &'a OpamAST, query: &str) -> Result<Vec<Match<'a>>, QueryError> {
+    Ok(Query::parse(query)?.eval(ast))
+}
+
+fn apply<'a>(step: &Step, node: Node<'a>) -> Vec<Node<'a>> {
+    let items = match node {
+        Node::Items(items)
+        | Node::Match(Match::Item {
+            item: Item::Section { items, .. },
+            ..
+        }) => items,
+        Node::Match(m) => {
+            return m
+                .value()
+                .map_or(vec![], |value| apply_value(step, node, value))
+        }
+    };
+    let matches = |field: Option<&str>| {
+        items
+            .iter()
+            .filter(move |(key, item)| match field {
+                None => true,
+                Some(field) => {
+                    *key == field
+                        || (section_kind(key) == field
+                            && matches!(item.as_ref(), Item::Section { .. }))
+                }
+            })
+            .map(|(key, item)| Node::Match(Match::Item { key, item }))
+            .collect()
+    };
+    match step {
+        Step::Field(name) => matches(Some(name)),
+        Step::All => matches(None),
+        Step::Named(name) => match node {
+            Node::Match(Match::Item {
+                item:
+                    Item::Section {
+                        name: Some(section),
+                        ..
+                    },
+                ..
+            }) if section == name => vec![node],
+            _ => vec![],
+        },
+        Step::Index(_) => vec![],
+    }
+}
+
+fn apply_value<'a>(step: &Step, node: Node<'a>, value: &'a Value) -> Vec<Node<'a>> {
+    let value_node = |value: &'a Value| Node::Match(Match::Value(value));
+    match step {
+        Step::Field(field) => match (field.as_str(), value) {
+            ("name", Value::Option(value, _)) | ("value", Value::Option(value, _)) => {
+                vec![value_node(value)]
+            }
+            ("name", _) | ("value", _) => vec![node],
+            ("options", Value::Option(_, options)) | ("filter", Value::Option(_, options)) => {
+                options.iter().map(|option| value_node(option)).collect()
+            }
+            _ => vec![],
+        },
+        Step::Index(index) => match value {
+            Value::List(values) => values
+                .get(*index)
+                .map(|value| value_node(value))
+                .into_iter()
+                .collect(),
+            _ if *index == 0 => vec![node],
+            _ => vec![],
+        },
+        Step::All => atoms(value).into_iter().map(value_node).collect(),
+        Step::Named(name) => atoms(value)
+            .into_iter()
+            .filter(|value| {
+                let value: &Value = match value {
+                    Value::Option(value, _) => value,
+                    value => value,
+                };
+                matches!(value, Value::String(s) if s == name)
+            })
+            .map(value_node)
+            .collect(),
+    }
+}
+
+// The elements of a list, a single element possibly being written without brackets, with
+// formulas split into their atoms.
+fn atoms(value: &Value) -> Vec<&Value> {
+    fn split<'a>(value: &'a Value, atoms: &mut Vec<&'a Value>) {
+        match value {
+            Value::Logop(_, v1, v2) => {
+                split(v1, atoms);
+                split(v2, atoms);
+            }
+            Value::Group(values) => values.iter().for_each(|value| split(value, atoms)),
+            value => atoms.push(value),
+        }
+    }
+    let mut atoms = vec![];
+    match value {
+        Value::List(values) => values.iter().for_each(|value| split(value, &mut atoms)),
+        value => split(value, &mut atoms),
+    }
+    atoms
+}
+
+struct QueryParser<'a> {
+    s: &'a str,
+    position: usize,
+}
+
+impl QueryParser<'_> {
+    fn error(&self, message: impl Into<String>) -> QueryError {
+        QueryError {
+            position: self.position,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.position..].chars().next()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), QueryError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected `{}`, found `{}`", expected, c))),
+            None => Err(self.error(format!("expected `{}`", expected))),
+        }
+    }
+
+    fn name(&mut self) -> Result<String, QueryError> {
+        let rest = &self.s[self.position..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '+'))
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return Err(self.error("expected a field name"));
+        }
+        self.position += end;
+        Ok(rest[..end].to_string())
+    }
+
+    fn selector(&mut self) -> Result<Step, QueryError> {
+        match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                Ok(Step::All)
+            }
+            Some('"') => {
+                self.position += 1;
+                let mut name = String::new();
+                let mut chars = self.s[self.position..].chars();
+                loop {
+                    let c = chars
+                        .next()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += c.len_utf8();
+                    match c {
+                        '"' => return Ok(Step::Named(name)),
+                        '\\' => {
+                            let c = chars
+                                .next()
+                                .ok_or_else(|| self.error("unterminated string"))?;
+                            self.position += c.len_utf8();
+                            name.push(c);
+                        }
+                        c => name.push(c),
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let rest = &self.s[self.position..];
+                let end = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let index = rest[..end]
+                    .parse()
+                    .map_err(|_| self.error("index out of range"))?;
+                self.position += end;
+                Ok(Step::Index(index))
+            }
+            _ => Err(self.error("expected `*`, an index or a quoted name")),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! Walking an opam repository checkout.
+//!
+//! Package definitions live in `packages/<name>/<name>.<version>/opam`, optionally next to a
+//! `files/` directory holding extra files, and the repository root carries a `repo` file.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::vec;
+
+use crate::error::{Error, FormatError};
+use crate::fields::{filtered, list, required, string, string_field, variable, Items};
+use crate::package::{split_atom, PackageName};
+use crate::parser::{parse_str, OpamAST, Value};
+use crate::version::OpamVersion;
+
+#[derive(Debug)]
+pub enum RepositoryError {
+    Io(PathBuf, io::Error),
+    /// A directory under `packages/<name>` isn't named `<name>.<version>`.
+    Layout(PathBuf),
+    Syntax {
+        path: PathBuf,
+        source: String,
+        error: Error,
+    },
+    Format(PathBuf, FormatError),
+}
+
+impl RepositoryError {
+    pub fn path(&self) -> &Path {
+        match self {
+            RepositoryError::Io(path, _) => path,
+            RepositoryError::Layout(path) => path,
+            RepositoryError::Syntax { path, .. } => path,
+            RepositoryError::Format(path, _) => path,
+        }
+    }
+}
+
+impl Display for RepositoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RepositoryError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            RepositoryError::Layout(path) => write!(
+                f,
+                "{}: expected a directory named <name>.<version>",
+                path.display()
+            ),
+            RepositoryError::Syntax { path, error, .. } => {
+                write!(f, "{}: {}", path.display(), error)
+            }
+            RepositoryError::Format(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for RepositoryError {}
+
+pub struct Repository {
+    root: PathBuf,
+}
+
+impl Repository {
+    /// Opens the repository at `root`, which must contain a `packages` directory.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, RepositoryError> {
+        let root = root.into();
+        let packages = root.join("packages");
+        if !packages.is_dir() {
+            return Err(RepositoryError::Io(
+                packages,
+                io::Error::new(io::ErrorKind::NotFound, "not a directory"),
+            ));
+        }
+        Ok(Repository { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of the `repo` file at the root of the repository, if there is one.
+    pub fn repo_file(&self) -> Option<PathBuf> {
+        Some(self.root.join("repo")).filter(|path| path.is_file())
+    }
+
+    pub fn repo(&self) -> Result<Option<RepoFile>, RepositoryError> {
+        let path = match self.repo_file() {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let ast = parse_file(&path)?;
+        RepoFile::from_ast(&ast)
+            .map(Some)
+            .map_err(|e| RepositoryError::Format(path, e))
+    }
+
+    /// Lazily lists package version directories, sorted by name then directory name.
+    pub fn package_dirs(&self) -> Result<PackageDirs, RepositoryError> {
+        Ok(PackageDirs {
+            names: sorted_dirs(&self.root.join("packages"))?.into_iter(),
+            versions: vec![].into_iter(),
+        })
+    }
+
+    /// Lazily parses every package definition of the repository.
+    pub fn packages(&self) -> Result<Packages, RepositoryError> {
+        Ok(Packages {
+            dirs: self.package_dirs()?,
+        })
+    }
+}
+
+/// A `packages/<name>/<name>.<version>` directory.
+#[derive(Debug, Clone)]
+pub struct PackageDir {
+    pub name: PackageName,
+    pub version: OpamVersion,
+    pub path: PathBuf,
+}
+
+impl PackageDir {
+    pub fn opam_file(&self) -> PathBuf {
+        self.path.join("opam")
+    }
+
+    pub fn files_dir(&self) -> Option<PathBuf> {
+        Some(self.path.join("files")).filter(|path| path.is_dir())
+    }
+
+    /// Paths of the extra files, relative to the `files/` directory.
+    pub fn files(&self) -> Result<Vec<PathBuf>, RepositoryError> {
+        let mut files = vec![];
+        if let Some(dir) = self.files_dir() {
+            collect_files(&dir, Path::new(""), &mut files)?;
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    pub fn parse(&self) -> Result<OpamAST, RepositoryError> {
+        parse_file(&self.opam_file())
+    }
+}
+
+/// The `repo` file at the root of a repository.
+#[derive(Debug, Clone)]
+pub struct RepoFile {
+    pub opam_version: String,
+    pub browse: Option<String>,
+    pub upstream: Option<String>,
+    /// Alternative repository URLs, each with an optional filter.
+    pub redirect: Vec<(String, Option<Value>)>,
+    /// Messages shown to users on update, each with an optional filter.
+    pub announce: Vec<(String, Option<Value>)>,
+    pub stamp: Option<String>,
+}
+
+impl RepoFile {
+    pub fn from_ast(ast: &OpamAST) -> Result<Self, FormatError> {
+        Ok(RepoFile {
+            opam_version: string(required(&ast.items, "opam-version")?, "opam-version")?
+                .to_string(),
+            browse: string_field(&ast.items, "browse")?,
+            upstream: string_field(&ast.items, "upstream")?,
+            redirect: filtered_strings(&ast.items, "redirect")?,
+            announce: filtered_strings(&ast.items, "announce")?,
+            stamp: string_field(&ast.items, "stamp")?,
+        })
+    }
+}
+
+fn filtered_strings(
+    items: &Items,
+    field: &str,
+) -> Result<Vec<(String, Option<Value>)>, FormatError> {
+    let value = match variable(items, field)? {
+        Some(value) => value,
+        None => return Ok(vec![]),
+    };
+    list(value)
+        .into_iter()
+        .map(|value| {
+            let (value, filter) = filtered(value, field)?;
+            Ok((string(value, field)?.to_string(), filter.cloned()))
+        })
+        .collect()
+}
+
+pub(crate) fn parse_source(path: &Path, source: String) -> Result<OpamAST, RepositoryError> {
+    parse_str(&source).map_err(|error| RepositoryError::Syntax {
+        path: path.to_path_buf(),
+        source,
+        error,
+    })
+}
+
+fn parse_file(path: &Path) -> Result<OpamAST, RepositoryError> {
+    let source =
+        fs::read_to_string(path).map_err(|e| RepositoryError::Io(path.to_path_buf(), e))?;
+    parse_source(path, source)
+}
+
+pub struct PackageDirs {
+    names: vec::IntoIter<PathBuf>,
+    versions: vec::IntoIter<PathBuf>,
+}
+
+impl Iterator for PackageDirs {
+    type Item = Result<PackageDir, RepositoryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(path) = self.versions.next() {
+                return Some(package_dir(path));
+            }
+            let name_dir = self.names.next()?;
+            match sorted_dirs(&name_dir) {
+                Ok(versions) => self.versions = versions.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+pub struct Packages {
+    dirs: PackageDirs,
+}
+
+impl Iterator for Packages {
+    type Item = Result<(PackageName, OpamVersion, OpamAST), RepositoryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dir = self.dirs.next()?;
+        Some(dir.and_then(|dir| {
+            let ast = dir.parse()?;
+            Ok((dir.name, dir.version, ast))
+        }))
+    }
+}
+
+fn package_dir(path: PathBuf) -> Result<PackageDir, RepositoryError> {
+    let parent = path.parent().and_then(Path::file_name);
+    let atom = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(split_atom);
+    match atom {
+        Some((name, version)) if parent == Some(name.as_str().as_ref()) => Ok(PackageDir {
+            name,
+            version,
+            path,
+        }),
+        _ => Err(RepositoryError::Layout(path)),
+    }
+}
+
+fn sorted_dirs(path: &Path) -> Result<Vec<PathBuf>, RepositoryError> {
+    let io_error = |e| RepositoryError::Io(path.to_path_buf(), e);
+    let mut dirs = vec![];
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn collect_files(
+    dir: &Path,
+    prefix: &Path,
+    files: &mut Vec<PathBuf>,
+) -> Result<(), RepositoryError> {
+    let io_error = |e| RepositoryError::Io(dir.to_path_buf(), e);
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let relative = prefix.join(entry.file_name());
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            collect_files(&entry.path(), &relative, files)?;
+        } else {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
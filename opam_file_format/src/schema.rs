@@ -0,0 +1,522 @@
+//! Expected shape of the fields of opam files.
+//!
+//! The grammar doesn't know what a field holds, so `depends: true` or `version: [1 2]` parse
+//! fine. A [`Schema`] maps field names to the [`Kind`] of value they expect, and
+//! [`Schema::validate`] reports the fields that don't match, with the span of the offending
+//! value.
+//!
+//! Schemas are provided for the files this crate models. They can be extended with more fields,
+//! e.g. `Schema::opam().field("x-ci-accept-failures", Kind::Strings)`, and given a kind for the
+//! `x-` fields they don't list.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+
+use crate::error::Error;
+use crate::fields::Items;
+use crate::format_version::FormatVersion;
+use crate::install::InstallSection;
+use crate::lexer::{Relop, Span};
+use crate::parser::{
+    parse_str_with_spans, section_kind, Item, ItemSpan, OpamAST, SourceMap, Value, ValueSpan,
+};
+
+#[derive(Debug, Clone)]
+pub enum Kind {
+    /// Anything the grammar accepts.
+    Any,
+    Bool,
+    Int,
+    String,
+    /// A string, or a list of strings.
+    Strings,
+    /// A list of identifiers, as in `flags`.
+    Idents,
+    /// A boolean, a string or a list of strings, as the `variables` of `.config` files.
+    Variable,
+    /// Strings with an optional filter each, as in `patches` or `messages`.
+    FilteredStrings,
+    /// Lists of strings, as in `extra-files` or `pin-depends`.
+    StringLists,
+    /// `"name" {constraint}` atoms combined with `&` and `|`, as in `depends`.
+    Formula,
+    /// A boolean expression over variables, as in `available`.
+    Filter,
+    /// Commands made of arguments, each with an optional filter, as in `build`.
+    Commands,
+    /// `VAR = "value"` environment updates, as in `setenv`.
+    EnvUpdates,
+    /// `"src" {"dst"}` file mappings of `.install` files.
+    FileMappings,
+    /// System packages with an optional filter, as in `depexts`.
+    Depexts,
+    Section(Schema),
+}
+
+impl Kind {
+    /// What the kind expects, as used in messages: "expected a package formula".
+    pub fn description(&self) -> &'static str {
+        match self {
+            Kind::Any => "any value",
+            Kind::Bool => "a boolean",
+            Kind::Int => "an integer",
+            Kind::String => "a string",
+            Kind::Strings => "a string or a list of strings",
+            Kind::Idents => "a list of identifiers",
+            Kind::Variable => "a boolean, a string or a list of strings",
+            Kind::FilteredStrings => "a list of strings with optional filters",
+            Kind::StringLists => "a list of lists of strings",
+            Kind::Formula => "a package formula",
+            Kind::Filter => "a filter",
+            Kind::Commands => "a list of commands",
+            Kind::EnvUpdates => "a list of environment updates",
+            Kind::FileMappings => "a list of file mappings",
+            Kind::Depexts => "a list of system packages with optional filters",
+            Kind::Section(_) => "a section",
+        }
+    }
+}
+
+/// Fields of a kind of file, or of a section.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub name: String,
+    fields: IndexMap<String, Kind>,
+    extensions: Option<Box<Kind>>,
+    others: Option<Box<Kind>>,
+}
+
+impl Schema {
+    /// A schema without fields, where `x-` fields are invalid.
+    pub fn new(name: impl Into<String>) -> Self {
+        Schema {
+            name: name.into(),
+            fields: IndexMap::new(),
+            extensions: None,
+            others: None,
+        }
+    }
+
+    /// Adds a field, or changes the kind of an existing one. Sections are fields of kind
+    /// [`Kind::Section`], keyed by their kind.
+    pub fn field(mut self, name: impl Into<String>, kind: Kind) -> Self {
+        self.fields.insert(name.into(), kind);
+        self
+    }
+
+    /// The kind of `x-` fields that aren't listed.
+    pub fn extensions(mut self, kind: Kind) -> Self {
+        self.extensions = Some(Box::new(kind));
+        self
+    }
+
+    /// The kind of any field that isn't listed, for sections whose fields are free.
+    pub fn others(mut self, kind: Kind) -> Self {
+        self.others = Some(Box::new(kind));
+        self
+    }
+
+    /// The kind `field` expects, or `None` when it isn't valid here.
+    pub fn get(&self, field: &str) -> Option<&Kind> {
+        self.fields
+            .get(field)
+            .or_else(|| {
+                self.extensions
+                    .as_deref()
+                    .filter(|_| field.starts_with("x-"))
+            })
+            .or(self.others.as_deref())
+    }
+
+    /// The listed fields, in order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &Kind)> {
+        self.fields.iter().map(|(name, kind)| (name.as_str(), kind))
+    }
+
+    /// Package definitions, `opam` files.
+    pub fn opam() -> Self {
+        Schema::new("opam")
+            .field("opam-version", Kind::String)
+            .field("name", Kind::String)
+            .field("version", Kind::String)
+            .field("maintainer", Kind::Strings)
+            .field("authors", Kind::Strings)
+            .field("author", Kind::Strings)
+            .field("license", Kind::Strings)
+            .field("homepage", Kind::Strings)
+            .field("doc", Kind::Strings)
+            .field("bug-reports", Kind::Strings)
+            .field("dev-repo", Kind::String)
+            .field("tags", Kind::Strings)
+            .field("synopsis", Kind::String)
+            .field("description", Kind::String)
+            .field("build", Kind::Commands)
+            .field("install", Kind::Commands)
+            .field("remove", Kind::Commands)
+            .field("run-test", Kind::Commands)
+            .field("depends", Kind::Formula)
+            .field("depopts", Kind::Formula)
+            .field("conflicts", Kind::Formula)
+            .field("conflict-class", Kind::Strings)
+            .field("depexts", Kind::Depexts)
+            .field("messages", Kind::FilteredStrings)
+            .field("post-messages", Kind::FilteredStrings)
+            .field("available", Kind::Filter)
+            .field("flags", Kind::Idents)
+            .field("features", Kind::Any)
+            .field("setenv", Kind::EnvUpdates)
+            .field("build-env", Kind::EnvUpdates)
+            .field("substs", Kind::Strings)
+            .field("patches", Kind::FilteredStrings)
+            .field("extra-files", Kind::StringLists)
+            .field("pin-depends", Kind::StringLists)
+            .field("libraries", Kind::Strings)
+            .field("syntax", Kind::Strings)
+            .field("build-test", Kind::Commands)
+            .field("build-doc", Kind::Commands)
+            .field("ocaml-version", Kind::Any)
+            .field("os", Kind::Any)
+            .field("url", Kind::Section(Schema::url()))
+            .field("extra-source", Kind::Section(Schema::url()))
+            .extensions(Kind::Any)
+    }
+
+    /// `url` and `extra-source` sections.
+    pub fn url() -> Self {
+        Schema::new("url")
+            .field("src", Kind::String)
+            .field("checksum", Kind::Strings)
+            .field("mirrors", Kind::Strings)
+            .field("swhid", Kind::String)
+    }
+
+    /// `<package>.install` files.
+    pub fn install() -> Self {
+        InstallSection::ALL.iter().fold(
+            Schema::new("install").field("opam-version", Kind::String),
+            |schema, section| schema.field(section.name(), Kind::FileMappings),
+        )
+    }
+
+    /// `<package>.config` files.
+    pub fn config() -> Self {
+        Schema::new("config")
+            .field("opam-version", Kind::String)
+            .field(
+                "variables",
+                Kind::Section(Schema::new("variables").others(Kind::Variable)),
+            )
+            .field("file-depends", Kind::StringLists)
+    }
+
+    /// The `repo` file at the root of a repository.
+    pub fn repo() -> Self {
+        Schema::new("repo")
+            .field("opam-version", Kind::String)
+            .field("browse", Kind::String)
+            .field("upstream", Kind::String)
+            .field("redirect", Kind::FilteredStrings)
+            .field("announce", Kind::FilteredStrings)
+            .field("stamp", Kind::String)
+    }
+
+    /// Switch export files, with the package definitions they embed.
+    pub fn switch_export() -> Self {
+        Schema::new("switch export")
+            .field("opam-version", Kind::String)
+            .field("compiler", Kind::Strings)
+            .field("roots", Kind::Strings)
+            .field("installed", Kind::Strings)
+            .field("pinned", Kind::Strings)
+            .field("package", Kind::Section(Schema::opam()))
+    }
+
+    /// Parses `source` and checks it against the schema.
+    pub fn validate_str(&self, source: &str) -> Result<Vec<Violation>, Error> {
+        let (ast, spans) = parse_str_with_spans(source)?;
+        Ok(self.validate(&ast, &spans))
+    }
+
+    /// Checks the fields of `ast` against the schema, `spans` locating its items. An empty
+    /// `SourceMap` may be given, in which case violations have no span.
+    pub fn validate(&self, ast: &OpamAST, spans: &SourceMap) -> Vec<Violation> {
+        let mut violations = vec![];
+        self.validate_items(&ast.items, Some(&spans.items), None, &mut violations);
+        violations
+    }
+
+    fn validate_items(
+        &self,
+        items: &Items,
+        spans: Option<&IndexMap<String, ItemSpan>>,
+        parent: Option<&str>,
+        violations: &mut Vec<Violation>,
+    ) {
+        for (key, item) in items {
+            let span = spans.and_then(|spans| spans.get(key));
+            let field = match parent {
+                Some(parent) => format!("{}.{}", parent, key),
+                None => key.clone(),
+            };
+            let mut report = |problem: Problem, at: Option<Span>| {
+                violations.push(Violation {
+                    field: field.clone(),
+                    problem,
+                    span: at.or_else(|| span.map(|span| span.span)),
+                })
+            };
+            match (self.get(section_kind(key)), item.as_ref()) {
+                (None, _) => report(Problem::UnknownField, span.map(|span| span.key)),
+                (Some(Kind::Section(schema)), Item::Section { items, .. }) => schema
+                    .validate_items(
+                        items,
+                        span.map(|span| &span.items),
+                        Some(&field),
+                        violations,
+                    ),
+                (Some(Kind::Section(_)), Item::Variable(_)) => {
+                    report(Problem::Expected("a section"), None)
+                }
+                (Some(_), Item::Section { .. }) => report(Problem::Expected("a field"), None),
+                (Some(kind), Item::Variable(value)) => {
+                    if let Err(at) = check(kind, value, span.and_then(|span| span.value.as_ref())) {
+                        report(Problem::Expected(kind.description()), at)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A field that isn't valid in its schema, or whose value doesn't have the expected kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The item key, prefixed by the keys of the enclosing sections: `url.checksum`.
+    pub field: String,
+    pub problem: Problem,
+    /// The offending value, or the item when the value is fine but the field isn't.
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    UnknownField,
+    /// The value isn't of the kind described, see [`Kind::description`].
+    Expected(&'static str),
+    /// The field, or a construct of its value, needs a later version of the file format.
+    Unsupported {
+        construct: String,
+        since: FormatVersion,
+    },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.problem {
+            Problem::UnknownField => write!(f, "unknown field `{}`", self.field),
+            Problem::Expected(expected) => {
+                write!(f, "invalid field `{}`: expected {}", self.field, expected)
+            }
+            Problem::Unsupported { construct, since } => write!(
+                f,
+                "invalid field `{}`: {} requires opam-version {}",
+                self.field, construct, since
+            ),
+        }
+    }
+}
+
+// A value and its location, when known. Checks fail with the location of the offending value.
+type Located<'a> = (&'a Value, Option<&'a ValueSpan>);
+type Check = Result<(), Option<Span>>;
+
+fn fail(span: Option<&ValueSpan>) -> Check {
+    Err(span.map(|span| span.span))
+}
+
+fn children<'a>(value: &'a Value, span: Option<&'a ValueSpan>) -> Vec<Located<'a>> {
+    value
+        .children()
+        .into_iter()
+        .enumerate()
+        .map(|(i, child)| (child, span.and_then(|span| span.children.get(i))))
+        .collect()
+}
+
+/// The elements of a list. A single element may be written without brackets.
+fn elements<'a>(value: &'a Value, span: Option<&'a ValueSpan>) -> Vec<Located<'a>> {
+    match value {
+        Value::List(_) => children(value, span),
+        value => vec![(value, span)],
+    }
+}
+
+/// Splits `value {options}` into the value and its options.
+fn options<'a>(value: &'a Value, span: Option<&'a ValueSpan>) -> (Located<'a>, Vec<Located<'a>>) {
+    match value {
+        Value::Option(..) => {
+            let mut children = children(value, span);
+            let value = children.remove(0);
+            (value, children)
+        }
+        value => ((value, span), vec![]),
+    }
+}
+
+fn all(values: Vec<Located>, check: impl Fn(&Value, Option<&ValueSpan>) -> Check) -> Check {
+    values
+        .into_iter()
+        .try_for_each(|(value, span)| check(value, span))
+}
+
+fn check(kind: &Kind, value: &Value, span: Option<&ValueSpan>) -> Check {
+    match kind {
+        Kind::Any => Ok(()),
+        Kind::Bool => match value {
+            Value::Bool(_) => Ok(()),
+            _ => fail(span),
+        },
+        Kind::Int => match value {
+            Value::Int(_) => Ok(()),
+            _ => fail(span),
+        },
+        Kind::String => string(value, span),
+        Kind::Strings => all(elements(value, span), string),
+        Kind::Idents => all(elements(value, span), |value, span| match value {
+            Value::Ident(_) => Ok(()),
+            _ => fail(span),
+        }),
+        Kind::Variable => match value {
+            Value::Bool(_) => Ok(()),
+            value => all(elements(value, span), string),
+        },
+        Kind::FilteredStrings => all(elements(value, span), |value, span| {
+            let ((value, span), options) = options(value, span);
+            string(value, span)?;
+            single_filter(options)
+        }),
+        Kind::StringLists => all(elements(value, span), |value, span| match value {
+            Value::List(_) => all(children(value, span), string),
+            value => string(value, span),
+        }),
+        Kind::Formula => all(elements(value, span), formula),
+        Kind::Filter => filter(value, span),
+        Kind::Commands => commands(value, span),
+        // `=` lexes as a relational operator.
+        Kind::EnvUpdates => all(elements(value, span), |value, span| match value {
+            Value::EnvBinding(..) | Value::Relop(Relop::Eq, ..) => {
+                match children(value, span).as_slice() {
+                    [(Value::Ident(_), _), (Value::String(_), _)] => Ok(()),
+                    [(Value::Ident(_), _), (_, span)] | [(_, span), _] => fail(*span),
+                    _ => fail(span),
+                }
+            }
+            _ => fail(span),
+        }),
+        Kind::FileMappings => all(elements(value, span), |value, span| {
+            let ((value, span), options) = options(value, span);
+            string(value, span)?;
+            match options.as_slice() {
+                [] => Ok(()),
+                [(value, span)] => string(value, *span),
+                [_, (_, span), ..] => fail(*span),
+            }
+        }),
+        Kind::Depexts => all(elements(value, span), |value, span| {
+            let ((value, span), options) = options(value, span);
+            match value {
+                Value::List(_) => all(children(value, span), string)?,
+                value => string(value, span)?,
+            }
+            single_filter(options)
+        }),
+        Kind::Section(_) => fail(span),
+    }
+}
+
+fn string(value: &Value, span: Option<&ValueSpan>) -> Check {
+    match value {
+        Value::String(_) => Ok(()),
+        _ => fail(span),
+    }
+}
+
+fn single_filter(options: Vec<Located>) -> Check {
+    match options.as_slice() {
+        [] => Ok(()),
+        [(value, span)] => filter(value, *span),
+        [_, (_, span), ..] => fail(*span),
+    }
+}
+
+/// `a = "b" & !c`, over variables, strings, booleans and integers.
+fn filter(value: &Value, span: Option<&ValueSpan>) -> Check {
+    match value {
+        Value::Bool(_) | Value::Int(_) | Value::String(_) | Value::Ident(_) => Ok(()),
+        Value::Relop(..) | Value::Logop(..) | Value::Pfxop(..) | Value::Group(_) => {
+            all(children(value, span), filter)
+        }
+        _ => fail(span),
+    }
+}
+
+/// `"name" {>= "1.0" & with-test}` atoms, combined with `&`, `|` and parentheses.
+fn formula(value: &Value, span: Option<&ValueSpan>) -> Check {
+    match value {
+        Value::String(_) => Ok(()),
+        Value::Option(..) => {
+            let ((value, span), constraints) = options(value, span);
+            string(value, span)?;
+            all(constraints, constraint)
+        }
+        Value::Logop(..) | Value::Group(_) => all(children(value, span), formula),
+        _ => fail(span),
+    }
+}
+
+/// Version constraints such as `>= "1.0"`, mixed with filters over variables.
+fn constraint(value: &Value, span: Option<&ValueSpan>) -> Check {
+    match value {
+        Value::PrefixRelop(..) => all(children(value, span), |value, span| match value {
+            Value::String(_) | Value::Ident(_) => Ok(()),
+            _ => fail(span),
+        }),
+        Value::Logop(..) | Value::Pfxop(..) | Value::Group(_) => {
+            all(children(value, span), constraint)
+        }
+        value => filter(value, span),
+    }
+}
+
+/// `[[args] {filter}]`, where a single command may be written without the outer brackets.
+fn commands(value: &Value, span: Option<&ValueSpan>) -> Check {
+    let commands = elements(value, span);
+    if !commands.iter().any(|(command, _)| has_list(command)) {
+        return all(commands, argument);
+    }
+    all(commands, |command, span| {
+        let ((command, span), options) = options(command, span);
+        match command {
+            Value::List(_) => all(children(command, span), argument)?,
+            _ => return fail(span),
+        }
+        single_filter(options)
+    })
+}
+
+fn has_list(value: &Value) -> bool {
+    match value {
+        Value::List(_) => true,
+        Value::Option(value, _) => matches!(value.as_ref(), Value::List(_)),
+        _ => false,
+    }
+}
+
+fn argument(value: &Value, span: Option<&ValueSpan>) -> Check {
+    let ((value, span), options) = options(value, span);
+    match value {
+        Value::String(_) | Value::Ident(_) => single_filter(options),
+        _ => fail(span),
+    }
+}
@@ -0,0 +1,863 @@
+//! Dependency resolution over a set of packages, such as a repository.
+//!
+//! A solution installs at most one version of each package such that the requested dependencies
+//! hold, every installed version is `available` and has its `depends` installed, no `conflicts`
+//! are installed, installed `depopts` satisfy their constraints, and no two installed packages
+//! share a `conflict-class`. Filters are evaluated under an [`Env`], as for
+//! [`Graph`](crate::graph::Graph), and newer versions are preferred.
+//!
+//! The problem is encoded as boolean clauses, one variable per package version and one per
+//! conjunction within alternatives, and solved by conflict-driven clause learning. Each clause comes from a [`Fact`] about the packages; when
+//! there is no solution, the facts that the final conflict derives from, reduced to a minimal
+//! set, explain why.
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::error::FormatError;
+use crate::fields::{strings_field, variable};
+use crate::filter::{self, Env, FilterValue};
+use crate::formula::{Dependency, Formula};
+use crate::graph::Node;
+use crate::package::PackageName;
+use crate::parser::{OpamAST, Value};
+use crate::version::OpamVersion;
+
+/// A reason for a clause of the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fact {
+    /// A dependency of the request.
+    Requested(Dependency),
+    /// A version whose `available` filter doesn't hold.
+    Unavailable(Node),
+    /// A version needs a formula, as one element of its `depends`.
+    Depends { package: Node, formula: Formula },
+    /// A version accepts an optional dependency only within its constraint.
+    Depopts {
+        package: Node,
+        dependency: Dependency,
+    },
+    Conflicts {
+        package: Node,
+        dependency: Dependency,
+    },
+    /// At most one package of a conflict class can be installed.
+    ConflictClass(String),
+    /// At most one version of a package can be installed.
+    SingleVersion(PackageName),
+    /// No version of the set satisfies a dependency. Only found in explanations.
+    Missing(Dependency),
+}
+
+impl Display for Fact {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Fact::Requested(dependency) => write!(f, "{} is requested", dependency),
+            Fact::Unavailable(package) => write!(f, "{} is not available", package.atom()),
+            Fact::Depends { package, formula } => {
+                write!(f, "{} depends on {}", package.atom(), formula.inline())
+            }
+            Fact::Depopts {
+                package,
+                dependency,
+            } => write!(
+                f,
+                "{} requires {} when installed",
+                package.atom(),
+                dependency
+            ),
+            Fact::Conflicts {
+                package,
+                dependency,
+            } => write!(f, "{} conflicts with {}", package.atom(), dependency),
+            Fact::ConflictClass(class) => write!(
+                f,
+                "packages of conflict class {} can't be installed together",
+                class
+            ),
+            Fact::SingleVersion(name) => write!(f, "only one version of {} can be installed", name),
+            Fact::Missing(dependency) => write!(f, "no version satisfies {}", dependency),
+        }
+    }
+}
+
+/// Why a request has no solution: facts that can't all hold, none of which could be dropped.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    pub facts: Vec<Fact>,
+}
+
+/// The facts, one per line.
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, fact) in self.facts.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", fact)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SolveError {
+    /// A field read by the solver doesn't have the expected shape.
+    Format(FormatError),
+    Unsatisfiable(Explanation),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Format(e) => write!(f, "{}", e),
+            SolveError::Unsatisfiable(explanation) => {
+                write!(f, "no solution:\n{}", explanation)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<FormatError> for SolveError {
+    fn from(e: FormatError) -> Self {
+        SolveError::Format(e)
+    }
+}
+
+/// The package versions to install, sorted by name.
+#[derive(Debug, Clone, Default)]
+pub struct Solution {
+    pub packages: Vec<Node>,
+}
+
+impl Solution {
+    /// The version of a package the solution installs.
+    pub fn version(&self, name: &PackageName) -> Option<&OpamVersion> {
+        self.packages
+            .iter()
+            .find(|node| node.name == *name)
+            .map(|node| &node.version)
+    }
+}
+
+/// Package definitions read for resolution, with their filters evaluated. Reading them once lets
+/// many requests be solved, as when checking a whole repository.
+#[derive(Debug, Clone, Default)]
+pub struct Solver {
+    nodes: Vec<Node>,
+    versions: Vec<Version>,
+    // The packages of each name, newest first.
+    by_name: HashMap<PackageName, Vec<usize>>,
+}
+
+impl Solver {
+    /// Reads package definitions, evaluating filters under `env`. The `name` and `version`
+    /// variables are those of each package.
+    pub fn new(
+        packages: &[(PackageName, OpamVersion, OpamAST)],
+        env: &Env,
+    ) -> Result<Self, FormatError> {
+        let mut solver = Solver::default();
+        for (name, version, ast) in packages {
+            solver
+                .by_name
+                .entry(name.clone())
+                .or_default()
+                .push(solver.nodes.len());
+            solver.nodes.push(Node {
+                name: name.clone(),
+                version: version.clone(),
+            });
+            solver
+                .versions
+                .push(Version::read(name, version, ast, env)?);
+        }
+        let nodes = &solver.nodes;
+        for packages in solver.by_name.values_mut() {
+            packages.sort_by(|&i, &j| nodes[j].version.cmp(&nodes[i].version));
+        }
+        Ok(solver)
+    }
+
+    /// The package versions, in the order they were given.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Finds versions to install for `request`. Only packages the request can reach through
+    /// `depends` are considered.
+    pub fn solve(&self, request: &[Dependency]) -> Result<Solution, Explanation> {
+        let problem = Problem::build(self, request);
+        match problem.run(None) {
+            Ok(model) => {
+                let mut packages: Vec<Node> = problem
+                    .nodes
+                    .iter()
+                    .enumerate()
+                    .filter(|&(var, _)| model[var])
+                    .map(|(_, node)| node.clone())
+                    .collect();
+                packages.sort_by(|n1, n2| n1.name.cmp(&n2.name));
+                Ok(Solution { packages })
+            }
+            Err(core) => Err(problem.explain(problem.minimize(core))),
+        }
+    }
+
+    /// Whether `request` has a solution. Failures aren't explained, which saves the work of
+    /// reducing the conflict.
+    pub fn satisfiable(&self, request: &[Dependency]) -> bool {
+        Problem::build(self, request).run(None).is_ok()
+    }
+}
+
+/// Finds versions of `packages` to install for `request`, evaluating filters under `env`, as
+/// [`Solver::solve`] does.
+pub fn solve(
+    packages: &[(PackageName, OpamVersion, OpamAST)],
+    env: &Env,
+    request: &[Dependency],
+) -> Result<Solution, SolveError> {
+    Solver::new(packages, env)?
+        .solve(request)
+        .map_err(SolveError::Unsatisfiable)
+}
+
+// The fields of a package version the solver reads, with filters evaluated.
+#[derive(Debug, Clone)]
+struct Version {
+    available: bool,
+    depends: Formula,
+    depopts: Formula,
+    conflicts: Formula,
+    classes: Vec<String>,
+}
+
+impl Version {
+    fn read(
+        name: &PackageName,
+        version: &OpamVersion,
+        ast: &OpamAST,
+        env: &Env,
+    ) -> Result<Self, FormatError> {
+        let lookup = |variable: &str| match variable {
+            "name" => Some(FilterValue::String(name.to_string())),
+            "version" => Some(FilterValue::String(version.to_string())),
+            variable => env.get(variable).cloned(),
+        };
+        let formula = |field: &str| -> Result<Formula, FormatError> {
+            Ok(variable(&ast.items, field)?
+                .map_or_else(Formula::empty, |value| Formula::from_value(value, &lookup)))
+        };
+        // opam 1.2 wrote `available: [filter]`.
+        let available = match variable(&ast.items, "available")? {
+            Some(Value::List(filters)) if filters.len() == 1 => {
+                filter::eval_bool(&filters[0], &lookup)
+            }
+            Some(filter) => filter::eval_bool(filter, &lookup),
+            None => true,
+        };
+        Ok(Version {
+            available,
+            depends: formula("depends")?,
+            depopts: formula("depopts")?,
+            conflicts: formula("conflicts")?,
+            classes: strings_field(&ast.items, "conflict-class")?,
+        })
+    }
+}
+
+// Literals are variables shifted left, with the low bit set when negated.
+type Lit = usize;
+
+fn positive(var: usize) -> Lit {
+    var << 1
+}
+
+fn negative(var: usize) -> Lit {
+    var << 1 | 1
+}
+
+// The problem as clauses over package versions, numbered like `nodes`, and auxiliary variables.
+#[derive(Default)]
+struct Problem {
+    nodes: Vec<Node>,
+    versions: HashMap<PackageName, Vec<usize>>,
+    vars: usize,
+    facts: Vec<Fact>,
+    clauses: Vec<(Vec<Lit>, usize)>,
+    // Where the variables of conjunctions come in the search: with their first package.
+    ranks: HashMap<usize, usize>,
+}
+
+impl Problem {
+    fn build(solver: &Solver, request: &[Dependency]) -> Self {
+        // Variables are numbered in breadth-first order from the request, newest versions first,
+        // which the search follows.
+        let mut problem = Problem::default();
+        let mut versions = vec![];
+        let mut queue: VecDeque<&PackageName> = request.iter().map(|d| &d.name).collect();
+        let mut seen: HashSet<&PackageName> = queue.iter().copied().collect();
+        while let Some(name) = queue.pop_front() {
+            for &i in solver.by_name.get(name).into_iter().flatten() {
+                let version = &solver.versions[i];
+                for dependency in version.depends.dependencies() {
+                    if let Some((name, _)) = solver.by_name.get_key_value(&dependency.name) {
+                        if seen.insert(name) {
+                            queue.push_back(name);
+                        }
+                    }
+                }
+                problem
+                    .versions
+                    .entry(name.clone())
+                    .or_default()
+                    .push(problem.nodes.len());
+                problem.nodes.push(solver.nodes[i].clone());
+                versions.push(version);
+            }
+        }
+        problem.vars = problem.nodes.len();
+
+        for dependency in request {
+            let lits = problem.matching(dependency).map(positive).collect();
+            problem.add(lits, Fact::Requested(dependency.clone()));
+        }
+        let mut names: Vec<PackageName> = problem.versions.keys().cloned().collect();
+        names.sort();
+        for name in names {
+            let vars = problem.versions[&name].clone();
+            let fact = problem.fact(Fact::SingleVersion(name));
+            problem.at_most_one(&vars, fact);
+        }
+        let mut classes: HashMap<&str, Vec<PackageName>> = HashMap::new();
+        for (var, version) in versions.iter().enumerate() {
+            let package = problem.nodes[var].clone();
+            if !version.available {
+                problem.add(vec![negative(var)], Fact::Unavailable(package.clone()));
+            }
+            let elements = match &version.depends {
+                Formula::And(formulas) => formulas.as_slice(),
+                formula => std::slice::from_ref(formula),
+            };
+            for formula in elements {
+                let fact = problem.fact(Fact::Depends {
+                    package: package.clone(),
+                    formula: formula.clone(),
+                });
+                let mut lits = vec![negative(var)];
+                problem.implying(formula, fact, &mut lits);
+                problem.clauses.push((lits, fact));
+            }
+            for dependency in version.depopts.dependencies() {
+                let excluded: Vec<usize> = problem
+                    .versions
+                    .get(&dependency.name)
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .filter(|&other| !dependency.constraint.matches(&problem.nodes[other].version))
+                    .collect();
+                problem.exclude(var, &excluded, || Fact::Depopts {
+                    package: package.clone(),
+                    dependency: dependency.clone(),
+                });
+            }
+            for dependency in version.conflicts.dependencies() {
+                let excluded: Vec<usize> = problem
+                    .matching(dependency)
+                    .filter(|&other| other != var)
+                    .collect();
+                problem.exclude(var, &excluded, || Fact::Conflicts {
+                    package: package.clone(),
+                    dependency: dependency.clone(),
+                });
+            }
+            for class in &version.classes {
+                let names = classes.entry(class).or_default();
+                if !names.contains(&package.name) {
+                    names.push(package.name.clone());
+                }
+            }
+        }
+
+        // Each package of a class gets a variable implied by its versions, at most one of
+        // which can hold.
+        let mut classes: Vec<(&str, Vec<PackageName>)> = classes.into_iter().collect();
+        classes.sort();
+        for (class, names) in classes {
+            if names.len() < 2 {
+                continue;
+            }
+            let fact = problem.fact(Fact::ConflictClass(class.to_string()));
+            let mut installed = vec![];
+            for name in names {
+                let package = problem.vars;
+                problem.vars += 1;
+                for &var in &problem.versions[&name] {
+                    if versions[var].classes.iter().any(|c| c == class) {
+                        problem
+                            .clauses
+                            .push((vec![negative(var), positive(package)], fact));
+                    }
+                }
+                installed.push(package);
+            }
+            problem.at_most_one(&installed, fact);
+        }
+        problem
+    }
+
+    fn fact(&mut self, fact: Fact) -> usize {
+        self.facts.push(fact);
+        self.facts.len() - 1
+    }
+
+    fn add(&mut self, lits: Vec<Lit>, fact: Fact) {
+        let fact = self.fact(fact);
+        self.clauses.push((lits, fact));
+    }
+
+    // The versions satisfying a dependency, newest first.
+    fn matching<'a>(&'a self, dependency: &'a Dependency) -> impl Iterator<Item = usize> + 'a {
+        self.versions
+            .get(&dependency.name)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(move |&var| dependency.constraint.matches(&self.nodes[var].version))
+    }
+
+    // Adds literals to `lits`, one of which implies `formula`: the versions matching its
+    // dependencies, and for conjunctions within alternatives, an auxiliary variable implying each
+    // of their elements, as in the Tseitin encoding. The clauses added share the fact.
+    fn implying(&mut self, formula: &Formula, fact: usize, lits: &mut Vec<Lit>) {
+        match formula {
+            Formula::Atom(dependency) => lits.extend(self.matching(dependency).map(positive)),
+            Formula::Or(formulas) => {
+                for formula in formulas {
+                    self.implying(formula, fact, lits);
+                }
+            }
+            Formula::And(formulas) => {
+                let conjunction = self.vars;
+                self.vars += 1;
+                let mut rank = conjunction;
+                for formula in formulas {
+                    let mut clause = vec![negative(conjunction)];
+                    self.implying(formula, fact, &mut clause);
+                    rank = clause[1..]
+                        .iter()
+                        .map(|&lit| self.rank(lit >> 1))
+                        .fold(rank, usize::min);
+                    self.clauses.push((clause, fact));
+                }
+                self.ranks.insert(conjunction, rank);
+                lits.push(positive(conjunction));
+            }
+        }
+    }
+
+    fn rank(&self, var: usize) -> usize {
+        self.ranks.get(&var).copied().unwrap_or(var)
+    }
+
+    // `var` excludes each of `excluded`, the clauses sharing one fact.
+    fn exclude(&mut self, var: usize, excluded: &[usize], fact: impl FnOnce() -> Fact) {
+        if excluded.is_empty() {
+            return;
+        }
+        let fact = self.fact(fact());
+        for &other in excluded {
+            self.clauses
+                .push((vec![negative(var), negative(other)], fact));
+        }
+    }
+
+    // At most one of `vars` holds: pairwise exclusions for a few variables, a sequential counter
+    // for more, whose auxiliary variable `i` holds when one of the first `i + 1` does.
+    fn at_most_one(&mut self, vars: &[usize], fact: usize) {
+        if vars.len() <= 6 {
+            for (i, &var) in vars.iter().enumerate() {
+                for &other in &vars[i + 1..] {
+                    self.clauses
+                        .push((vec![negative(var), negative(other)], fact));
+                }
+            }
+            return;
+        }
+        let counter = self.vars;
+        self.vars += vars.len() - 1;
+        for (i, &var) in vars.iter().enumerate() {
+            if i + 1 < vars.len() {
+                self.clauses
+                    .push((vec![negative(var), positive(counter + i)], fact));
+            }
+            if i > 0 {
+                self.clauses
+                    .push((vec![negative(var), negative(counter + i - 1)], fact));
+                if i + 1 < vars.len() {
+                    self.clauses
+                        .push((vec![negative(counter + i - 1), positive(counter + i)], fact));
+                }
+            }
+        }
+    }
+
+    // Solves the clauses of the facts `kept` marks, or all of them. On failure, returns the
+    // facts the final conflict derives from.
+    fn run(&self, kept: Option<&[bool]>) -> Result<Vec<bool>, BTreeSet<usize>> {
+        let mut sat = Sat::new((0..self.vars).map(|var| self.rank(var)).collect());
+        for (lits, fact) in &self.clauses {
+            if kept.is_none_or(|kept| kept[*fact]) {
+                sat.add(lits.clone(), Origin::Fact(*fact));
+            }
+        }
+        sat.solve()
+    }
+
+    // Drops the facts of the core that aren't needed for the conflict, one at a time.
+    fn minimize(&self, mut core: BTreeSet<usize>) -> BTreeSet<usize> {
+        let mut needed = BTreeSet::new();
+        while let Some(&fact) = core.difference(&needed).next() {
+            let mut kept = vec![false; self.facts.len()];
+            for &other in &core {
+                kept[other] = other != fact;
+            }
+            match self.run(Some(&kept)) {
+                Ok(_) => {
+                    needed.insert(fact);
+                }
+                Err(smaller) => core = smaller,
+            }
+        }
+        core
+    }
+
+    // The facts of the core, followed by the dependencies no version satisfies.
+    fn explain(&self, core: BTreeSet<usize>) -> Explanation {
+        let mut facts: Vec<Fact> = core.iter().map(|&fact| self.facts[fact].clone()).collect();
+        let mut missing = vec![];
+        for fact in &facts {
+            let dependencies = match fact {
+                Fact::Requested(dependency) => vec![dependency],
+                Fact::Depends { formula, .. } => formula.dependencies(),
+                _ => vec![],
+            };
+            for dependency in dependencies {
+                let fact = Fact::Missing(dependency.clone());
+                if self.matching(dependency).next().is_none() && !missing.contains(&fact) {
+                    missing.push(fact);
+                }
+            }
+        }
+        facts.extend(missing);
+        Explanation { facts }
+    }
+}
+
+// Where a clause comes from: a fact, or the clauses it was learned from.
+enum Origin {
+    Fact(usize),
+    Learned(Vec<usize>),
+}
+
+fn value(values: &[Option<bool>], lit: Lit) -> Option<bool> {
+    values[lit >> 1].map(|value| value != (lit & 1 == 1))
+}
+
+// A CDCL solver with two watched literals per clause. Decisions satisfy the first clause that
+// needs a positive literal, picking the variable ranked first, so that the search follows the
+// order of the variables; other variables end up false.
+//
+// A clause of the problem needs a positive literal when none of its literals is true and its
+// negative ones are all false. Counting both for each clause as variables are assigned tells when
+// a clause starts needing one, and it then waits in a heap by index until a decision is made.
+struct Sat {
+    clauses: Vec<Vec<Lit>>,
+    origins: Vec<Origin>,
+    watches: Vec<Vec<usize>>,
+    ranks: Vec<usize>,
+    // The clauses of the problem each literal appears in.
+    occurrences: Vec<Vec<usize>>,
+    true_lits: Vec<usize>,
+    open_negatives: Vec<usize>,
+    // Clauses that needed a positive literal when pushed, some of which may not anymore.
+    needing: BinaryHeap<Reverse<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    // The length of the trail at each decision.
+    decisions: Vec<usize>,
+    propagated: usize,
+}
+
+impl Sat {
+    fn new(ranks: Vec<usize>) -> Self {
+        let vars = ranks.len();
+        Sat {
+            clauses: vec![],
+            origins: vec![],
+            watches: vec![vec![]; 2 * vars],
+            ranks,
+            occurrences: vec![vec![]; 2 * vars],
+            true_lits: vec![],
+            open_negatives: vec![],
+            needing: BinaryHeap::new(),
+            values: vec![None; vars],
+            levels: vec![0; vars],
+            reasons: vec![None; vars],
+            trail: vec![],
+            decisions: vec![],
+            propagated: 0,
+        }
+    }
+
+    // Clauses of the problem are added before solving, learned ones while solving, asserting
+    // literal first.
+    fn add(&mut self, mut lits: Vec<Lit>, origin: Origin) -> usize {
+        let index = self.clauses.len();
+        let mut open_negatives = 0;
+        if let Origin::Fact(_) = origin {
+            lits.sort_unstable();
+            lits.dedup();
+            for &lit in &lits {
+                self.occurrences[lit].push(index);
+                open_negatives += lit & 1;
+            }
+            if open_negatives == 0 {
+                self.needing.push(Reverse(index));
+            }
+        }
+        self.true_lits.push(0);
+        self.open_negatives.push(open_negatives);
+        if lits.len() >= 2 {
+            self.watches[lits[0]].push(index);
+            self.watches[lits[1]].push(index);
+        }
+        self.clauses.push(lits);
+        self.origins.push(origin);
+        index
+    }
+
+    fn assign(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit >> 1;
+        self.values[var] = Some(lit & 1 == 0);
+        self.levels[var] = self.decisions.len();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+        for &index in &self.occurrences[lit] {
+            self.true_lits[index] += 1;
+        }
+        for &index in &self.occurrences[negative(var)] {
+            self.open_negatives[index] -= 1;
+            if self.open_negatives[index] == 0 && self.true_lits[index] == 0 {
+                self.needing.push(Reverse(index));
+            }
+        }
+    }
+
+    fn unassign(&mut self, lit: Lit) {
+        let var = lit >> 1;
+        self.values[var] = None;
+        self.reasons[var] = None;
+        for &index in &self.occurrences[negative(var)] {
+            self.open_negatives[index] += 1;
+        }
+        for &index in &self.occurrences[lit] {
+            self.true_lits[index] -= 1;
+            if self.true_lits[index] == 0 && self.open_negatives[index] == 0 {
+                self.needing.push(Reverse(index));
+            }
+        }
+    }
+
+    fn solve(mut self) -> Result<Vec<bool>, BTreeSet<usize>> {
+        for index in 0..self.clauses.len() {
+            match self.clauses[index].as_slice() {
+                [] => return Err(self.core(index)),
+                &[lit] => match value(&self.values, lit) {
+                    Some(false) => return Err(self.core(index)),
+                    Some(true) => (),
+                    None => self.assign(lit, Some(index)),
+                },
+                _ => (),
+            }
+        }
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.decisions.is_empty() {
+                    return Err(self.core(conflict));
+                }
+                let (learned, level, antecedents) = self.analyze(conflict);
+                self.backtrack(level);
+                let asserting = learned[0];
+                let index = self.add(learned, Origin::Learned(antecedents));
+                self.assign(asserting, Some(index));
+            } else if let Some(lit) = self.decide() {
+                self.decisions.push(self.trail.len());
+                self.assign(lit, None);
+            } else {
+                return Ok(self
+                    .values
+                    .iter()
+                    .map(|&value| value == Some(true))
+                    .collect());
+            }
+        }
+    }
+
+    // Propagates the assignments of the trail, returning a clause they falsify.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let falsified = self.trail[self.propagated] ^ 1;
+            self.propagated += 1;
+            let watching = std::mem::take(&mut self.watches[falsified]);
+            let mut kept = Vec::with_capacity(watching.len());
+            let mut conflict = None;
+            for (i, &index) in watching.iter().enumerate() {
+                let clause = &mut self.clauses[index];
+                if clause[0] == falsified {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+                if value(&self.values, first) == Some(true) {
+                    kept.push(index);
+                    continue;
+                }
+                let values = &self.values;
+                if let Some(k) =
+                    (2..clause.len()).find(|&k| value(values, clause[k]) != Some(false))
+                {
+                    clause.swap(1, k);
+                    self.watches[clause[1]].push(index);
+                    continue;
+                }
+                kept.push(index);
+                if value(&self.values, first) == Some(false) {
+                    kept.extend_from_slice(&watching[i + 1..]);
+                    conflict = Some(index);
+                    break;
+                }
+                self.assign(first, Some(index));
+            }
+            self.watches[falsified] = kept;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    // Learns a clause from a conflict by resolving it with the reasons of the literals of the
+    // current level, up to the first unique implication point. Returns the clause, asserting
+    // literal first, the level to go back to, and the clauses used, including the reasons of the
+    // level 0 literals left out.
+    fn analyze(&self, conflict: usize) -> (Vec<Lit>, usize, Vec<usize>) {
+        let level = self.decisions.len();
+        let mut seen = vec![false; self.values.len()];
+        let mut learned = vec![0];
+        let mut antecedents = vec![conflict];
+        let mut pending = 0;
+        let mut position = self.trail.len();
+        let mut clause = conflict;
+        let mut skip = 0;
+        loop {
+            for &lit in &self.clauses[clause][skip..] {
+                let var = lit >> 1;
+                if seen[var] {
+                    continue;
+                }
+                seen[var] = true;
+                if self.levels[var] == level {
+                    pending += 1;
+                } else if self.levels[var] > 0 {
+                    learned.push(lit);
+                } else if let Some(reason) = self.reasons[var] {
+                    antecedents.push(reason);
+                }
+            }
+            let lit = loop {
+                position -= 1;
+                if seen[self.trail[position] >> 1] {
+                    break self.trail[position];
+                }
+            };
+            pending -= 1;
+            if pending == 0 {
+                learned[0] = lit ^ 1;
+                break;
+            }
+            clause = self.reasons[lit >> 1].unwrap();
+            antecedents.push(clause);
+            // The implied literal comes first in its reason.
+            skip = 1;
+        }
+        let mut backjump = 0;
+        if let Some(k) = (1..learned.len()).max_by_key(|&k| self.levels[learned[k] >> 1]) {
+            learned.swap(1, k);
+            backjump = self.levels[learned[1] >> 1];
+        }
+        (learned, backjump, antecedents)
+    }
+
+    fn backtrack(&mut self, level: usize) {
+        if let Some(&start) = self.decisions.get(level) {
+            for lit in self.trail.split_off(start) {
+                self.unassign(lit);
+            }
+            self.decisions.truncate(level);
+            self.propagated = self.trail.len();
+        }
+    }
+
+    // A literal for the first clause that isn't satisfied and can't be by setting the remaining
+    // variables false. The clause stays in the heap until it's found satisfied.
+    fn decide(&mut self) -> Option<Lit> {
+        while let Some(&Reverse(index)) = self.needing.peek() {
+            if self.true_lits[index] == 0 && self.open_negatives[index] == 0 {
+                let (values, ranks) = (&self.values, &self.ranks);
+                let candidate = self.clauses[index]
+                    .iter()
+                    .copied()
+                    .filter(|&lit| lit & 1 == 0 && value(values, lit).is_none())
+                    .min_by_key(|&lit| (ranks[lit >> 1], lit));
+                if candidate.is_some() {
+                    return candidate;
+                }
+            }
+            self.needing.pop();
+        }
+        None
+    }
+
+    // The facts a clause derives from, following learned clauses and the reasons of the level 0
+    // literals they rely on.
+    fn core(&self, conflict: usize) -> BTreeSet<usize> {
+        let mut facts = BTreeSet::new();
+        let mut visited = vec![false; self.clauses.len()];
+        let mut stack = vec![conflict];
+        while let Some(clause) = stack.pop() {
+            if std::mem::replace(&mut visited[clause], true) {
+                continue;
+            }
+            match &self.origins[clause] {
+                Origin::Fact(fact) => {
+                    facts.insert(*fact);
+                }
+                Origin::Learned(antecedents) => stack.extend(antecedents),
+            }
+            for &lit in &self.clauses[clause] {
+                let var = lit >> 1;
+                if self.values[var].is_some() && self.levels[var] == 0 {
+                    stack.extend(self.reasons[var]);
+                }
+            }
+        }
+        facts
+    }
+}
@@ -0,0 +1,322 @@
+//! SPDX license expressions, as found in the `license` field.
+//!
+//! ```text
+//! license: ["MIT" "LGPL-2.1-or-later WITH OCaml-LGPL-linking-exception" "LicenseRef-Foo"]
+//! ```
+//!
+//! Identifiers are checked against an embedded copy of the SPDX license list. Like SPDX, they are
+//! matched case-insensitively, while operators must be upper case.
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use lazy_static::lazy_static;
+
+/// Version of the embedded SPDX license list.
+pub const LIST_VERSION: &str = "3.27.0";
+
+lazy_static! {
+    static ref LICENSES: HashMap<String, (&'static str, bool)> = load(include_str!("licenses.txt"));
+    static ref EXCEPTIONS: HashMap<String, (&'static str, bool)> =
+        load(include_str!("exceptions.txt"));
+}
+
+// Lines are `<identifier>`, or `<identifier> deprecated`, keyed by lowercase identifier.
+fn load(list: &'static str) -> HashMap<String, (&'static str, bool)> {
+    list.lines()
+        .filter(|line| !line.starts_with('#') && !line.is_empty())
+        .map(|line| {
+            let mut words = line.split(' ');
+            let id = words.next().unwrap_or(line);
+            (
+                id.to_ascii_lowercase(),
+                (id, words.next() == Some("deprecated")),
+            )
+        })
+        .collect()
+}
+
+/// The identifier of `id` as spelt in the SPDX license list, if it's there.
+pub fn license(id: &str) -> Option<&'static str> {
+    LICENSES.get(&id.to_ascii_lowercase()).map(|(id, _)| *id)
+}
+
+pub fn is_deprecated_license(id: &str) -> bool {
+    LICENSES
+        .get(&id.to_ascii_lowercase())
+        .is_some_and(|(_, deprecated)| *deprecated)
+}
+
+/// The identifier of `id` as spelt in the SPDX exception list, if it's there.
+pub fn exception(id: &str) -> Option<&'static str> {
+    EXCEPTIONS.get(&id.to_ascii_lowercase()).map(|(id, _)| *id)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LicenseId {
+    /// An identifier of the SPDX license list, `+` meaning this version or any later one.
+    Spdx { id: String, or_later: bool },
+    /// A license defined outside the list, `[DocumentRef-<doc>:]LicenseRef-<name>`.
+    Ref {
+        document: Option<String>,
+        name: String,
+    },
+}
+
+impl Display for LicenseId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LicenseId::Spdx { id, or_later } => {
+                write!(f, "{}{}", id, if *or_later { "+" } else { "" })
+            }
+            LicenseId::Ref { document, name } => {
+                if let Some(document) = document {
+                    write!(f, "DocumentRef-{}:", document)?;
+                }
+                write!(f, "LicenseRef-{}", name)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LicenseExpr {
+    License {
+        license: LicenseId,
+        exception: Option<String>,
+    },
+    And(Box<LicenseExpr>, Box<LicenseExpr>),
+    Or(Box<LicenseExpr>, Box<LicenseExpr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpdxError {
+    pub expression: String,
+    pub message: String,
+}
+
+impl Display for SpdxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid license expression \"{}\": {}",
+            self.expression, self.message
+        )
+    }
+}
+
+impl std::error::Error for SpdxError {}
+
+impl LicenseExpr {
+    /// Parses an expression, without checking identifiers against the license list.
+    pub fn parse(expression: &str) -> Result<Self, SpdxError> {
+        let spaced = expression.replace('(', " ( ").replace(')', " ) ");
+        let mut parser = Parser {
+            expression,
+            tokens: spaced.split_whitespace().collect(),
+            position: 0,
+        };
+        let expr = parser.or_expr()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) if is_operator(token) => {
+                Err(parser.error(format!("operator `{}` must be upper case", token)))
+            }
+            Some(token) => Err(parser.error(format!("unexpected `{}`", token))),
+        }
+    }
+
+    /// Parses an expression and checks that its licenses and exceptions are in the SPDX lists.
+    pub fn parse_checked(expression: &str) -> Result<Self, SpdxError> {
+        let expr = LicenseExpr::parse(expression)?;
+        let error = |message| SpdxError {
+            expression: expression.to_string(),
+            message,
+        };
+        for (license, exception) in expr.licenses() {
+            if let LicenseId::Spdx { id, .. } = license {
+                if self::license(id).is_none() {
+                    return Err(error(format!("unknown license `{}`", id)));
+                }
+            }
+            if let Some(exception) = exception {
+                if self::exception(exception).is_none() {
+                    return Err(error(format!("unknown license exception `{}`", exception)));
+                }
+            }
+        }
+        Ok(expr)
+    }
+
+    /// The licenses of the expression with their exceptions, left to right.
+    pub fn licenses(&self) -> Vec<(&LicenseId, Option<&str>)> {
+        match self {
+            LicenseExpr::License { license, exception } => vec![(license, exception.as_deref())],
+            LicenseExpr::And(e1, e2) | LicenseExpr::Or(e1, e2) => {
+                let mut licenses = e1.licenses();
+                licenses.extend(e2.licenses());
+                licenses
+            }
+        }
+    }
+
+    /// Identifiers of the expression that are deprecated in the SPDX license list.
+    pub fn deprecated(&self) -> Vec<&str> {
+        self.licenses()
+            .into_iter()
+            .filter_map(|(license, _)| match license {
+                LicenseId::Spdx { id, .. } if is_deprecated_license(id) => Some(id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Prints the expression with as few parentheses as needed.
+impl Display for LicenseExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LicenseExpr::License { license, exception } => {
+                write!(f, "{}", license)?;
+                if let Some(exception) = exception {
+                    write!(f, " WITH {}", exception)?;
+                }
+                Ok(())
+            }
+            LicenseExpr::And(e1, e2) => {
+                for (i, e) in [e1, e2].iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " AND ")?;
+                    }
+                    match e.as_ref() {
+                        LicenseExpr::Or(..) => write!(f, "({})", e)?,
+                        e => write!(f, "{}", e)?,
+                    }
+                }
+                Ok(())
+            }
+            LicenseExpr::Or(e1, e2) => write!(f, "{} OR {}", e1, e2),
+        }
+    }
+}
+
+// Recursive descent over whitespace-separated tokens. `WITH` binds tighter than `AND`, which
+// binds tighter than `OR`.
+struct Parser<'a> {
+    expression: &'a str,
+    tokens: Vec<&'a str>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: String) -> SpdxError {
+        SpdxError {
+            expression: self.expression.to_string(),
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Result<&'a str, SpdxError> {
+        let token = self
+            .peek()
+            .ok_or_else(|| self.error("unexpected end of expression".to_string()))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn or_expr(&mut self) -> Result<LicenseExpr, SpdxError> {
+        let left = self.and_expr()?;
+        if self.peek() == Some("OR") {
+            self.position += 1;
+            let right = self.or_expr()?;
+            return Ok(LicenseExpr::Or(Box::new(left), Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self) -> Result<LicenseExpr, SpdxError> {
+        let left = self.primary()?;
+        if self.peek() == Some("AND") {
+            self.position += 1;
+            let right = self.and_expr()?;
+            return Ok(LicenseExpr::And(Box::new(left), Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn primary(&mut self) -> Result<LicenseExpr, SpdxError> {
+        let token = self.next()?;
+        if token == "(" {
+            let expr = self.or_expr()?;
+            return match self.next()? {
+                ")" => Ok(expr),
+                token => Err(self.error(format!("expected `)`, found `{}`", token))),
+            };
+        }
+        let license = self.license_id(token)?;
+        let exception = if self.peek() == Some("WITH") {
+            self.position += 1;
+            let exception = self.next()?;
+            if !is_idstring(exception) {
+                return Err(self.error(format!("invalid license exception `{}`", exception)));
+            }
+            Some(exception.to_string())
+        } else {
+            None
+        };
+        Ok(LicenseExpr::License { license, exception })
+    }
+
+    fn license_id(&self, token: &str) -> Result<LicenseId, SpdxError> {
+        let invalid = || self.error(format!("invalid license identifier `{}`", token));
+        if token == ")" || is_operator(token) {
+            return Err(self.error(format!("expected a license, found `{}`", token)));
+        }
+        let (document, reference) = match token.strip_prefix("DocumentRef-") {
+            Some(rest) => {
+                let colon = rest.find(':').ok_or_else(invalid)?;
+                (Some(&rest[..colon]), &rest[colon + 1..])
+            }
+            None => (None, token),
+        };
+        if let Some(name) = reference.strip_prefix("LicenseRef-") {
+            if !is_idstring(name) || !document.is_none_or(is_idstring) {
+                return Err(invalid());
+            }
+            return Ok(LicenseId::Ref {
+                document: document.map(String::from),
+                name: name.to_string(),
+            });
+        }
+        if document.is_some() {
+            return Err(invalid());
+        }
+        let (id, or_later) = match token.strip_suffix('+') {
+            Some(id) => (id, true),
+            None => (token, false),
+        };
+        if !is_idstring(id) {
+            return Err(invalid());
+        }
+        Ok(LicenseId::Spdx {
+            id: id.to_string(),
+            or_later,
+        })
+    }
+}
+
+fn is_operator(token: &str) -> bool {
+    ["AND", "OR", "WITH"]
+        .iter()
+        .any(|operator| token.eq_ignore_ascii_case(operator))
+}
+
+fn is_idstring(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+}
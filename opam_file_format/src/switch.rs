@@ -0,0 +1,131 @@
+//! Typed model of switch export files, as written by `opam switch export`.
+//!
+//! ```text
+//! opam-version: "2.0"
+//! compiler: ["ocaml-base-compiler.4.14.0"]
+//! roots: ["foo.dev" "ocaml-base-compiler.4.14.0"]
+//! installed: ["foo.dev" "ocaml.4.14.0" "ocaml-base-compiler.4.14.0"]
+//! pinned: ["foo.dev"]
+//! package "foo" {
+//!   opam-version: "2.0"
+//!   ...
+//! }
+//! ```
+//!
+//! Switch state files (`.opam-switch/switch-state`) use the same fields, without the embedded
+//! package definitions.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+
+use crate::error::FormatError;
+use crate::fields::{string_field, string_value, strings_field, strings_value, Items};
+use crate::package::{split_atom, PackageName};
+use crate::parser::{section_key, Item, OpamAST};
+use crate::printer::OpamPrinter;
+use crate::version::OpamVersion;
+
+#[derive(Debug, Clone, Default)]
+pub struct SwitchExport {
+    pub opam_version: Option<String>,
+    pub compiler: Vec<(PackageName, OpamVersion)>,
+    pub roots: Vec<(PackageName, OpamVersion)>,
+    pub installed: Vec<(PackageName, OpamVersion)>,
+    pub pinned: Vec<(PackageName, OpamVersion)>,
+    /// Definitions of the packages that can't be found in a repository, typically pinned ones.
+    pub packages: IndexMap<PackageName, OpamAST>,
+}
+
+impl SwitchExport {
+    /// Reads an export from an AST built by [`parse_with_spans`](crate::parser::parse_with_spans),
+    /// which keeps every `package` section.
+    pub fn from_ast(ast: &OpamAST) -> Result<Self, FormatError> {
+        let mut export = SwitchExport {
+            opam_version: string_field(&ast.items, "opam-version")?,
+            compiler: atoms(&ast.items, "compiler")?,
+            roots: atoms(&ast.items, "roots")?,
+            installed: atoms(&ast.items, "installed")?,
+            pinned: atoms(&ast.items, "pinned")?,
+            packages: IndexMap::new(),
+        };
+        for (name, items) in ast.sections("package") {
+            let name = name.ok_or_else(|| FormatError::new("package", "missing package name"))?;
+            export.packages.insert(
+                PackageName::new(name),
+                OpamAST {
+                    items: items.clone(),
+                },
+            );
+        }
+        Ok(export)
+    }
+
+    /// The version of `name` installed in the switch.
+    pub fn installed_version(&self, name: &PackageName) -> Option<&OpamVersion> {
+        self.installed
+            .iter()
+            .find(|(installed, _)| installed == name)
+            .map(|(_, version)| version)
+    }
+
+    pub fn is_pinned(&self, name: &PackageName) -> bool {
+        self.pinned.iter().any(|(pinned, _)| pinned == name)
+    }
+
+    /// The embedded definition of `name`, for packages that can't be found in a repository.
+    pub fn package(&self, name: &PackageName) -> Option<&OpamAST> {
+        self.packages.get(name)
+    }
+
+    pub fn to_ast(&self) -> OpamAST {
+        let mut items = IndexMap::new();
+        if let Some(version) = &self.opam_version {
+            items.insert("opam-version".to_string(), string_value(version));
+        }
+        for (field, atoms) in [
+            ("compiler", &self.compiler),
+            ("roots", &self.roots),
+            ("installed", &self.installed),
+            ("pinned", &self.pinned),
+        ]
+        .iter()
+        {
+            if !atoms.is_empty() {
+                let atoms: Vec<String> = atoms
+                    .iter()
+                    .map(|(name, version)| format!("{}.{}", name, version))
+                    .collect();
+                items.insert(field.to_string(), strings_value(&atoms));
+            }
+        }
+        for (name, ast) in &self.packages {
+            items.insert(
+                section_key("package", Some(name.as_str())),
+                Box::new(Item::Section {
+                    name: Some(name.to_string()),
+                    items: ast.items.clone(),
+                }),
+            );
+        }
+        OpamAST { items }
+    }
+}
+
+fn atoms(items: &Items, field: &str) -> Result<Vec<(PackageName, OpamVersion)>, FormatError> {
+    strings_field(items, field)?
+        .iter()
+        .map(|atom| {
+            split_atom(atom).ok_or_else(|| {
+                FormatError::new(field, format!("expected name.version, found {}", atom))
+            })
+        })
+        .collect()
+}
+
+/// Prints the switch export in opam syntax.
+impl Display for SwitchExport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", OpamPrinter::new(&self.to_ast()))
+    }
+}
@@ -0,0 +1,333 @@
+//! Source URLs, as found in `url` and `extra-source` sections and in the `dev-repo` field.
+//!
+//! An address is `[<vc>+]<transport>://<path>[#<fragment>]`, e.g. `git+https://host/repo#branch`.
+//! The version control system may be left out, in which case it is guessed from the transport and
+//! from the suffix of the path, like opam does.
+//! The transport may be left out too, for scp-like ssh addresses such as `git@host:repo.git` and
+//! for local paths, which are printed back as they were written.
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::str::FromStr;
+
+use indexmap::IndexMap;
+
+use crate::checksum::{section_checksums, Checksum};
+use crate::error::FormatError;
+use crate::fields::{
+    required, string, string_field, string_value, strings_field, strings_value, Items,
+};
+use crate::parser::{Item, OpamAST};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceKind {
+    Http,
+    Git,
+    Hg,
+    Darcs,
+    Local,
+    Rsync,
+}
+
+impl SourceKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            SourceKind::Http => "http",
+            SourceKind::Git => "git",
+            SourceKind::Hg => "hg",
+            SourceKind::Darcs => "darcs",
+            SourceKind::Local => "local",
+            SourceKind::Rsync => "rsync",
+        }
+    }
+
+    pub fn is_vcs(self) -> bool {
+        matches!(self, SourceKind::Git | SourceKind::Hg | SourceKind::Darcs)
+    }
+
+    // The version control prefix of `vc+transport://`.
+    fn from_vc(vc: &str) -> Option<Self> {
+        match vc {
+            "git" => Some(SourceKind::Git),
+            "hg" => Some(SourceKind::Hg),
+            "darcs" => Some(SourceKind::Darcs),
+            "http" | "https" | "ftp" => Some(SourceKind::Http),
+            "file" => Some(SourceKind::Local),
+            "rsync" => Some(SourceKind::Rsync),
+            _ => None,
+        }
+    }
+
+    fn from_transport(transport: &str) -> Self {
+        match transport {
+            "git" => SourceKind::Git,
+            "hg" => SourceKind::Hg,
+            "darcs" => SourceKind::Darcs,
+            "http" | "https" | "ftp" => SourceKind::Http,
+            "file" => SourceKind::Local,
+            _ => SourceKind::Rsync,
+        }
+    }
+
+    // Prefix naming this kind in `vc+transport://`.
+    fn vc(self) -> &'static str {
+        match self {
+            SourceKind::Local => "file",
+            kind => kind.name(),
+        }
+    }
+
+    fn from_suffix(path: &str) -> Option<Self> {
+        let path = path.trim_end_matches('/');
+        if path.ends_with(".git") {
+            Some(SourceKind::Git)
+        } else if path.ends_with(".hg") {
+            Some(SourceKind::Hg)
+        } else {
+            None
+        }
+    }
+}
+
+/// A single source address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OpamUrl {
+    pub kind: SourceKind,
+    /// Transport, e.g. `https` or `ssh`, `file` for local paths.
+    pub transport: String,
+    /// Everything after `://`, or the whole address when written without a transport.
+    pub path: String,
+    /// The part after `#`, a branch or revision for version control systems.
+    pub fragment: Option<String>,
+    /// Whether the kind was written as a `vc+` prefix, rather than guessed from the transport and
+    /// the path.
+    pub explicit_kind: bool,
+    /// Whether the address was written `transport://path`, rather than as an scp-like
+    /// `user@host:path` or a local path.
+    pub explicit_transport: bool,
+}
+
+impl OpamUrl {
+    pub fn parse(s: &str) -> Result<Self, FormatError> {
+        let error = |message: &str| FormatError::new("url", format!("{}: {}", message, s));
+        let (address, fragment) = match s.find('#') {
+            Some(i) => (&s[..i], Some(s[i + 1..].to_string())),
+            None => (s, None),
+        };
+        if address.is_empty() {
+            return Err(error("empty address"));
+        }
+        let lowercase_scheme;
+        let explicit_transport = address.contains("://");
+        let (kind, transport, path, explicit_kind) = match address.find("://") {
+            Some(i) => {
+                lowercase_scheme = address[..i].to_ascii_lowercase();
+                let scheme = lowercase_scheme.as_str();
+                let path = &address[i + 3..];
+                match scheme.find('+') {
+                    Some(plus) => {
+                        let kind = SourceKind::from_vc(&scheme[..plus])
+                            .ok_or_else(|| error("unknown version control system"))?;
+                        (kind, &scheme[plus + 1..], path, true)
+                    }
+                    None => (implied_kind(scheme, path), scheme, path, false),
+                }
+            }
+            // `user@host:path`, scp-like ssh addresses.
+            None if looks_like_ssh(address) => {
+                (implied_kind("ssh", address), "ssh", address, false)
+            }
+            None => (implied_kind("file", address), "file", address, false),
+        };
+        if transport.is_empty() {
+            return Err(error("missing transport"));
+        }
+        if path.is_empty() {
+            return Err(error("empty path"));
+        }
+        Ok(OpamUrl {
+            kind,
+            transport: transport.to_string(),
+            path: path.to_string(),
+            fragment,
+            explicit_kind,
+            explicit_transport,
+        })
+    }
+
+    /// Host part of the path, for remote addresses.
+    pub fn host(&self) -> Option<&str> {
+        self.host_range().map(|range| &self.path[range])
+    }
+
+    // Where the host is in the path: after the user info, which ends at the last `@` before the
+    // first `/`, and before the port.
+    fn host_range(&self) -> Option<Range<usize>> {
+        if self.transport == "file" {
+            return None;
+        }
+        let authority = &self.path[..self.path.find('/').unwrap_or(self.path.len())];
+        let start = authority.rfind('@').map_or(0, |at| at + 1);
+        let end = authority[start..]
+            .find(':')
+            .map_or(authority.len(), |colon| start + colon);
+        Some(start..end)
+    }
+
+    /// Lowercases the host and drops trailing slashes, so that equal addresses compare equal.
+    pub fn normalize(&self) -> OpamUrl {
+        let mut path = self.path.trim_end_matches('/').to_string();
+        if let Some(range) = self.host_range() {
+            let host = path[range.clone()].to_ascii_lowercase();
+            path.replace_range(range, &host);
+        }
+        OpamUrl {
+            kind: self.kind,
+            transport: self.transport.clone(),
+            path,
+            fragment: self.fragment.clone(),
+            explicit_kind: self.explicit_kind,
+            explicit_transport: self.explicit_transport,
+        }
+    }
+
+    /// Whether both addresses point to the same source, ignoring the fragment, a `.git` suffix and
+    /// the difference between http and https.
+    pub fn same_source(&self, other: &OpamUrl) -> bool {
+        let key = |url: &OpamUrl| {
+            let url = url.normalize();
+            let transport = if url.transport == "http" {
+                "https".to_string()
+            } else {
+                url.transport
+            };
+            let path = url
+                .path
+                .strip_suffix(".git")
+                .unwrap_or(&url.path)
+                .to_string();
+            (url.kind, transport, path)
+        };
+        key(self) == key(other)
+    }
+}
+
+// The kind of source an address without version control prefix points to.
+fn implied_kind(transport: &str, path: &str) -> SourceKind {
+    SourceKind::from_suffix(path).unwrap_or_else(|| SourceKind::from_transport(transport))
+}
+
+fn looks_like_ssh(address: &str) -> bool {
+    match (address.find(':'), address.find('/')) {
+        (Some(colon), slash) => colon > 0 && slash.is_none_or(|slash| colon < slash),
+        (None, _) => false,
+    }
+}
+
+impl FromStr for OpamUrl {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OpamUrl::parse(s)
+    }
+}
+
+/// Prints the address, with a version control prefix when it was written with one or when the
+/// transport doesn't imply it. Addresses written without a transport, such as `git@host:repo.git`,
+/// are printed as they were, as long as they still imply their kind.
+impl Display for OpamUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let implied = implied_kind(&self.transport, &self.path) == self.kind;
+        if self.explicit_transport || !implied {
+            if self.explicit_kind || !implied {
+                write!(f, "{}+", self.kind.vc())?;
+            }
+            write!(f, "{}://", self.transport)?;
+        }
+        f.write_str(&self.path)?;
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+/// A `url` or `extra-source` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    pub src: OpamUrl,
+    pub mirrors: Vec<OpamUrl>,
+    pub checksum: Vec<Checksum>,
+    /// Software Heritage identifier of the source.
+    pub swhid: Option<String>,
+}
+
+impl Url {
+    pub fn from_items(items: &Items) -> Result<Self, FormatError> {
+        let src = OpamUrl::parse(string(required(items, "src")?, "src")?)?;
+        let mirrors = strings_field(items, "mirrors")?
+            .iter()
+            .map(|mirror| OpamUrl::parse(mirror))
+            .collect::<Result<_, _>>()?;
+        Ok(Url {
+            src,
+            mirrors,
+            checksum: section_checksums(items)?,
+            swhid: string_field(items, "swhid")?,
+        })
+    }
+
+    pub fn to_items(&self) -> Items {
+        let mut items = IndexMap::new();
+        items.insert("src".to_string(), string_value(&self.src.to_string()));
+        if !self.mirrors.is_empty() {
+            let mirrors: Vec<String> = self.mirrors.iter().map(OpamUrl::to_string).collect();
+            items.insert("mirrors".to_string(), strings_value(&mirrors));
+        }
+        let checksums: Vec<String> = self.checksum.iter().map(Checksum::to_string).collect();
+        match checksums.as_slice() {
+            [] => (),
+            [checksum] => {
+                items.insert("checksum".to_string(), string_value(checksum));
+            }
+            checksums => {
+                items.insert("checksum".to_string(), strings_value(checksums));
+            }
+        }
+        if let Some(swhid) = &self.swhid {
+            items.insert("swhid".to_string(), string_value(swhid));
+        }
+        items
+    }
+}
+
+/// The `url` section of a package definition.
+pub fn url(ast: &OpamAST) -> Result<Option<Url>, FormatError> {
+    match ast.items.get("url").map(Box::as_ref) {
+        None => Ok(None),
+        Some(Item::Section { items, .. }) => Url::from_items(items).map(Some),
+        Some(Item::Variable(_)) => {
+            Err(FormatError::new("url", "expected a section, found a field"))
+        }
+    }
+}
+
+/// The `extra-source "name" {...}` sections of a package definition, by file name. See
+/// [`OpamAST::sections`] for the ASTs that keep all of them.
+pub fn extra_sources(ast: &OpamAST) -> Result<Vec<(String, Url)>, FormatError> {
+    ast.sections("extra-source")
+        .map(|(name, items)| {
+            let name = name.ok_or_else(|| FormatError::new("extra-source", "missing file name"))?;
+            Ok((name.to_string(), Url::from_items(items)?))
+        })
+        .collect()
+}
+
+/// The `dev-repo` field of a package definition.
+pub fn dev_repo(ast: &OpamAST) -> Result<Option<OpamUrl>, FormatError> {
+    string_field(&ast.items, "dev-repo")?
+        .map(|dev_repo| {
+            OpamUrl::parse(&dev_repo).map_err(|e| FormatError::new("dev-repo", e.message))
+        })
+        .transpose()
+}
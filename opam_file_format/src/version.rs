@@ -0,0 +1,97 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A package version, ordered the way opam orders them.
+///
+/// Versions are compared as alternating sequences of non-digit and digit chunks. Non-digit
+/// chunks are compared character-wise with `~` sorting before anything, even the end of the
+/// chunk, and letters sorting before other characters. Digit chunks are compared numerically.
+/// So `1.0~beta < 1.0 < 1.0a < 1.0.1 < 1.00.2`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OpamVersion(String);
+
+impl OpamVersion {
+    pub fn new(version: impl Into<String>) -> Self {
+        OpamVersion(version.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for OpamVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for OpamVersion {
+    fn from(version: &str) -> Self {
+        OpamVersion::new(version)
+    }
+}
+
+impl Ord for OpamVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `1.0` and `1.00` are the same version to opam, but must not be equal here so that `Ord`
+        // agrees with the derived `Eq`. Fall back to the raw string to break the tie.
+        compare_versions(&self.0, &other.0).then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl PartialOrd for OpamVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares two version strings with opam's ordering.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+    while !a.is_empty() || !b.is_empty() {
+        let (alpha_a, rest_a) = split_while(a, |c| !c.is_ascii_digit());
+        let (alpha_b, rest_b) = split_while(b, |c| !c.is_ascii_digit());
+        match compare_non_digits(alpha_a, alpha_b) {
+            Ordering::Equal => (),
+            ord => return ord,
+        }
+        let (digits_a, rest_a) = split_while(rest_a, |c| c.is_ascii_digit());
+        let (digits_b, rest_b) = split_while(rest_b, |c| c.is_ascii_digit());
+        match compare_digits(digits_a, digits_b) {
+            Ordering::Equal => (),
+            ord => return ord,
+        }
+        a = rest_a;
+        b = rest_b;
+    }
+    Ordering::Equal
+}
+
+fn split_while(s: &[u8], f: impl Fn(u8) -> bool) -> (&[u8], &[u8]) {
+    let pos = s.iter().position(|c| !f(*c)).unwrap_or(s.len());
+    s.split_at(pos)
+}
+
+fn char_order(c: Option<&u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(b'~') => -1,
+        Some(c) if c.is_ascii_alphabetic() => i32::from(*c),
+        Some(c) => i32::from(*c) + 256,
+    }
+}
+
+fn compare_non_digits(a: &[u8], b: &[u8]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| char_order(a.get(i)).cmp(&char_order(b.get(i))))
+        .find(|ord| *ord != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn compare_digits(a: &[u8], b: &[u8]) -> Ordering {
+    let (_, a) = split_while(a, |c| c == b'0');
+    let (_, b) = split_while(b, |c| c == b'0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
@@ -0,0 +1,109 @@
+use std::fs;
+
+use opam_file_format::checksum::{
+    extra_source_checksums, url_checksums, verify_archive, verify_reader, Checksum, HashAlgorithm,
+    VerifyError,
+};
+use opam_file_format::parse_str;
+use opam_file_format::parser::parse_str_with_spans;
+
+const MD5: &str = "900150983cd24fb0d6963f7d28e17f72";
+const SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+const SHA512: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                      2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+
+#[test]
+fn parsing() {
+    let checksum = Checksum::parse(&format!("sha256={}", SHA256.to_uppercase())).unwrap();
+    assert_eq!(checksum.algorithm, HashAlgorithm::Sha256);
+    assert_eq!(checksum.digest, SHA256);
+    assert_eq!(checksum.to_string(), format!("sha256={}", SHA256));
+
+    for invalid in &[
+        MD5.to_string(),
+        format!("sha1={}", MD5),
+        format!("md5={}", SHA256),
+        format!("md5={}g", &MD5[1..]),
+    ] {
+        assert!(invalid.parse::<Checksum>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn sections() {
+    let source = format!(
+        r#"url {{
+  src: "https://example.com/foo.tar.gz"
+  checksum: ["md5={}" "sha512={}"]
+}}
+extra-source "a.patch" {{
+  src: "https://example.com/a.patch"
+  checksum: "sha256={}"
+}}
+extra-source "b.patch" {{
+  src: "https://example.com/b.patch"
+  checksum: "md5={}"
+}}
+"#,
+        MD5, SHA512, SHA256, MD5
+    );
+    let ast = parse_str(&source).unwrap();
+    let algorithms: Vec<HashAlgorithm> = url_checksums(&ast)
+        .unwrap()
+        .iter()
+        .map(|checksum| checksum.algorithm)
+        .collect();
+    assert_eq!(algorithms, [HashAlgorithm::Md5, HashAlgorithm::Sha512]);
+
+    let (ast, _) = parse_str_with_spans(&source).unwrap();
+    let extra: Vec<(String, usize)> = extra_source_checksums(&ast)
+        .unwrap()
+        .into_iter()
+        .map(|(name, checksums)| (name, checksums.len()))
+        .collect();
+    assert_eq!(
+        extra,
+        [("a.patch".to_string(), 1), ("b.patch".to_string(), 1)]
+    );
+
+    let ast = parse_str("url { src: \"https://a\" checksum: \"md5=00\" }").unwrap();
+    assert!(url_checksums(&ast).is_err());
+    assert_eq!(
+        url_checksums(&parse_str("name: \"a\"").unwrap()).unwrap(),
+        []
+    );
+}
+
+#[test]
+fn verification() {
+    let checksums: Vec<Checksum> = [
+        format!("md5={}", MD5),
+        format!("sha256={}", SHA256),
+        format!("sha512={}", SHA512),
+        format!("sha256={}", MD5.repeat(2)),
+    ]
+    .iter()
+    .map(|checksum| checksum.parse().unwrap())
+    .collect();
+    let verifications = verify_reader(&b"abc"[..], &checksums).unwrap();
+    let ok: Vec<bool> = verifications.iter().map(|v| v.is_ok()).collect();
+    assert_eq!(ok, [true, true, true, false]);
+    assert_eq!(verifications[3].actual, SHA256);
+}
+
+#[test]
+fn archives() {
+    let path = std::env::temp_dir().join(format!("opam-checksum-{}", std::process::id()));
+    fs::write(&path, "abc").unwrap();
+    let with_url =
+        |checksum: &str| parse_str(&format!("url {{ src: \"https://a\" {} }}", checksum)).unwrap();
+    let verified = verify_archive(&with_url(&format!("checksum: \"md5={}\"", MD5)), &path);
+    let missing = verify_archive(&with_url(""), &path);
+    fs::remove_file(&path).unwrap();
+    assert!(verified.unwrap().iter().all(|v| v.is_ok()));
+    assert!(matches!(missing, Err(VerifyError::NoChecksum)));
+    assert!(matches!(
+        verify_archive(&with_url(&format!("checksum: \"md5={}\"", MD5)), &path),
+        Err(VerifyError::Io(_))
+    ));
+}
@@ -0,0 +1,161 @@
+use opam_file_format::diff::{diff, Change, Direction};
+use opam_file_format::parse_str;
+use serde_json::json;
+
+fn changes(old: &str, new: &str) -> Vec<String> {
+    let (old, new) = (parse_str(old).unwrap(), parse_str(new).unwrap());
+    diff(&old, &new).iter().map(Change::to_string).collect()
+}
+
+fn direction(old: &str, new: &str) -> Direction {
+    let depends = |constraint: &str| format!("depends: [\"base\" {{{}}}]", constraint);
+    let (old, new) = (
+        parse_str(&depends(old)).unwrap(),
+        parse_str(&depends(new)).unwrap(),
+    );
+    match diff(&old, &new).as_slice() {
+        [Change::ConstraintChanged { direction, .. }] => *direction,
+        changes => panic!("{:?}", changes),
+    }
+}
+
+#[test]
+fn fields_and_sections() {
+    assert_eq!(
+        changes(
+            r#"name: "foo"
+synopsis: "A package"
+license: "MIT"
+url {
+  src: "https://example.com/foo-1.0.tar.gz"
+  checksum: "md5=900150983cd24fb0d6963f7d28e17f72"
+}
+"#,
+            r#"name: "foo" # comments and layout don't matter
+synopsis:   "A better package"
+url {
+  src: "https://example.com/foo-1.1.tar.gz"
+  checksum: "md5=900150983cd24fb0d6963f7d28e17f72"
+}
+homepage: "https://example.com"
+"#
+        ),
+        [
+            r#"~ synopsis: "A package" -> "A better package""#,
+            r#"- license: "MIT""#,
+            r#"~ url.src: "https://example.com/foo-1.0.tar.gz" -> "https://example.com/foo-1.1.tar.gz""#,
+            r#"+ homepage: "https://example.com""#,
+        ]
+    );
+    assert_eq!(
+        changes("name: \"foo\"\n", "name: \"foo\"\n"),
+        [] as [&str; 0]
+    );
+}
+
+#[test]
+fn dependencies() {
+    assert_eq!(
+        changes(
+            r#"depends: ["ocaml" {>= "4.08"} "dune" "base" {< "1.0"}]"#,
+            r#"depends: ["ocaml" {>= "4.14"} "base" {< "2.0"} "odoc" {with-doc}]"#
+        ),
+        [
+            r#"~ depends: "ocaml" {>= "4.08"} -> "ocaml" {>= "4.14"} (tightened)"#,
+            r#"- depends: "dune""#,
+            r#"~ depends: "base" {< "1.0"} -> "base" {< "2.0"} (loosened)"#,
+            r#"+ depends: "odoc" {with-doc}"#,
+        ]
+    );
+    // Alternatives being introduced aren't about a single dependency.
+    assert_eq!(
+        changes(r#"depends: ["base"]"#, r#"depends: ["base" | "core"]"#),
+        [r#"+ depends: "core""#]
+    );
+}
+
+#[test]
+fn constraint_directions() {
+    assert_eq!(
+        direction(r#">= "1.0""#, r#">= "1.0" & < "2.0""#),
+        Direction::Tightened
+    );
+    assert_eq!(direction(r#"= "1.0""#, r#">= "1.0""#), Direction::Loosened);
+    assert_eq!(
+        direction(r#"> "1.0""#, r#"!(<= "1.0")"#),
+        Direction::Equivalent
+    );
+    assert_eq!(direction(r#"< "1.0""#, r#">= "1.0""#), Direction::Changed);
+    assert_eq!(
+        direction(r#">= "1.0""#, r#">= "1.0" & build"#),
+        Direction::Tightened
+    );
+    assert_eq!(
+        direction(r#"< "2.0""#, r#"< "2.0" | with-test"#),
+        Direction::Loosened
+    );
+    assert_eq!(
+        direction(r#">= "1.0~beta""#, r#">= "1.0""#),
+        Direction::Tightened
+    );
+}
+
+#[test]
+fn commands() {
+    assert_eq!(
+        changes(
+            r#"build: [["dune" "subst"] ["dune" "build"] ["dune" "runtest"] {with-test}]"#,
+            r#"build: [["dune" "build" "-p" name] ["dune" "runtest"] {with-test} ["make" "doc"]]"#
+        ),
+        [
+            r#"~ build[0]: ["dune" "subst"] -> ["dune" "build" "-p" name]"#,
+            r#"- build[1]: ["dune" "build"]"#,
+            r#"+ build[2]: ["make" "doc"]"#,
+        ]
+    );
+    assert_eq!(
+        changes(
+            r#"build: [["dune" "build"] ["dune" "runtest"] {with-test}]"#,
+            r#"build: [["dune" "subst"] ["dune" "build"] ["dune" "runtest"]]"#
+        ),
+        [
+            r#"+ build[0]: ["dune" "subst"]"#,
+            r#"~ build[2]: ["dune" "runtest"] {with-test} -> ["dune" "runtest"]"#,
+        ]
+    );
+    // A single command may be written without the outer brackets.
+    assert_eq!(
+        changes(
+            r#"build: ["make"]"#,
+            r#"build: [["make"] ["make" "install"]]"#
+        ),
+        [r#"+ build[1]: ["make" "install"]"#]
+    );
+}
+
+#[test]
+fn json() {
+    let old = parse_str(r#"depends: ["ocaml" {>= "4.08"}] build: [["make"]]"#).unwrap();
+    let new = parse_str(r#"depends: ["ocaml" {>= "4.02"}] build: [["make" "all"]]"#).unwrap();
+    let json: Vec<_> = diff(&old, &new).iter().map(Change::to_json).collect();
+    assert_eq!(
+        json,
+        [
+            json!({
+                "change": "constraint-changed",
+                "field": "depends",
+                "name": "ocaml",
+                "old": r#""ocaml" {>= "4.08"}"#,
+                "new": r#""ocaml" {>= "4.02"}"#,
+                "direction": "loosened",
+            }),
+            json!({
+                "change": "command-modified",
+                "field": "build",
+                "index": 0,
+                "old": r#"["make"]"#,
+                "new": r#"["make" "all"]"#,
+            }),
+        ]
+    );
+}
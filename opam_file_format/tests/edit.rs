@@ -0,0 +1,144 @@
+use opam_file_format::checksum::Checksum;
+use opam_file_format::edit::{Document, EditError};
+use opam_file_format::parse_str;
+use opam_file_format::parser::{Item, OpamAST, Value};
+
+const SOURCE: &str = r#"opam-version: "2.0"
+name: "foo" # the package
+depends: [
+  "ocaml" {>= "4.08"}
+  # the build system
+  "dune"
+]
+build: [
+  ["dune" "build"]
+  ["dune" "runtest"] {with-test}
+]
+url {
+  src: "https://example.com/foo.tar.gz"
+}
+"#;
+
+fn value(source: &str) -> Value {
+    match parse_str(&format!("value: {}", source)).unwrap().items["value"].as_ref() {
+        Item::Variable(value) => value.clone(),
+        item => panic!("{:?}", item),
+    }
+}
+
+fn field(ast: &OpamAST, field: &str) -> String {
+    match ast.items[field].as_ref() {
+        Item::Variable(value) => value.to_string(),
+        item => panic!("{:?}", item),
+    }
+}
+
+#[test]
+fn documents_keep_the_rest_of_the_source() {
+    let mut document = Document::parse(SOURCE).unwrap();
+    document
+        .add_dependency("depends", "odoc", Some(&value(r#"with-doc"#)))
+        .unwrap();
+    document
+        .replace_dependency("depends", "ocaml", Some(&value(r#">= "4.14""#)))
+        .unwrap();
+    document.remove_dependency("depends", "dune").unwrap();
+    document
+        .set_field("synopsis", &value(r#""A package""#))
+        .unwrap();
+    document
+        .add_command_filter("build", 0, &value("os = \"linux\" | os = \"macos\""))
+        .unwrap();
+    document
+        .add_command_filter("build", 1, &value("arch = \"x86_64\""))
+        .unwrap();
+    let md5 = "md5=900150983cd24fb0d6963f7d28e17f72";
+    document.set_checksum(&md5.parse().unwrap()).unwrap();
+    assert_eq!(
+        document.source(),
+        format!(
+            r#"opam-version: "2.0"
+name: "foo" # the package
+synopsis: "A package"
+depends: [
+  "ocaml" {{>= "4.14"}}
+  # the build system
+  "odoc" {{with-doc}}
+]
+build: [
+  ["dune" "build"] {{os = "linux" | os = "macos"}}
+  ["dune" "runtest"] {{with-test & arch = "x86_64"}}
+]
+url {{
+  src: "https://example.com/foo.tar.gz"
+  checksum: "{}"
+}}
+"#,
+            md5
+        )
+    );
+}
+
+#[test]
+fn documents_and_asts_agree() {
+    let mut document = Document::parse(SOURCE).unwrap();
+    let mut ast = document.ast().clone();
+    let filter = value(r#"os = "linux" | os = "macos""#);
+    document.add_command_filter("build", 1, &filter).unwrap();
+    ast.add_command_filter("build", 1, filter).unwrap();
+    document.remove_field("url").unwrap();
+    ast.remove_field("url");
+    document.add_dependency("depends", "base", None).unwrap();
+    ast.add_dependency("depends", "base", None).unwrap();
+    for field_name in &["build", "depends"] {
+        assert_eq!(field(document.ast(), field_name), field(&ast, field_name));
+    }
+    assert_eq!(
+        field(&ast, "build"),
+        r#"[["dune" "build"] ["dune" "runtest"] {with-test & (os = "linux" | os = "macos")}]"#
+    );
+    assert!(!document.source().contains("url"));
+
+    let sha256 = "sha256=ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    let checksum: Checksum = sha256.parse().unwrap();
+    let mut ast = parse_str(SOURCE).unwrap();
+    ast.set_checksum(&checksum).unwrap();
+    ast.set_checksum(&checksum).unwrap();
+    match ast.items["url"].as_ref() {
+        Item::Section { items, .. } => {
+            let keys: Vec<&str> = items.keys().map(String::as_str).collect();
+            assert_eq!(keys, ["src", "checksum"]);
+        }
+        item => panic!("{:?}", item),
+    }
+}
+
+#[test]
+fn errors() {
+    let mut document = Document::parse(SOURCE).unwrap();
+    assert!(matches!(
+        document.add_dependency("depends", "dune", None),
+        Err(EditError::Exists(_))
+    ));
+    assert!(matches!(
+        document.remove_dependency("depends", "base"),
+        Err(EditError::NotFound(_))
+    ));
+    assert!(matches!(
+        document.remove_field("license"),
+        Err(EditError::NotFound(_))
+    ));
+    assert!(matches!(
+        document.add_command_filter("build", 2, &value("true")),
+        Err(EditError::NotFound(_))
+    ));
+    assert!(matches!(
+        document.remove_dependency("url", "foo"),
+        Err(EditError::Format(_))
+    ));
+    assert!(matches!(
+        document.set_field("name", &Value::Ident(String::new())),
+        Err(EditError::Syntax(_))
+    ));
+    assert_eq!(document.source(), SOURCE);
+}
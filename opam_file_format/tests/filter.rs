@@ -0,0 +1,90 @@
+use opam_file_format::filter::{Env, FilterValue};
+use opam_file_format::formula::{Formula, VersionConstraint};
+use opam_file_format::parse_str;
+use opam_file_format::parser::{Item, Value};
+use opam_file_format::version::OpamVersion;
+
+fn field(source: &str) -> Value {
+    let ast = parse_str(source).unwrap();
+    match ast.items.values().next().unwrap().as_ref() {
+        Item::Variable(value) => value.clone(),
+        item => panic!("{:?}", item),
+    }
+}
+
+fn filter(filter: &str) -> Value {
+    field(&format!("available: {}", filter))
+}
+
+#[test]
+fn prefix_operators_bind_tighter_than_and() {
+    let env = Env::new().set("os", "linux").set("arch", "arm32");
+    let available = filter(r#"!(os = "win32") & arch != "arm32""#);
+    assert!(!env.eval_bool(&available));
+    assert!(env.clone().set("arch", "x86_64").eval_bool(&available));
+
+    assert!(env.eval_bool(&filter("!false & true")));
+    assert!(!env.eval_bool(&filter("!true & true")));
+    assert_eq!(
+        env.eval(&filter("?undefined & true")),
+        Some(FilterValue::Bool(false))
+    );
+}
+
+#[test]
+fn and_binds_tighter_than_or() {
+    let env = Env::new();
+    assert!(env.eval_bool(&filter("false & false | true")));
+    assert!(env.eval_bool(&filter("true | false & false")));
+    assert!(!env.eval_bool(&filter("false & (false | true)")));
+    assert!(env.eval_bool(&filter("false | true & !false | false")));
+}
+
+#[test]
+fn precedence_nesting_is_stable() {
+    let value = filter("!foo & bar | baz & !(qux | quux) | ?corge");
+    let nested = value.with_precedence();
+    assert_ne!(nested, value);
+    assert_eq!(nested.with_precedence(), nested);
+    assert_eq!(
+        filter("(foo | bar) & baz").with_precedence(),
+        filter("(foo | bar) & baz")
+    );
+}
+
+#[test]
+fn formulas_and_constraints() {
+    let version = |version: &str| OpamVersion::new(version.to_string());
+    let lookup = |variable: &str| match variable {
+        "build" => Some(FilterValue::Bool(true)),
+        "with-test" => Some(FilterValue::Bool(false)),
+        _ => None,
+    };
+
+    // `false & true | true` holds: the dependency is kept, without constraint.
+    let depends = field(r#"depends: ["a" {with-test & build | build}]"#);
+    let formula = Formula::from_value(&depends, &lookup);
+    assert!(matches!(
+        formula,
+        Formula::Atom(dependency) if dependency.constraint == VersionConstraint::Any
+    ));
+
+    // `!(>= "2")` applies to the first bound only.
+    let depends = field(r#"depends: ["a" {!(>= "2") & >= "1" | = "3"}]"#);
+    let constraint = match Formula::from_value(&depends, &lookup) {
+        Formula::Atom(dependency) => dependency.constraint,
+        formula => panic!("{:?}", formula),
+    };
+    assert!(constraint.matches(&version("1.5")));
+    assert!(!constraint.matches(&version("0.5")));
+    assert!(!constraint.matches(&version("2.5")));
+    assert!(constraint.matches(&version("3")));
+    assert_ne!(constraint, VersionConstraint::Any);
+
+    // `"b" & "c" | "d"` is an alternative between `"b" & "c"` and `"d"`.
+    let depends = field(r#"depends: ["b" & "c" | "d"]"#);
+    assert!(matches!(
+        Formula::from_value(&depends, &lookup),
+        Formula::Or(alternatives) if alternatives.len() == 2
+    ));
+}
@@ -0,0 +1,45 @@
+use opam_file_format::formatter::{format_str, is_formatted};
+use opam_file_format::Error;
+
+#[test]
+fn fields_and_dependencies() {
+    let source = r#"depends: ["dune" {>= "3.0"} "ocaml" {>= "4.08"}] # compilers
+opam-version: "2.0"
+name: "foo"
+"#;
+    let formatted = r#"opam-version: "2.0"
+name: "foo"
+depends: [
+  "dune"  {>= "3.0"}
+  "ocaml" {>= "4.08"}
+] # compilers
+"#;
+    assert_eq!(format_str(source).unwrap(), formatted);
+    assert!(is_formatted(formatted).unwrap());
+}
+
+#[test]
+fn repeated_items_are_refused() {
+    // The name of the repeated item, found where it's repeated.
+    let repeated = |source: &str, name: &str| match format_str(source) {
+        Err(Error::Parse(Some(span), message)) => {
+            assert_eq!(message, "repeated field or section");
+            assert_eq!(span.start, source.rfind(name).unwrap());
+            assert_eq!(&source[span.start..span.end], name);
+        }
+        result => panic!("{:?}", result),
+    };
+    repeated("name: \"a\"\nversion: \"1\"\nname: \"b\"\n", "name");
+    repeated("url {\n  src: \"a\"\n  src: \"b\"\n}\n", "src");
+    repeated("url {\n  src: \"a\"\n}\nurl {\n  src: \"b\"\n}\n", "url");
+
+    // Named sections of the same kind are different items.
+    let source = r#"extra-source "a.patch" {
+  src: "https://example.com/a.patch"
+}
+extra-source "b.patch" {
+  src: "https://example.com/b.patch"
+}
+"#;
+    assert_eq!(format_str(source).unwrap(), source);
+}
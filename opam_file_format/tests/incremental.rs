@@ -0,0 +1,139 @@
+use opam_file_format::incremental::{NodeId, TextEdit, Tree};
+use opam_file_format::lexer::Span;
+use opam_file_format::parse_str;
+use opam_file_format::parser::{parse_str_with_spans, ItemSpan};
+
+const SOURCE: &str = r#"opam-version: "2.0"
+name: "foo"
+depends: [
+  "ocaml" {>= "4.08"}
+  "dune"
+]
+build: [["dune" "build"]]
+url {
+  src: "https://example.com/foo.tar.gz"
+}
+"#;
+
+// Replaces the first occurrence of `old` in the text of the tree.
+fn replace(tree: Tree, old: &str, new: &str) -> Tree {
+    let start = tree.text().find(old).unwrap();
+    tree.edit(&TextEdit {
+        range: Span {
+            start,
+            end: start + old.len(),
+        },
+        text: new.to_string(),
+    })
+}
+
+fn assert_same_spans(incremental: &ItemSpan, full: &ItemSpan) {
+    assert_eq!(incremental.span, full.span);
+    assert_eq!(incremental.key, full.key);
+    assert_eq!(incremental.value, full.value);
+    assert_eq!(incremental.items.len(), full.items.len());
+    for ((key, incremental), (full_key, full)) in incremental.items.iter().zip(&full.items) {
+        assert_eq!(key, full_key);
+        assert_same_spans(incremental, full);
+    }
+}
+
+// The tree gives what parsing its whole text again gives.
+fn assert_reparsed(tree: &Tree) {
+    match (tree.ast(), parse_str_with_spans(tree.text())) {
+        (Ok((ast, spans)), Ok((full_ast, full_spans))) => {
+            assert_eq!(ast.items, full_ast.items, "{}", tree.text());
+            assert_eq!(spans.items.len(), full_spans.items.len());
+            for ((key, spans), (full_key, full_spans)) in spans.items.iter().zip(&full_spans.items)
+            {
+                assert_eq!(key, full_key);
+                assert_same_spans(spans, full_spans);
+            }
+        }
+        (Err(error), Err(full_error)) => assert_eq!(error.to_string(), full_error.to_string()),
+        (incremental, full) => panic!("{:?}\n{:?}", incremental.map(|_| ()), full.map(|_| ())),
+    }
+}
+
+fn ids(tree: &Tree) -> Vec<(String, NodeId)> {
+    tree.nodes()
+        .iter()
+        .map(|node| (node.key.clone(), node.id))
+        .collect()
+}
+
+fn node_id(tree: &Tree, key: &str) -> NodeId {
+    tree.nodes().iter().find(|node| node.key == key).unwrap().id
+}
+
+#[test]
+fn edits_match_a_full_reparse() {
+    let mut tree = Tree::parse(SOURCE);
+    assert_reparsed(&tree);
+    let before = ids(&tree);
+    let edits = [
+        (r#""4.08""#, r#""4.14""#),
+        (
+            "\"dune\"\n",
+            "\"dune\" {>= \"3.0\"}\n  \"odoc\" {with-doc}\n",
+        ),
+        ("name: \"foo\"\n", "name: \"foo\"\nversion: \"1.0\"\n"),
+        ("foo.tar.gz", "foo-1.0.tar.gz"),
+        ("build: [[\"dune\" \"build\"]]\n", ""),
+        ("\"2.0\"", "\"2.0\" synopsis: \"A package\""),
+        // A change that continues the value of the field before it.
+        ("]\nurl", "] {with-test}\nurl"),
+    ];
+    for (old, new) in &edits {
+        tree = replace(tree, old, new);
+        assert!(tree.error().is_none(), "{:?}", tree.error());
+        assert_reparsed(&tree);
+    }
+    assert_eq!(node_id(&tree, "opam-version"), before[0].1);
+    assert_eq!(node_id(&tree, "name"), before[1].1);
+    assert!(!before
+        .iter()
+        .any(|(_, id)| *id == node_id(&tree, "version")));
+}
+
+#[test]
+fn untouched_items_keep_their_identity() {
+    let tree = Tree::parse(SOURCE);
+    let before = ids(&tree);
+    let tree = replace(tree, "foo.tar.gz", "bar.tar.gz");
+    assert_reparsed(&tree);
+    let after = ids(&tree);
+    assert_eq!(before.len(), after.len());
+    // `build` is parsed again along with `url` but comes out the same, so only `url` changes.
+    assert_eq!(before[..4], after[..4]);
+    assert_ne!(before[4], after[4]);
+}
+
+#[test]
+fn damaged_documents_recover() {
+    let tree = Tree::parse(SOURCE);
+    let before = ids(&tree);
+    let tree = replace(tree, "build: [", "build: [[");
+    assert!(tree.error().is_some());
+    assert_eq!(
+        tree.error().unwrap().to_string(),
+        parse_str(tree.text()).unwrap_err().to_string()
+    );
+    assert_reparsed(&tree);
+    let keys: Vec<&str> = tree.nodes().iter().map(|node| node.key.as_str()).collect();
+    assert!(keys.contains(&"opam-version"));
+
+    // Edits elsewhere while the document is damaged, then the fix.
+    let tree = replace(tree, "\"foo\"", "\"bar\"");
+    assert!(tree.error().is_some());
+    let tree = replace(tree, "build: [[", "build: [");
+    assert!(tree.error().is_none(), "{:?}", tree.error());
+    assert_reparsed(&tree);
+    let after = ids(&tree);
+    assert_eq!(before.len(), after.len());
+    for (key, id) in before {
+        if key != "name" {
+            assert_eq!(node_id(&tree, &key), id, "{}", key);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::fs;
+
+use opam_file_format::legacy::{upgrade, upgrade_dir, Descr, LegacyUrl};
+use opam_file_format::parser::{Item, OpamAST};
+use opam_file_format::{parse_str, OpamPrinter};
+
+const OPAM: &str = r#"opam-version: "1.2"
+name: "foo"
+version: "1.0"
+maintainer: "me@example.com"
+build: [make]
+build-test: [make "test"]
+build-doc: [[make "doc"] [make "install-doc"]]
+depends: [
+  "base"
+  "ounit" {test}
+  "odoc" {doc & >= "1.0"}
+]
+ocaml-version: [>= "4.02"]
+available: [ocaml-version < "5.0" & os != "win32"]
+"#;
+
+fn field(ast: &OpamAST, field: &str) -> String {
+    match ast.items[field].as_ref() {
+        Item::Variable(value) => value.to_string(),
+        item => panic!("{:?}", item),
+    }
+}
+
+#[test]
+fn fields_are_upgraded() {
+    let ast = upgrade(&parse_str(OPAM).unwrap(), None, None).unwrap();
+    assert_eq!(field(&ast, "opam-version"), r#""2.0""#);
+    assert_eq!(
+        field(&ast, "depends"),
+        r#"["base" "ounit" {with-test} "odoc" {with-doc & >= "1.0"} "ocaml" {>= "4.02" & < "5.0"}]"#
+    );
+    assert_eq!(field(&ast, "available"), r#"os != "win32""#);
+    assert_eq!(
+        field(&ast, "build"),
+        r#"[[make] [make "test"] {with-test} [make "doc"] {with-doc} [make "install-doc"] {with-doc}]"#
+    );
+    assert!(!ast.items.contains_key("ocaml-version"));
+    assert!(!ast.items.contains_key("build-test"));
+    assert_eq!(field(&ast, "maintainer"), r#""me@example.com""#);
+}
+
+#[test]
+fn other_uses_of_ocaml_version() {
+    let opam = parse_str(
+        r#"opam-version: "1.2"
+available: [ocaml-version >= "4.02" & os = "linux" | ocaml-version >= "4.08"]
+"#,
+    )
+    .unwrap();
+    let ast = upgrade(&opam, None, None).unwrap();
+    // The alternative keeps `available` from being split into constraints.
+    assert!(!ast.items.contains_key("depends"));
+    assert_eq!(
+        field(&ast, "available"),
+        r#"(ocaml:version >= "4.02" & os = "linux") | ocaml:version >= "4.08""#
+    );
+}
+
+#[test]
+fn descr_and_url_files() {
+    let descr = Descr::parse("A package\n\nThat does things.\n");
+    assert_eq!(descr.synopsis, "A package");
+    assert_eq!(descr.description.as_deref(), Some("That does things."));
+
+    let url = LegacyUrl::from_ast(
+        &parse_str(
+            r#"git: "https://example.com/foo.git#v1.0"
+checksum: "0123456789abcdef0123456789abcdef"
+"#,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(url.src(), "git+https://example.com/foo.git#v1.0");
+    assert_eq!(url.checksums(), ["md5=0123456789abcdef0123456789abcdef"]);
+
+    let ast = upgrade(&parse_str(OPAM).unwrap(), Some(&descr), Some(&url)).unwrap();
+    assert_eq!(field(&ast, "synopsis"), r#""A package""#);
+    assert_eq!(field(&ast, "description"), r#""That does things.""#);
+    match ast.items["url"].as_ref() {
+        Item::Section { items, .. } => {
+            assert_eq!(items.len(), 2);
+            assert!(matches!(items["src"].as_ref(), Item::Variable(_)));
+        }
+        item => panic!("{:?}", item),
+    }
+
+    let both = parse_str("http: \"https://a\"\nlocal: \"/b\"\n").unwrap();
+    assert!(LegacyUrl::from_ast(&both).is_err());
+}
+
+#[test]
+fn package_directories() {
+    let dir = std::env::temp_dir().join(format!("opam-legacy-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("opam"), OPAM).unwrap();
+    fs::write(dir.join("descr"), "A package\n").unwrap();
+    fs::write(
+        dir.join("url"),
+        "archive: \"https://example.com/foo-1.0.tar.gz\"\n",
+    )
+    .unwrap();
+    let ast = upgrade_dir(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+    let ast = ast.unwrap();
+    assert_eq!(field(&ast, "synopsis"), r#""A package""#);
+    assert!(!ast.items.contains_key("description"));
+    let url = OpamPrinter::new(&ast).to_string();
+    assert!(url.contains(r#"src: "https://example.com/foo-1.0.tar.gz""#));
+
+    assert!(upgrade_dir(&dir).is_err());
+}
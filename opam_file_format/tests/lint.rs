@@ -0,0 +1,42 @@
+use opam_file_format::lint::{lint_str, Level, Lint};
+
+const HEADER: &str = r#"opam-version: "2.0"
+synopsis: "A package"
+maintainer: "me@example.com"
+authors: "Me"
+homepage: "https://example.com"
+bug-reports: "https://example.com/issues"
+dev-repo: "git+https://example.com/repo.git"
+"#;
+
+fn lints(fields: &str) -> Vec<Lint> {
+    lint_str(&format!("{}{}", HEADER, fields)).unwrap()
+}
+
+#[test]
+fn complete_definitions_pass() {
+    assert_eq!(lints(""), vec![]);
+}
+
+#[test]
+fn dependency_filters() {
+    let source = r#"depends: [
+  "ocaml" {>= "4.08"}
+  "dune" {build & ocaml:version >= "4.14"}
+  "ounit" {with-test & os != "win32"}
+]
+"#;
+    assert_eq!(lints(source), vec![]);
+
+    let source = r#"depends: ["ounit" {test} "odoc" {dev-setup}]"#;
+    let lints = lints(source);
+    let codes: Vec<(u32, Level)> = lints.iter().map(|lint| (lint.code, lint.level)).collect();
+    assert_eq!(codes, [(29, Level::Error), (29, Level::Error)]);
+    assert!(lints[0].message.ends_with("use 'with-test' instead"));
+    let at = |lint: &Lint| {
+        let span = lint.span.unwrap();
+        &source[span.start - HEADER.len()..span.end - HEADER.len()]
+    };
+    assert_eq!(at(&lints[0]), "test");
+    assert_eq!(at(&lints[1]), "dev-setup");
+}
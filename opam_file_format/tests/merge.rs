@@ -0,0 +1,131 @@
+use opam_file_format::merge::{merge, Conflict, Merged};
+use opam_file_format::parse_str;
+use opam_file_format::parser::{Item, OpamAST};
+use opam_file_format::OpamPrinter;
+
+const BASE: &str = r#"opam-version: "2.0"
+name: "foo"
+version: "1.0"
+synopsis: "A package"
+depends: [
+  "ocaml" {>= "4.08"}
+  "dune"
+  "base"
+]
+url {
+  src: "https://example.com/foo-1.0.tar.gz"
+}
+"#;
+
+fn three_way(ours: &str, theirs: &str) -> Merged {
+    let parse = |source: &str| parse_str(source).unwrap();
+    merge(&parse(BASE), &parse(ours), &parse(theirs))
+}
+
+fn field(ast: &OpamAST, field: &str) -> String {
+    match ast.items[field].as_ref() {
+        Item::Variable(value) => value.to_string(),
+        item => panic!("{:?}", item),
+    }
+}
+
+#[test]
+fn changes_on_either_side_are_taken() {
+    let ours = BASE
+        .replace(
+            r#"synopsis: "A package""#,
+            r#"synopsis: "A better package""#,
+        )
+        .replace("  \"base\"\n", "  \"base\"\n  \"odoc\" {with-doc}\n");
+    let theirs = BASE
+        .replace(r#""1.0""#, r#""1.1""#)
+        .replace("foo-1.0", "foo-1.1")
+        .replace("  \"dune\"\n", "  \"dune\" {>= \"3.0\"}\n")
+        .replace(
+            "url {",
+            "license: \"MIT\"\nurl {\n  checksum: \"md5=900150983cd24fb0d6963f7d28e17f72\"",
+        );
+    let merged = three_way(&ours, &theirs);
+    assert!(merged.is_clean(), "{:?}", merged.conflicts);
+    let ast = &merged.ast;
+    assert_eq!(field(ast, "version"), r#""1.1""#);
+    assert_eq!(field(ast, "synopsis"), r#""A better package""#);
+    assert_eq!(
+        field(ast, "depends"),
+        r#"["ocaml" {>= "4.08"} "dune" {>= "3.0"} "base" "odoc" {with-doc}]"#
+    );
+    let keys: Vec<&str> = ast.items.keys().map(String::as_str).collect();
+    assert_eq!(
+        keys,
+        [
+            "opam-version",
+            "name",
+            "version",
+            "synopsis",
+            "depends",
+            "license",
+            "url"
+        ]
+    );
+    match ast.items["url"].as_ref() {
+        Item::Section { items, .. } => {
+            let keys: Vec<&str> = items.keys().map(String::as_str).collect();
+            assert_eq!(keys, ["checksum", "src"]);
+            assert!(OpamPrinter::new(ast).to_string().contains("foo-1.1.tar.gz"));
+        }
+        item => panic!("{:?}", item),
+    }
+}
+
+#[test]
+fn same_changes_and_removals() {
+    let both = BASE.replace("  \"base\"\n", "");
+    let theirs = both.replace("synopsis: \"A package\"\n", "");
+    let merged = three_way(&both, &theirs);
+    assert!(merged.is_clean(), "{:?}", merged.conflicts);
+    assert!(!merged.ast.items.contains_key("synopsis"));
+    assert_eq!(
+        field(&merged.ast, "depends"),
+        r#"["ocaml" {>= "4.08"} "dune"]"#
+    );
+}
+
+#[test]
+fn conflicts_keep_our_side() {
+    let ours = BASE
+        .replace(r#""1.0""#, r#""1.1""#)
+        .replace(r#"{>= "4.08"}"#, r#"{>= "4.14"}"#)
+        .replace("  \"dune\"\n", "");
+    let theirs = BASE
+        .replace(r#""1.0""#, r#""2.0""#)
+        .replace(r#"{>= "4.08"}"#, r#"{>= "4.10"}"#)
+        .replace("  \"dune\"\n", "  \"dune\" {>= \"3.0\"}\n")
+        .replace("foo-1.0", "foo-2.0");
+    let merged = three_way(&ours, &theirs);
+    let conflicts: Vec<String> = merged.conflicts.iter().map(Conflict::to_string).collect();
+    assert_eq!(
+        conflicts,
+        [
+            r#"conflict in `version`: base "1.0", ours "1.1", theirs "2.0""#,
+            r#"conflict in `depends` on "ocaml": base "ocaml" {>= "4.08"}, ours "ocaml" {>= "4.14"}, theirs "ocaml" {>= "4.10"}"#,
+            r#"conflict in `depends` on "dune": base "dune", ours absent, theirs "dune" {>= "3.0"}"#,
+        ]
+    );
+    assert!(matches!(
+        &merged.conflicts[0],
+        Conflict::Field { field, .. } if field == "version"
+    ));
+    let ast = &merged.ast;
+    assert_eq!(field(ast, "version"), r#""1.1""#);
+    assert_eq!(field(ast, "depends"), r#"["ocaml" {>= "4.14"} "base"]"#);
+    // Their change to the url, which we didn't touch, is still taken.
+    assert!(OpamPrinter::new(ast).to_string().contains("foo-2.0.tar.gz"));
+
+    let ours = BASE.replace("https://example.com", "https://ours.example.com");
+    let theirs = BASE.replace("https://example.com", "https://theirs.example.com");
+    let merged = three_way(&ours, &theirs);
+    assert!(matches!(
+        merged.conflicts.as_slice(),
+        [Conflict::Field { field, .. }] if field == "url.src"
+    ));
+}
@@ -0,0 +1,64 @@
+use opam_file_format::parse_str;
+use opam_file_format::query::query;
+
+const SOURCE: &str = r#"opam-version: "2.0"
+license: "MIT"
+depends: [
+  "ocaml" {>= "4.08"}
+  "dune" | "jbuilder"
+  ("lwt" | "async") {with-test}
+]
+conflicts: !"base"
+extra-source "a.patch" {
+  src: "https://example.com/a.patch"
+  checksum: "md5=00000000000000000000000000000000"
+}
+"#;
+
+fn run(q: &str) -> Vec<String> {
+    let ast = parse_str(SOURCE).unwrap();
+    query(&ast, q)
+        .unwrap()
+        .iter()
+        .map(|m| m.to_string())
+        .collect()
+}
+
+#[test]
+fn fields_and_sections() {
+    assert_eq!(run("license"), [r#""MIT""#]);
+    assert_eq!(
+        run(r#"extra-source["a.patch"].checksum"#),
+        [r#""md5=00000000000000000000000000000000""#]
+    );
+    assert_eq!(run(r#"extra-source["b.patch"]"#), Vec::<String>::new());
+    assert_eq!(run("depends[0].filter"), [r#">= "4.08""#]);
+}
+
+#[test]
+fn formulas_split_into_atoms() {
+    assert_eq!(run("depends[*].name").len(), 4);
+    assert_eq!(run(r#"depends["jbuilder"]"#), [r#""jbuilder""#]);
+
+    // Groups with options and negations stay whole.
+    assert_eq!(run(r#"depends["lwt"]"#), Vec::<String>::new());
+    assert_eq!(run("depends[2].filter"), ["with-test"]);
+    assert_eq!(run(r#"conflicts["base"]"#), Vec::<String>::new());
+    assert_eq!(run("conflicts[*]").len(), 1);
+}
+
+#[test]
+fn values_without_brackets_are_lists_of_one() {
+    assert_eq!(run("license[0]"), [r#""MIT""#]);
+    assert_eq!(run("license[1]"), Vec::<String>::new());
+    assert_eq!(run(r#"license["MIT"]"#), [r#""MIT""#]);
+}
+
+#[test]
+fn invalid_queries() {
+    let ast = parse_str(SOURCE).unwrap();
+    let error = query(&ast, "depends[").unwrap_err();
+    assert_eq!(error.position, 8);
+    assert!(query(&ast, "depends.").is_err());
+    assert!(query(&ast, r#"depends["dune"#).is_err());
+}
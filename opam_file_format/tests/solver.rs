@@ -0,0 +1,158 @@
+use opam_file_format::filter::Env;
+use opam_file_format::formula::{Dependency, VersionConstraint};
+use opam_file_format::parse_str;
+use opam_file_format::solver::{Fact, Solution, Solver};
+use opam_file_format::version::OpamVersion;
+use opam_file_format::{OpamAST, PackageName};
+
+// Packages written `name.version` followed by the fields of their opam file.
+fn read(packages: &[(&str, &str)]) -> Solver {
+    let packages: Vec<(PackageName, OpamVersion, OpamAST)> = packages
+        .iter()
+        .map(|(atom, fields)| {
+            let (name, version) = atom.split_once('.').unwrap();
+            let ast = parse_str(&format!("opam-version: \"2.0\"\n{}\n", fields)).unwrap();
+            (PackageName::new(name), OpamVersion::new(version), ast)
+        })
+        .collect();
+    Solver::new(&packages, &Env::install()).unwrap()
+}
+
+fn request(name: &str) -> Vec<Dependency> {
+    vec![Dependency {
+        name: PackageName::new(name),
+        constraint: VersionConstraint::Any,
+    }]
+}
+
+fn atoms(solution: &Solution) -> Vec<String> {
+    solution.packages.iter().map(|node| node.atom()).collect()
+}
+
+#[test]
+fn newest_versions_are_installed() {
+    let solver = read(&[
+        ("app.1", r#"depends: ["lib" {< "3"} "tool" {build}]"#),
+        ("lib.1", ""),
+        ("lib.2", ""),
+        ("lib.3", ""),
+        ("tool.1", ""),
+        ("tool.2", r#"available: false"#),
+        ("unrelated.1", ""),
+    ]);
+    let solution = solver.solve(&request("app")).unwrap();
+    assert_eq!(atoms(&solution), ["app.1", "lib.2", "tool.1"]);
+    assert_eq!(
+        solution.version(&PackageName::new("lib")),
+        Some(&OpamVersion::new("2"))
+    );
+}
+
+#[test]
+fn conjunctions_within_alternatives() {
+    let solver = read(&[
+        (
+            "app.1",
+            r#"depends: [("lib" & "missing") | ("lib" & "tool") | "other"]"#,
+        ),
+        ("lib.1", ""),
+        ("tool.1", ""),
+        ("other.1", ""),
+    ]);
+    let solution = solver.solve(&request("app")).unwrap();
+    assert_eq!(atoms(&solution), ["app.1", "lib.1", "tool.1"]);
+
+    // Distributed into clauses, this would take 2^24 of them.
+    let alternatives: Vec<String> = (0..24)
+        .map(|i| format!(r#"("left-{0}" & "right-{0}")"#, i))
+        .collect();
+    let mut packages = vec![(
+        "app.1".to_string(),
+        format!("depends: [{}]", alternatives.join(" | ")),
+    )];
+    for i in 0..24 {
+        packages.push((format!("left-{}.1", i), String::new()));
+        packages.push((format!("right-{}.1", i), String::new()));
+    }
+    let packages: Vec<(&str, &str)> = packages
+        .iter()
+        .map(|(atom, fields)| (atom.as_str(), fields.as_str()))
+        .collect();
+    let solution = read(&packages).solve(&request("app")).unwrap();
+    assert_eq!(atoms(&solution), ["app.1", "left-0.1", "right-0.1"]);
+}
+
+#[test]
+fn conflicts_and_conflict_classes() {
+    let solver = read(&[
+        ("app.1", r#"depends: ["lib" "tool"]"#),
+        (
+            "app.2",
+            r#"depends: ["lib" "tool"] conflicts: ["tool" {>= "2"}]"#,
+        ),
+        ("lib.1", r#"conflict-class: "backend""#),
+        ("tool.1", r#"conflict-class: "backend""#),
+        ("tool.2", ""),
+    ]);
+    let solution = solver.solve(&request("app")).unwrap();
+    assert_eq!(atoms(&solution), ["app.1", "lib.1", "tool.2"]);
+}
+
+#[test]
+fn unsatisfiable_requests_are_explained_minimally() {
+    let solver = read(&[
+        ("app.1", r#"depends: ["lib" "tool"]"#),
+        ("lib.1", r#"depends: ["base" {>= "2"}]"#),
+        ("lib.2", r#"depends: ["base" {>= "3"}] available: false"#),
+        ("tool.1", r#"depends: ["base"]"#),
+        ("base.1", ""),
+        ("base.2", ""),
+        ("base.3", ""),
+    ]);
+    assert!(solver.satisfiable(&request("app")));
+
+    let solver = read(&[
+        ("app.1", r#"depends: ["lib" "tool"]"#),
+        ("lib.1", r#"depends: ["base" {>= "2"} "gone"]"#),
+        ("tool.1", ""),
+        ("base.2", ""),
+    ]);
+    let explanation = solver.solve(&request("app")).unwrap_err();
+    let facts: Vec<String> = explanation.facts.iter().map(Fact::to_string).collect();
+    assert_eq!(
+        facts,
+        [
+            r#""app" is requested"#,
+            r#"app.1 depends on "lib""#,
+            r#"lib.1 depends on "gone""#,
+            r#"no version satisfies "gone""#,
+        ]
+    );
+    assert!(!solver.satisfiable(&request("app")));
+}
+
+#[test]
+fn conflicting_dependencies_are_explained() {
+    let solver = read(&[
+        ("app.1", r#"depends: ["lib" "tool"]"#),
+        ("lib.1", r#"depends: ["base" {< "2"}]"#),
+        (
+            "tool.1",
+            r#"depends: ["base" {>= "2"} | ("other" & "base" {>= "3"})]"#,
+        ),
+        ("base.1", ""),
+        ("base.2", ""),
+        ("other.1", ""),
+    ]);
+    let explanation = solver.solve(&request("app")).unwrap_err();
+    assert_eq!(
+        explanation.to_string(),
+        r#""app" is requested
+only one version of base can be installed
+app.1 depends on "lib"
+app.1 depends on "tool"
+lib.1 depends on "base" {< "2"}
+tool.1 depends on "base" {>= "2"} | ("other" & "base" {>= "3"})
+no version satisfies "base" {>= "3"}"#
+    );
+}
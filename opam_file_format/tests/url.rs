@@ -0,0 +1,80 @@
+use opam_file_format::parse_str;
+use opam_file_format::parser::{Item, Value};
+use opam_file_format::url::{self, OpamUrl, SourceKind};
+
+#[test]
+fn addresses_print_as_written() {
+    for address in &[
+        "https://example.com/foo-1.0.tar.gz",
+        "git+https://github.com/org/repo#main",
+        "git://github.com/org/repo.git",
+        "git@github.com:org/repo.git",
+        "git@github.com:org/repo.git#v1.0",
+        "hg+ssh://hg@example.com/repo",
+        "./vendor/foo",
+        "/srv/foo",
+    ] {
+        assert_eq!(OpamUrl::parse(address).unwrap().to_string(), *address);
+    }
+}
+
+#[test]
+fn scp_like_addresses() {
+    let url = OpamUrl::parse("git@github.com:org/repo.git").unwrap();
+    assert_eq!(url.kind, SourceKind::Git);
+    assert_eq!(url.transport, "ssh");
+    assert!(!url.explicit_transport);
+    assert_eq!(url.host(), Some("github.com"));
+
+    // Without a `.git` suffix, the kind has to be spelled out, which needs a transport.
+    let mut url = OpamUrl::parse("git@github.com:org/repo").unwrap();
+    assert_eq!(url.kind, SourceKind::Rsync);
+    url.kind = SourceKind::Git;
+    assert_eq!(url.to_string(), "git+ssh://git@github.com:org/repo");
+}
+
+#[test]
+fn hosts_skip_user_info_and_ports() {
+    let host = |address| OpamUrl::parse(address).unwrap().host().map(str::to_string);
+    assert_eq!(
+        host("https://user:p@ss@Example.com:8080/a@b"),
+        Some("Example.com".to_string())
+    );
+    assert_eq!(host("file:///srv/foo"), None);
+    assert_eq!(
+        OpamUrl::parse("https://user@Example.COM/Repo/")
+            .unwrap()
+            .normalize()
+            .to_string(),
+        "https://user@example.com/Repo"
+    );
+}
+
+#[test]
+fn sections_round_trip() {
+    let ast = parse_str(
+        r#"
+url {
+  src: "git@github.com:org/repo.git#v1.0"
+  mirrors: ["git+https://github.com/org/repo.git#v1.0"]
+}
+dev-repo: "git@github.com:org/repo.git"
+"#,
+    )
+    .unwrap();
+    let section = url::url(&ast).unwrap().unwrap();
+    let items = section.to_items();
+    let src = |items: &indexmap::IndexMap<String, Box<Item>>| match items["src"].as_ref() {
+        Item::Variable(Value::String(src)) => src.clone(),
+        item => panic!("{:?}", item),
+    };
+    assert_eq!(src(&items), "git@github.com:org/repo.git#v1.0");
+    match ast.items["url"].as_ref() {
+        Item::Section { items: written, .. } => assert_eq!(items, *written),
+        item => panic!("{:?}", item),
+    }
+    assert_eq!(
+        url::dev_repo(&ast).unwrap().unwrap().to_string(),
+        "git@github.com:org/repo.git"
+    );
+}
@@ -0,0 +1,35 @@
+//! A tree-sitter grammar for opam files, for editors to highlight them and navigate them.
+//!
+//! The grammar has the tokens of `opam_file_format::lexer` and the rules of
+//! `opam_file_format::parser`, and gives the same tree for the same file: values are named after
+//! the variants of `parser::Value`, and operators are the `operator` field of the node applying
+//! them.
+//!
+//! ```
+//! let mut parser = tree_sitter::Parser::new();
+//! parser.set_language(tree_sitter_opam::language()).unwrap();
+//! let tree = parser.parse("depends: [\"dune\" {>= \"3.0\"}]", None).unwrap();
+//! assert_eq!(
+//!     tree.root_node().to_sexp(),
+//!     "(source_file (field name: (ident) value: (list (option value: (string) (prefix_relop operand: (string))))))"
+//! );
+//! ```
+use tree_sitter::Language;
+
+extern "C" {
+    fn tree_sitter_opam() -> Language;
+}
+
+/// The tree-sitter language of opam files.
+pub fn language() -> Language {
+    unsafe { tree_sitter_opam() }
+}
+
+/// The grammar the parser was generated from.
+pub const GRAMMAR: &str = include_str!("../../grammar.js");
+
+/// The syntax highlighting query.
+pub const HIGHLIGHTS_QUERY: &str = include_str!("../../queries/highlights.scm");
+
+/// The types of the nodes of the trees, as listed in `node-types.json`.
+pub const NODE_TYPES: &str = include_str!("../../src/node-types.json");
@@ -0,0 +1,120 @@
+/**
+ * @file opam file grammar for tree-sitter
+ * @license MIT
+ *
+ * Tokens follow `opam_file_format::lexer::Token` and rules follow the grammar of
+ * `opam_file_format::parser`, so that both give the same tree for the same file. The test
+ * corpus is checked against `parser::parse` by `tests/corpus.rs`.
+ */
+
+// Operators of `RELOP`, `ENVOP`, `LOGOP` and `PFXOP` tokens.
+const RELOP = ['=', '!=', '<', '<=', '>', '>=', '~'];
+const ENVOP = ['+=', '=+', '=+='];
+const LOGOP = ['&', '|'];
+const PFXOP = ['!', '?'];
+
+const ID = /[a-zA-Z0-9_-]*[a-zA-Z][a-zA-Z0-9_-]*/;
+const ESCAPE = /\\(["'\\nrtb ]|[0-9][0-9][0-9]|x[0-9a-fA-F][0-9a-fA-F]|\r?\n[ \t]*)/;
+
+const PREC = {
+  logop: 1,
+  pfxop: 1,
+  relop: 2,
+  option: 3,
+};
+
+module.exports = grammar({
+  name: 'opam',
+
+  extras: $ => [/[ \t\r\n]/, $.comment],
+
+  // `#` line comments and nested `(* *)` comments, scanned in `src/scanner.c`.
+  externals: $ => [$.comment],
+
+  rules: {
+    source_file: $ => repeat($._item),
+
+    _item: $ => choice($.field, $.section),
+
+    field: $ => seq(field('name', $.ident), ':', field('value', $._value)),
+
+    section: $ => seq(
+      field('kind', $.ident),
+      optional(field('name', $.string)),
+      '{',
+      repeat($._item),
+      '}',
+    ),
+
+    _value: $ => choice(
+      $._atom,
+      $.list,
+      $.group,
+      $.option,
+      $.logop,
+      $.relop,
+      $.prefix_relop,
+      $.env_binding,
+      $.pfxop,
+    ),
+
+    _atom: $ => choice($.ident, $.bool, $.int, $.string),
+
+    list: $ => seq('[', repeat($._value), ']'),
+
+    group: $ => seq('(', repeat($._value), ')'),
+
+    // Options bind tighter than the operators, `a & b {c}` applying `{c}` to `b`.
+    option: $ => prec(PREC.option, seq(
+      field('value', $._value),
+      '{',
+      repeat($._value),
+      '}',
+    )),
+
+    // `&` and `|` share a precedence and group to the right, as `parser::parse` does.
+    logop: $ => prec.right(PREC.logop, seq(
+      field('left', $._value),
+      field('operator', choice(...LOGOP)),
+      field('right', $._value),
+    )),
+
+    relop: $ => prec(PREC.relop, seq(
+      field('left', $._atom),
+      field('operator', choice(...RELOP)),
+      field('right', $._atom),
+    )),
+
+    prefix_relop: $ => seq(
+      field('operator', choice(...RELOP)),
+      field('operand', $._atom),
+    ),
+
+    env_binding: $ => prec(PREC.relop, seq(
+      field('variable', $._atom),
+      field('operator', choice(...ENVOP)),
+      field('value', $._atom),
+    )),
+
+    // The operand extends as far as it can, `!a & b` negating `a & b`.
+    pfxop: $ => prec.right(PREC.pfxop, seq(
+      field('operator', choice(...PFXOP)),
+      field('operand', $._value),
+    )),
+
+    ident: $ => token(seq(
+      choice(ID, '_'),
+      repeat(seq('+', choice(ID, '_'))),
+      optional(seq(':', ID)),
+    )),
+
+    bool: $ => choice('true', 'false'),
+
+    int: $ => /-?[0-9_]+/,
+
+    string: $ => token(choice(
+      seq('"', repeat(choice(/[^"\\]/, ESCAPE)), '"'),
+      seq('"""', repeat(choice(/[^"\\]/, /"[^"\\]/, /""[^"\\]/, seq(optional(/""?/), ESCAPE))), '"""'),
+    )),
+  },
+});
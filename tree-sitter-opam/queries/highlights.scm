@@ -0,0 +1,41 @@
+(comment) @comment
+
+(string) @string
+
+(int) @number
+
+(bool) @constant.builtin
+
+(field name: (ident) @property)
+
+(section kind: (ident) @type)
+
+(ident) @variable
+
+[
+  "="
+  "!="
+  "<"
+  "<="
+  ">"
+  ">="
+  "~"
+  "+="
+  "=+"
+  "=+="
+  "&"
+  "|"
+  "!"
+  "?"
+] @operator
+
+":" @punctuation.delimiter
+
+[
+  "["
+  "]"
+  "("
+  ")"
+  "{"
+  "}"
+] @punctuation.bracket
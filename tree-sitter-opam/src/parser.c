@@ -0,0 +1,4138 @@
+#include <tree_sitter/parser.h>
+
+#if defined(__GNUC__) || defined(__clang__)
+#pragma GCC diagnostic push
+#pragma GCC diagnostic ignored "-Wmissing-field-initializers"
+#endif
+
+#define LANGUAGE_VERSION 14
+#define STATE_COUNT 97
+#define LARGE_STATE_COUNT 2
+#define SYMBOL_COUNT 48
+#define ALIAS_COUNT 0
+#define TOKEN_COUNT 28
+#define EXTERNAL_TOKEN_COUNT 1
+#define FIELD_COUNT 8
+#define MAX_ALIAS_SEQUENCE_LENGTH 5
+#define PRODUCTION_ID_COUNT 8
+
+enum {
+  sym_ident = 1,
+  anon_sym_COLON = 2,
+  sym_string = 3,
+  anon_sym_LBRACE = 4,
+  anon_sym_RBRACE = 5,
+  sym_int = 6,
+  anon_sym_LBRACK = 7,
+  anon_sym_RBRACK = 8,
+  anon_sym_LPAREN = 9,
+  anon_sym_RPAREN = 10,
+  anon_sym_AMP = 11,
+  anon_sym_PIPE = 12,
+  anon_sym_EQ = 13,
+  anon_sym_BANG_EQ = 14,
+  anon_sym_LT = 15,
+  anon_sym_LT_EQ = 16,
+  anon_sym_GT = 17,
+  anon_sym_GT_EQ = 18,
+  anon_sym_TILDE = 19,
+  anon_sym_PLUS_EQ = 20,
+  anon_sym_EQ_PLUS = 21,
+  anon_sym_EQ_PLUS_EQ = 22,
+  anon_sym_BANG = 23,
+  anon_sym_QMARK = 24,
+  anon_sym_true = 25,
+  anon_sym_false = 26,
+  sym_comment = 27,
+  sym_source_file = 28,
+  sym__item = 29,
+  sym_field = 30,
+  sym_section = 31,
+  sym__value = 32,
+  sym__atom = 33,
+  sym_list = 34,
+  sym_group = 35,
+  sym_option = 36,
+  sym_logop = 37,
+  sym_relop = 38,
+  sym_prefix_relop = 39,
+  sym_env_binding = 40,
+  sym_pfxop = 41,
+  sym_bool = 42,
+  aux_sym_source_file_repeat1 = 43,
+  aux_sym_section_repeat1 = 44,
+  aux_sym_list_repeat1 = 45,
+  aux_sym_group_repeat1 = 46,
+  aux_sym_option_repeat1 = 47,
+};
+
+static const char * const ts_symbol_names[] = {
+  [ts_builtin_sym_end] = "end",
+  [sym_ident] = "ident",
+  [anon_sym_COLON] = ":",
+  [sym_string] = "string",
+  [anon_sym_LBRACE] = "{",
+  [anon_sym_RBRACE] = "}",
+  [sym_int] = "int",
+  [anon_sym_LBRACK] = "[",
+  [anon_sym_RBRACK] = "]",
+  [anon_sym_LPAREN] = "(",
+  [anon_sym_RPAREN] = ")",
+  [anon_sym_AMP] = "&",
+  [anon_sym_PIPE] = "|",
+  [anon_sym_EQ] = "=",
+  [anon_sym_BANG_EQ] = "!=",
+  [anon_sym_LT] = "<",
+  [anon_sym_LT_EQ] = "<=",
+  [anon_sym_GT] = ">",
+  [anon_sym_GT_EQ] = ">=",
+  [anon_sym_TILDE] = "~",
+  [anon_sym_PLUS_EQ] = "+=",
+  [anon_sym_EQ_PLUS] = "=+",
+  [anon_sym_EQ_PLUS_EQ] = "=+=",
+  [anon_sym_BANG] = "!",
+  [anon_sym_QMARK] = "?",
+  [anon_sym_true] = "true",
+  [anon_sym_false] = "false",
+  [sym_comment] = "comment",
+  [sym_source_file] = "source_file",
+  [sym__item] = "_item",
+  [sym_field] = "field",
+  [sym_section] = "section",
+  [sym__value] = "_value",
+  [sym__atom] = "_atom",
+  [sym_list] = "list",
+  [sym_group] = "group",
+  [sym_option] = "option",
+  [sym_logop] = "logop",
+  [sym_relop] = "relop",
+  [sym_prefix_relop] = "prefix_relop",
+  [sym_env_binding] = "env_binding",
+  [sym_pfxop] = "pfxop",
+  [sym_bool] = "bool",
+  [aux_sym_source_file_repeat1] = "source_file_repeat1",
+  [aux_sym_section_repeat1] = "section_repeat1",
+  [aux_sym_list_repeat1] = "list_repeat1",
+  [aux_sym_group_repeat1] = "group_repeat1",
+  [aux_sym_option_repeat1] = "option_repeat1",
+};
+
+static const TSSymbol ts_symbol_map[] = {
+  [ts_builtin_sym_end] = ts_builtin_sym_end,
+  [sym_ident] = sym_ident,
+  [anon_sym_COLON] = anon_sym_COLON,
+  [sym_string] = sym_string,
+  [anon_sym_LBRACE] = anon_sym_LBRACE,
+  [anon_sym_RBRACE] = anon_sym_RBRACE,
+  [sym_int] = sym_int,
+  [anon_sym_LBRACK] = anon_sym_LBRACK,
+  [anon_sym_RBRACK] = anon_sym_RBRACK,
+  [anon_sym_LPAREN] = anon_sym_LPAREN,
+  [anon_sym_RPAREN] = anon_sym_RPAREN,
+  [anon_sym_AMP] = anon_sym_AMP,
+  [anon_sym_PIPE] = anon_sym_PIPE,
+  [anon_sym_EQ] = anon_sym_EQ,
+  [anon_sym_BANG_EQ] = anon_sym_BANG_EQ,
+  [anon_sym_LT] = anon_sym_LT,
+  [anon_sym_LT_EQ] = anon_sym_LT_EQ,
+  [anon_sym_GT] = anon_sym_GT,
+  [anon_sym_GT_EQ] = anon_sym_GT_EQ,
+  [anon_sym_TILDE] = anon_sym_TILDE,
+  [anon_sym_PLUS_EQ] = anon_sym_PLUS_EQ,
+  [anon_sym_EQ_PLUS] = anon_sym_EQ_PLUS,
+  [anon_sym_EQ_PLUS_EQ] = anon_sym_EQ_PLUS_EQ,
+  [anon_sym_BANG] = anon_sym_BANG,
+  [anon_sym_QMARK] = anon_sym_QMARK,
+  [anon_sym_true] = anon_sym_true,
+  [anon_sym_false] = anon_sym_false,
+  [sym_comment] = sym_comment,
+  [sym_source_file] = sym_source_file,
+  [sym__item] = sym__item,
+  [sym_field] = sym_field,
+  [sym_section] = sym_section,
+  [sym__value] = sym__value,
+  [sym__atom] = sym__atom,
+  [sym_list] = sym_list,
+  [sym_group] = sym_group,
+  [sym_option] = sym_option,
+  [sym_logop] = sym_logop,
+  [sym_relop] = sym_relop,
+  [sym_prefix_relop] = sym_prefix_relop,
+  [sym_env_binding] = sym_env_binding,
+  [sym_pfxop] = sym_pfxop,
+  [sym_bool] = sym_bool,
+  [aux_sym_source_file_repeat1] = aux_sym_source_file_repeat1,
+  [aux_sym_section_repeat1] = aux_sym_section_repeat1,
+  [aux_sym_list_repeat1] = aux_sym_list_repeat1,
+  [aux_sym_group_repeat1] = aux_sym_group_repeat1,
+  [aux_sym_option_repeat1] = aux_sym_option_repeat1,
+};
+
+static const TSSymbolMetadata ts_symbol_metadata[] = {
+  [ts_builtin_sym_end] = {
+    .visible = false,
+    .named = true,
+  },
+  [sym_ident] = {
+    .visible = true,
+    .named = true,
+  },
+  [anon_sym_COLON] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_string] = {
+    .visible = true,
+    .named = true,
+  },
+  [anon_sym_LBRACE] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_RBRACE] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_int] = {
+    .visible = true,
+    .named = true,
+  },
+  [anon_sym_LBRACK] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_RBRACK] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_LPAREN] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_RPAREN] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_AMP] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_PIPE] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_BANG_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_LT] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_LT_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_GT] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_GT_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_TILDE] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_PLUS_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_EQ_PLUS] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_EQ_PLUS_EQ] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_BANG] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_QMARK] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_true] = {
+    .visible = true,
+    .named = false,
+  },
+  [anon_sym_false] = {
+    .visible = true,
+    .named = false,
+  },
+  [sym_comment] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_source_file] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym__item] = {
+    .visible = false,
+    .named = true,
+  },
+  [sym_field] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_section] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym__value] = {
+    .visible = false,
+    .named = true,
+  },
+  [sym__atom] = {
+    .visible = false,
+    .named = true,
+  },
+  [sym_list] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_group] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_option] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_logop] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_relop] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_prefix_relop] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_env_binding] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_pfxop] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_bool] = {
+    .visible = true,
+    .named = true,
+  },
+  [aux_sym_source_file_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
+  [aux_sym_section_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
+  [aux_sym_list_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
+  [aux_sym_group_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
+  [aux_sym_option_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
+};
+
+enum {
+  field_kind = 1,
+  field_left = 2,
+  field_name = 3,
+  field_operand = 4,
+  field_operator = 5,
+  field_right = 6,
+  field_value = 7,
+  field_variable = 8,
+};
+
+static const char * const ts_field_names[] = {
+  [0] = NULL,
+  [field_kind] = "kind",
+  [field_left] = "left",
+  [field_name] = "name",
+  [field_operand] = "operand",
+  [field_operator] = "operator",
+  [field_right] = "right",
+  [field_value] = "value",
+  [field_variable] = "variable",
+};
+
+static const TSFieldMapSlice ts_field_map_slices[PRODUCTION_ID_COUNT] = {
+  [1] = {.index = 0, .length = 2},
+  [2] = {.index = 2, .length = 2},
+  [3] = {.index = 4, .length = 1},
+  [4] = {.index = 5, .length = 1},
+  [5] = {.index = 6, .length = 3},
+  [6] = {.index = 9, .length = 2},
+  [7] = {.index = 11, .length = 3},
+};
+
+static const TSFieldMapEntry ts_field_map_entries[] = {
+  [0] =
+    {field_name, 0},
+    {field_value, 2},
+  [2] =
+    {field_kind, 0},
+    {field_name, 1},
+  [4] =
+    {field_kind, 0},
+  [5] =
+    {field_value, 0},
+  [6] =
+    {field_left, 0},
+    {field_operator, 1},
+    {field_right, 2},
+  [9] =
+    {field_operand, 1},
+    {field_operator, 0},
+  [11] =
+    {field_operator, 1},
+    {field_value, 2},
+    {field_variable, 0},
+};
+
+static const TSSymbol ts_alias_sequences[PRODUCTION_ID_COUNT][MAX_ALIAS_SEQUENCE_LENGTH] = {
+  [0] = {0},
+};
+
+static const uint16_t ts_non_terminal_alias_map[] = {
+  0,
+};
+
+static const TSStateId ts_primary_state_ids[STATE_COUNT] = {
+  [0] = 0,
+  [1] = 1,
+  [2] = 2,
+  [3] = 3,
+  [4] = 4,
+  [5] = 5,
+  [6] = 6,
+  [7] = 7,
+  [8] = 8,
+  [9] = 9,
+  [10] = 10,
+  [11] = 11,
+  [12] = 12,
+  [13] = 13,
+  [14] = 14,
+  [15] = 15,
+  [16] = 16,
+  [17] = 17,
+  [18] = 18,
+  [19] = 19,
+  [20] = 20,
+  [21] = 21,
+  [22] = 22,
+  [23] = 23,
+  [24] = 24,
+  [25] = 25,
+  [26] = 26,
+  [27] = 27,
+  [28] = 28,
+  [29] = 29,
+  [30] = 30,
+  [31] = 31,
+  [32] = 32,
+  [33] = 33,
+  [34] = 34,
+  [35] = 35,
+  [36] = 36,
+  [37] = 37,
+  [38] = 38,
+  [39] = 39,
+  [40] = 40,
+  [41] = 41,
+  [42] = 42,
+  [43] = 43,
+  [44] = 44,
+  [45] = 45,
+  [46] = 46,
+  [47] = 47,
+  [48] = 48,
+  [49] = 49,
+  [50] = 50,
+  [51] = 51,
+  [52] = 52,
+  [53] = 53,
+  [54] = 54,
+  [55] = 55,
+  [56] = 56,
+  [57] = 57,
+  [58] = 58,
+  [59] = 59,
+  [60] = 60,
+  [61] = 61,
+  [62] = 62,
+  [63] = 63,
+  [64] = 64,
+  [65] = 65,
+  [66] = 66,
+  [67] = 67,
+  [68] = 68,
+  [69] = 69,
+  [70] = 70,
+  [71] = 71,
+  [72] = 72,
+  [73] = 73,
+  [74] = 74,
+  [75] = 75,
+  [76] = 76,
+  [77] = 77,
+  [78] = 78,
+  [79] = 79,
+  [80] = 80,
+  [81] = 81,
+  [82] = 82,
+  [83] = 83,
+  [84] = 84,
+  [85] = 85,
+  [86] = 86,
+  [87] = 87,
+  [88] = 88,
+  [89] = 89,
+  [90] = 90,
+  [91] = 91,
+  [92] = 92,
+  [93] = 93,
+  [94] = 94,
+  [95] = 95,
+  [96] = 96,
+};
+
+static bool ts_lex(TSLexer *lexer, TSStateId state) {
+  START_LEXER();
+  switch (state) {
+    case 0:
+      if (eof) ADVANCE(83);
+      if (lookahead == '!') ADVANCE(1);
+      if (lookahead == '"') ADVANCE(2);
+      if (lookahead == '&') ADVANCE(3);
+      if (lookahead == '(') ADVANCE(4);
+      if (lookahead == ')') ADVANCE(5);
+      if (lookahead == '+') ADVANCE(6);
+      if (lookahead == '-') ADVANCE(7);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(8);
+      if (lookahead == ':') ADVANCE(9);
+      if (lookahead == '<') ADVANCE(10);
+      if (lookahead == '=') ADVANCE(11);
+      if (lookahead == '>') ADVANCE(12);
+      if (lookahead == '?') ADVANCE(13);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'e') ||
+          ('g' <= lookahead && lookahead <= 's') ||
+          ('u' <= lookahead && lookahead <= 'z')) ADVANCE(14);
+      if (lookahead == '[') ADVANCE(15);
+      if (lookahead == ']') ADVANCE(16);
+      if (lookahead == '_') ADVANCE(17);
+      if (lookahead == 'f') ADVANCE(18);
+      if (lookahead == 't') ADVANCE(19);
+      if (lookahead == '{') ADVANCE(20);
+      if (lookahead == '|') ADVANCE(21);
+      if (lookahead == '}') ADVANCE(22);
+      if (lookahead == '~') ADVANCE(23);
+      if (lookahead == '\t' ||
+          lookahead == '\n' ||
+          lookahead == '\r' ||
+          lookahead == ' ') SKIP(0)
+      END_STATE();
+    case 1:
+      ACCEPT_TOKEN(anon_sym_BANG);
+      if (lookahead == '=') ADVANCE(24);
+      END_STATE();
+    case 2:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(25);
+      if (lookahead == '"') ADVANCE(26);
+      if (lookahead == '\\') ADVANCE(27);
+      END_STATE();
+    case 3:
+      ACCEPT_TOKEN(anon_sym_AMP);
+      END_STATE();
+    case 4:
+      ACCEPT_TOKEN(anon_sym_LPAREN);
+      END_STATE();
+    case 5:
+      ACCEPT_TOKEN(anon_sym_RPAREN);
+      END_STATE();
+    case 6:
+      if (lookahead == '=') ADVANCE(28);
+      END_STATE();
+    case 7:
+      if (lookahead == '-') ADVANCE(29);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(8);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(14);
+      END_STATE();
+    case 8:
+      ACCEPT_TOKEN(sym_int);
+      if (lookahead == '-') ADVANCE(29);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(8);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(14);
+      END_STATE();
+    case 9:
+      ACCEPT_TOKEN(anon_sym_COLON);
+      END_STATE();
+    case 10:
+      ACCEPT_TOKEN(anon_sym_LT);
+      if (lookahead == '=') ADVANCE(30);
+      END_STATE();
+    case 11:
+      ACCEPT_TOKEN(anon_sym_EQ);
+      if (lookahead == '+') ADVANCE(31);
+      END_STATE();
+    case 12:
+      ACCEPT_TOKEN(anon_sym_GT);
+      if (lookahead == '=') ADVANCE(32);
+      END_STATE();
+    case 13:
+      ACCEPT_TOKEN(anon_sym_QMARK);
+      END_STATE();
+    case 14:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      END_STATE();
+    case 15:
+      ACCEPT_TOKEN(anon_sym_LBRACK);
+      END_STATE();
+    case 16:
+      ACCEPT_TOKEN(anon_sym_RBRACK);
+      END_STATE();
+    case 17:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-') ADVANCE(29);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(8);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(14);
+      END_STATE();
+    case 18:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('b' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      if (lookahead == 'a') ADVANCE(37);
+      END_STATE();
+    case 19:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'q') ||
+          ('s' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      if (lookahead == 'r') ADVANCE(38);
+      END_STATE();
+    case 20:
+      ACCEPT_TOKEN(anon_sym_LBRACE);
+      END_STATE();
+    case 21:
+      ACCEPT_TOKEN(anon_sym_PIPE);
+      END_STATE();
+    case 22:
+      ACCEPT_TOKEN(anon_sym_RBRACE);
+      END_STATE();
+    case 23:
+      ACCEPT_TOKEN(anon_sym_TILDE);
+      END_STATE();
+    case 24:
+      ACCEPT_TOKEN(anon_sym_BANG_EQ);
+      END_STATE();
+    case 25:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(25);
+      if (lookahead == '"') ADVANCE(39);
+      if (lookahead == '\\') ADVANCE(27);
+      END_STATE();
+    case 26:
+      ACCEPT_TOKEN(sym_string);
+      if (lookahead == '"') ADVANCE(40);
+      END_STATE();
+    case 27:
+      if (lookahead == '\n') ADVANCE(41);
+      if (lookahead == '\r') ADVANCE(42);
+      if (lookahead == ' ' ||
+          lookahead == '"' ||
+          lookahead == '\'' ||
+          lookahead == '\\' ||
+          lookahead == 'b' ||
+          lookahead == 'n' ||
+          lookahead == 'r' ||
+          lookahead == 't') ADVANCE(43);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(44);
+      if (lookahead == 'x') ADVANCE(45);
+      END_STATE();
+    case 28:
+      ACCEPT_TOKEN(anon_sym_PLUS_EQ);
+      END_STATE();
+    case 29:
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(29);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(14);
+      END_STATE();
+    case 30:
+      ACCEPT_TOKEN(anon_sym_LT_EQ);
+      END_STATE();
+    case 31:
+      ACCEPT_TOKEN(anon_sym_EQ_PLUS);
+      if (lookahead == '=') ADVANCE(46);
+      END_STATE();
+    case 32:
+      ACCEPT_TOKEN(anon_sym_GT_EQ);
+      END_STATE();
+    case 33:
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9')) ADVANCE(47);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(48);
+      if (lookahead == '_') ADVANCE(49);
+      END_STATE();
+    case 34:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      END_STATE();
+    case 35:
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(50);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(51);
+      END_STATE();
+    case 36:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      END_STATE();
+    case 37:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'k') ||
+          ('m' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      if (lookahead == 'l') ADVANCE(52);
+      END_STATE();
+    case 38:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 't') ||
+          ('v' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      if (lookahead == 'u') ADVANCE(53);
+      END_STATE();
+    case 39:
+      ACCEPT_TOKEN(sym_string);
+      END_STATE();
+    case 40:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 41:
+      if (lookahead != 0 &&
+          lookahead != '\t' &&
+          lookahead != ' ' &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(25);
+      if (lookahead == '\t' ||
+          lookahead == ' ') ADVANCE(57);
+      if (lookahead == '"') ADVANCE(39);
+      if (lookahead == '\\') ADVANCE(27);
+      END_STATE();
+    case 42:
+      if (lookahead == '\n') ADVANCE(41);
+      END_STATE();
+    case 43:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(25);
+      if (lookahead == '"') ADVANCE(39);
+      if (lookahead == '\\') ADVANCE(27);
+      END_STATE();
+    case 44:
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(58);
+      END_STATE();
+    case 45:
+      if (('0' <= lookahead && lookahead <= '9') ||
+          ('A' <= lookahead && lookahead <= 'F') ||
+          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(59);
+      END_STATE();
+    case 46:
+      ACCEPT_TOKEN(anon_sym_EQ_PLUS_EQ);
+      END_STATE();
+    case 47:
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(47);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(48);
+      END_STATE();
+    case 48:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(60);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(61);
+      END_STATE();
+    case 49:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(47);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(48);
+      END_STATE();
+    case 50:
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(50);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(51);
+      END_STATE();
+    case 51:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(62);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(63);
+      END_STATE();
+    case 52:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'r') ||
+          ('t' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      if (lookahead == 's') ADVANCE(64);
+      END_STATE();
+    case 53:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'd') ||
+          ('f' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      if (lookahead == 'e') ADVANCE(65);
+      END_STATE();
+    case 54:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 55:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(66);
+      if (lookahead == '"') ADVANCE(67);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 56:
+      if (lookahead == '\n') ADVANCE(68);
+      if (lookahead == '\r') ADVANCE(69);
+      if (lookahead == ' ' ||
+          lookahead == '"' ||
+          lookahead == '\'' ||
+          lookahead == '\\' ||
+          lookahead == 'b' ||
+          lookahead == 'n' ||
+          lookahead == 'r' ||
+          lookahead == 't') ADVANCE(70);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(71);
+      if (lookahead == 'x') ADVANCE(72);
+      END_STATE();
+    case 57:
+      if (lookahead != 0 &&
+          lookahead != '\t' &&
+          lookahead != ' ' &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(25);
+      if (lookahead == '\t' ||
+          lookahead == ' ') ADVANCE(57);
+      if (lookahead == '"') ADVANCE(39);
+      if (lookahead == '\\') ADVANCE(27);
+      END_STATE();
+    case 58:
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(73);
+      END_STATE();
+    case 59:
+      if (('0' <= lookahead && lookahead <= '9') ||
+          ('A' <= lookahead && lookahead <= 'F') ||
+          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(74);
+      END_STATE();
+    case 60:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(60);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(61);
+      END_STATE();
+    case 61:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(60);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(61);
+      END_STATE();
+    case 62:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(62);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(63);
+      END_STATE();
+    case 63:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(62);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(63);
+      END_STATE();
+    case 64:
+      ACCEPT_TOKEN(sym_ident);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'd') ||
+          ('f' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      if (lookahead == 'e') ADVANCE(75);
+      END_STATE();
+    case 65:
+      ACCEPT_TOKEN(anon_sym_true);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      END_STATE();
+    case 66:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 67:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(76);
+      if (lookahead == '"') ADVANCE(77);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 68:
+      if (lookahead != 0 &&
+          lookahead != '\t' &&
+          lookahead != ' ' &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '\t' ||
+          lookahead == ' ') ADVANCE(78);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 69:
+      if (lookahead == '\n') ADVANCE(68);
+      END_STATE();
+    case 70:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 71:
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(79);
+      END_STATE();
+    case 72:
+      if (('0' <= lookahead && lookahead <= '9') ||
+          ('A' <= lookahead && lookahead <= 'F') ||
+          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(80);
+      END_STATE();
+    case 73:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(25);
+      if (lookahead == '"') ADVANCE(39);
+      if (lookahead == '\\') ADVANCE(27);
+      END_STATE();
+    case 74:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(25);
+      if (lookahead == '"') ADVANCE(39);
+      if (lookahead == '\\') ADVANCE(27);
+      END_STATE();
+    case 75:
+      ACCEPT_TOKEN(anon_sym_false);
+      if (lookahead == '+') ADVANCE(33);
+      if (lookahead == '-' ||
+          ('0' <= lookahead && lookahead <= '9') ||
+          lookahead == '_') ADVANCE(34);
+      if (lookahead == ':') ADVANCE(35);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(36);
+      END_STATE();
+    case 76:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 77:
+      ACCEPT_TOKEN(sym_string);
+      END_STATE();
+    case 78:
+      if (lookahead != 0 &&
+          lookahead != '\t' &&
+          lookahead != ' ' &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '\t' ||
+          lookahead == ' ') ADVANCE(78);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 79:
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(81);
+      END_STATE();
+    case 80:
+      if (('0' <= lookahead && lookahead <= '9') ||
+          ('A' <= lookahead && lookahead <= 'F') ||
+          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(82);
+      END_STATE();
+    case 81:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 82:
+      if (lookahead != 0 &&
+          lookahead != '"' &&
+          lookahead != '\\') ADVANCE(54);
+      if (lookahead == '"') ADVANCE(55);
+      if (lookahead == '\\') ADVANCE(56);
+      END_STATE();
+    case 83:
+      ACCEPT_TOKEN(ts_builtin_sym_end);
+      END_STATE();
+    default:
+      return false;
+  }
+}
+
+static const TSLexMode ts_lex_modes[STATE_COUNT] = {
+  [0] = {.lex_state = 0, .external_lex_state = 1},
+  [1] = {.lex_state = 0, .external_lex_state = 1},
+  [2] = {.lex_state = 0, .external_lex_state = 1},
+  [3] = {.lex_state = 0, .external_lex_state = 1},
+  [4] = {.lex_state = 0, .external_lex_state = 1},
+  [5] = {.lex_state = 0, .external_lex_state = 1},
+  [6] = {.lex_state = 0, .external_lex_state = 1},
+  [7] = {.lex_state = 0, .external_lex_state = 1},
+  [8] = {.lex_state = 0, .external_lex_state = 1},
+  [9] = {.lex_state = 0, .external_lex_state = 1},
+  [10] = {.lex_state = 0, .external_lex_state = 1},
+  [11] = {.lex_state = 0, .external_lex_state = 1},
+  [12] = {.lex_state = 0, .external_lex_state = 1},
+  [13] = {.lex_state = 0, .external_lex_state = 1},
+  [14] = {.lex_state = 0, .external_lex_state = 1},
+  [15] = {.lex_state = 0, .external_lex_state = 1},
+  [16] = {.lex_state = 0, .external_lex_state = 1},
+  [17] = {.lex_state = 0, .external_lex_state = 1},
+  [18] = {.lex_state = 0, .external_lex_state = 1},
+  [19] = {.lex_state = 0, .external_lex_state = 1},
+  [20] = {.lex_state = 0, .external_lex_state = 1},
+  [21] = {.lex_state = 0, .external_lex_state = 1},
+  [22] = {.lex_state = 0, .external_lex_state = 1},
+  [23] = {.lex_state = 0, .external_lex_state = 1},
+  [24] = {.lex_state = 0, .external_lex_state = 1},
+  [25] = {.lex_state = 0, .external_lex_state = 1},
+  [26] = {.lex_state = 0, .external_lex_state = 1},
+  [27] = {.lex_state = 0, .external_lex_state = 1},
+  [28] = {.lex_state = 0, .external_lex_state = 1},
+  [29] = {.lex_state = 0, .external_lex_state = 1},
+  [30] = {.lex_state = 0, .external_lex_state = 1},
+  [31] = {.lex_state = 0, .external_lex_state = 1},
+  [32] = {.lex_state = 0, .external_lex_state = 1},
+  [33] = {.lex_state = 0, .external_lex_state = 1},
+  [34] = {.lex_state = 0, .external_lex_state = 1},
+  [35] = {.lex_state = 0, .external_lex_state = 1},
+  [36] = {.lex_state = 0, .external_lex_state = 1},
+  [37] = {.lex_state = 0, .external_lex_state = 1},
+  [38] = {.lex_state = 0, .external_lex_state = 1},
+  [39] = {.lex_state = 0, .external_lex_state = 1},
+  [40] = {.lex_state = 0, .external_lex_state = 1},
+  [41] = {.lex_state = 0, .external_lex_state = 1},
+  [42] = {.lex_state = 0, .external_lex_state = 1},
+  [43] = {.lex_state = 0, .external_lex_state = 1},
+  [44] = {.lex_state = 0, .external_lex_state = 1},
+  [45] = {.lex_state = 0, .external_lex_state = 1},
+  [46] = {.lex_state = 0, .external_lex_state = 1},
+  [47] = {.lex_state = 0, .external_lex_state = 1},
+  [48] = {.lex_state = 0, .external_lex_state = 1},
+  [49] = {.lex_state = 0, .external_lex_state = 1},
+  [50] = {.lex_state = 0, .external_lex_state = 1},
+  [51] = {.lex_state = 0, .external_lex_state = 1},
+  [52] = {.lex_state = 0, .external_lex_state = 1},
+  [53] = {.lex_state = 0, .external_lex_state = 1},
+  [54] = {.lex_state = 0, .external_lex_state = 1},
+  [55] = {.lex_state = 0, .external_lex_state = 1},
+  [56] = {.lex_state = 0, .external_lex_state = 1},
+  [57] = {.lex_state = 0, .external_lex_state = 1},
+  [58] = {.lex_state = 0, .external_lex_state = 1},
+  [59] = {.lex_state = 0, .external_lex_state = 1},
+  [60] = {.lex_state = 0, .external_lex_state = 1},
+  [61] = {.lex_state = 0, .external_lex_state = 1},
+  [62] = {.lex_state = 0, .external_lex_state = 1},
+  [63] = {.lex_state = 0, .external_lex_state = 1},
+  [64] = {.lex_state = 0, .external_lex_state = 1},
+  [65] = {.lex_state = 0, .external_lex_state = 1},
+  [66] = {.lex_state = 0, .external_lex_state = 1},
+  [67] = {.lex_state = 0, .external_lex_state = 1},
+  [68] = {.lex_state = 0, .external_lex_state = 1},
+  [69] = {.lex_state = 0, .external_lex_state = 1},
+  [70] = {.lex_state = 0, .external_lex_state = 1},
+  [71] = {.lex_state = 0, .external_lex_state = 1},
+  [72] = {.lex_state = 0, .external_lex_state = 1},
+  [73] = {.lex_state = 0, .external_lex_state = 1},
+  [74] = {.lex_state = 0, .external_lex_state = 1},
+  [75] = {.lex_state = 0, .external_lex_state = 1},
+  [76] = {.lex_state = 0, .external_lex_state = 1},
+  [77] = {.lex_state = 0, .external_lex_state = 1},
+  [78] = {.lex_state = 0, .external_lex_state = 1},
+  [79] = {.lex_state = 0, .external_lex_state = 1},
+  [80] = {.lex_state = 0, .external_lex_state = 1},
+  [81] = {.lex_state = 0, .external_lex_state = 1},
+  [82] = {.lex_state = 0, .external_lex_state = 1},
+  [83] = {.lex_state = 0, .external_lex_state = 1},
+  [84] = {.lex_state = 0, .external_lex_state = 1},
+  [85] = {.lex_state = 0, .external_lex_state = 1},
+  [86] = {.lex_state = 0, .external_lex_state = 1},
+  [87] = {.lex_state = 0, .external_lex_state = 1},
+  [88] = {.lex_state = 0, .external_lex_state = 1},
+  [89] = {.lex_state = 0, .external_lex_state = 1},
+  [90] = {.lex_state = 0, .external_lex_state = 1},
+  [91] = {.lex_state = 0, .external_lex_state = 1},
+  [92] = {.lex_state = 0, .external_lex_state = 1},
+  [93] = {.lex_state = 0, .external_lex_state = 1},
+  [94] = {.lex_state = 0, .external_lex_state = 1},
+  [95] = {.lex_state = 0, .external_lex_state = 1},
+  [96] = {.lex_state = 0, .external_lex_state = 1},
+};
+
+enum {
+  ts_external_token_comment = 0,
+};
+
+static const TSSymbol ts_external_scanner_symbol_map[EXTERNAL_TOKEN_COUNT] = {
+  [ts_external_token_comment] = sym_comment,
+};
+
+static const bool ts_external_scanner_states[2][EXTERNAL_TOKEN_COUNT] = {
+  [1] = {
+    [ts_external_token_comment] = true,
+  },
+};
+
+static const uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
+  [0] = {
+    [ts_builtin_sym_end] = ACTIONS(1),
+    [sym_ident] = ACTIONS(1),
+    [anon_sym_COLON] = ACTIONS(1),
+    [sym_string] = ACTIONS(1),
+    [anon_sym_LBRACE] = ACTIONS(1),
+    [anon_sym_RBRACE] = ACTIONS(1),
+    [sym_int] = ACTIONS(1),
+    [anon_sym_LBRACK] = ACTIONS(1),
+    [anon_sym_RBRACK] = ACTIONS(1),
+    [anon_sym_LPAREN] = ACTIONS(1),
+    [anon_sym_RPAREN] = ACTIONS(1),
+    [anon_sym_AMP] = ACTIONS(1),
+    [anon_sym_PIPE] = ACTIONS(1),
+    [anon_sym_EQ] = ACTIONS(1),
+    [anon_sym_BANG_EQ] = ACTIONS(1),
+    [anon_sym_LT] = ACTIONS(1),
+    [anon_sym_LT_EQ] = ACTIONS(1),
+    [anon_sym_GT] = ACTIONS(1),
+    [anon_sym_GT_EQ] = ACTIONS(1),
+    [anon_sym_TILDE] = ACTIONS(1),
+    [anon_sym_PLUS_EQ] = ACTIONS(1),
+    [anon_sym_EQ_PLUS] = ACTIONS(1),
+    [anon_sym_EQ_PLUS_EQ] = ACTIONS(1),
+    [anon_sym_BANG] = ACTIONS(1),
+    [anon_sym_QMARK] = ACTIONS(1),
+    [anon_sym_true] = ACTIONS(1),
+    [anon_sym_false] = ACTIONS(1),
+    [sym_comment] = ACTIONS(3),
+  },
+  [1] = {
+    [ts_builtin_sym_end] = ACTIONS(5),
+    [sym_ident] = ACTIONS(7),
+    [sym_comment] = ACTIONS(3),
+    [sym_source_file] = STATE(7),
+    [sym__item] = STATE(3),
+    [sym_field] = STATE(4),
+    [sym_section] = STATE(5),
+    [aux_sym_source_file_repeat1] = STATE(2),
+  },
+};
+
+static const uint16_t ts_small_parse_table[] = {
+  [0] = 7,
+    ACTIONS(9), 1,
+      ts_builtin_sym_end,
+    ACTIONS(7), 1,
+      sym_ident,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(3), 1,
+      sym__item,
+    STATE(4), 1,
+      sym_field,
+    STATE(5), 1,
+      sym_section,
+    STATE(8), 1,
+      aux_sym_source_file_repeat1,
+  [22] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(11), 2,
+      ts_builtin_sym_end,
+      sym_ident,
+  [30] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(13), 3,
+      ts_builtin_sym_end,
+      sym_ident,
+      anon_sym_RBRACE,
+  [39] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(13), 3,
+      ts_builtin_sym_end,
+      sym_ident,
+      anon_sym_RBRACE,
+  [48] = 4,
+    ACTIONS(15), 1,
+      anon_sym_COLON,
+    ACTIONS(17), 1,
+      sym_string,
+    ACTIONS(19), 1,
+      anon_sym_LBRACE,
+    ACTIONS(3), 1,
+      sym_comment,
+  [61] = 2,
+    ACTIONS(21), 1,
+      ts_builtin_sym_end,
+    ACTIONS(3), 1,
+      sym_comment,
+  [68] = 7,
+    ACTIONS(23), 1,
+      ts_builtin_sym_end,
+    ACTIONS(25), 1,
+      sym_ident,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(3), 1,
+      sym__item,
+    STATE(4), 1,
+      sym_field,
+    STATE(5), 1,
+      sym_section,
+    STATE(8), 1,
+      aux_sym_source_file_repeat1,
+  [90] = 28,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(12), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+  [175] = 2,
+    ACTIONS(60), 1,
+      anon_sym_LBRACE,
+    ACTIONS(3), 1,
+      sym_comment,
+  [182] = 7,
+    ACTIONS(7), 1,
+      sym_ident,
+    ACTIONS(62), 1,
+      anon_sym_RBRACE,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(41), 1,
+      sym__item,
+    STATE(4), 1,
+      sym_field,
+    STATE(5), 1,
+      sym_section,
+    STATE(40), 1,
+      aux_sym_section_repeat1,
+  [204] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(64), 3,
+      ts_builtin_sym_end,
+      sym_ident,
+      anon_sym_RBRACE,
+  [222] = 12,
+    ACTIONS(74), 1,
+      anon_sym_EQ,
+    ACTIONS(76), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(78), 1,
+      anon_sym_LT,
+    ACTIONS(80), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(82), 1,
+      anon_sym_GT,
+    ACTIONS(84), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(86), 1,
+      anon_sym_TILDE,
+    ACTIONS(88), 1,
+      anon_sym_PLUS_EQ,
+    ACTIONS(90), 1,
+      anon_sym_EQ_PLUS,
+    ACTIONS(92), 1,
+      anon_sym_EQ_PLUS_EQ,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 16,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [274] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [303] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [332] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [361] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [390] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [419] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [448] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [477] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(72), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [506] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(94), 26,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_PLUS_EQ,
+      anon_sym_EQ_PLUS,
+      anon_sym_EQ_PLUS_EQ,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [538] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(94), 26,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_PLUS_EQ,
+      anon_sym_EQ_PLUS,
+      anon_sym_EQ_PLUS_EQ,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [570] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(94), 26,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_PLUS_EQ,
+      anon_sym_EQ_PLUS,
+      anon_sym_EQ_PLUS_EQ,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [602] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(94), 26,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_PLUS_EQ,
+      anon_sym_EQ_PLUS,
+      anon_sym_EQ_PLUS_EQ,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [634] = 30,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(96), 1,
+      anon_sym_RBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(57), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(56), 1,
+      aux_sym_list_repeat1,
+  [725] = 30,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(98), 1,
+      anon_sym_RPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(60), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(59), 1,
+      aux_sym_group_repeat1,
+  [816] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(62), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [841] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(63), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [866] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(64), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [891] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(65), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [916] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(66), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [941] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(67), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [966] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(68), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [991] = 28,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(69), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+  [1076] = 28,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(70), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+  [1161] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(100), 26,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_PLUS_EQ,
+      anon_sym_EQ_PLUS,
+      anon_sym_EQ_PLUS_EQ,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [1193] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(100), 26,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_PLUS_EQ,
+      anon_sym_EQ_PLUS,
+      anon_sym_EQ_PLUS_EQ,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [1225] = 7,
+    ACTIONS(7), 1,
+      sym_ident,
+    ACTIONS(102), 1,
+      anon_sym_RBRACE,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(41), 1,
+      sym__item,
+    STATE(4), 1,
+      sym_field,
+    STATE(5), 1,
+      sym_section,
+    STATE(71), 1,
+      aux_sym_section_repeat1,
+  [1247] = 7,
+    ACTIONS(7), 1,
+      sym_ident,
+    ACTIONS(104), 1,
+      anon_sym_RBRACE,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(41), 1,
+      sym__item,
+    STATE(4), 1,
+      sym_field,
+    STATE(5), 1,
+      sym_section,
+    STATE(73), 1,
+      aux_sym_section_repeat1,
+  [1269] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(106), 2,
+      sym_ident,
+      anon_sym_RBRACE,
+  [1277] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(108), 3,
+      ts_builtin_sym_end,
+      sym_ident,
+      anon_sym_RBRACE,
+  [1286] = 30,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(110), 1,
+      anon_sym_RBRACE,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(76), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(75), 1,
+      aux_sym_option_repeat1,
+  [1377] = 28,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(78), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+  [1462] = 28,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(79), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+  [1547] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(80), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1572] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(81), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1597] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(82), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1622] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(83), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1647] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(84), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1672] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(85), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1697] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(86), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1722] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(87), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1747] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(88), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1772] = 8,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(89), 1,
+      sym__atom,
+    STATE(23), 1,
+      sym_bool,
+  [1797] = 30,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(112), 1,
+      anon_sym_RBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(57), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(90), 1,
+      aux_sym_list_repeat1,
+  [1888] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(114), 17,
+      sym_ident,
+      sym_string,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [1920] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(116), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [1949] = 30,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(118), 1,
+      anon_sym_RPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(60), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(92), 1,
+      aux_sym_group_repeat1,
+  [2040] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(120), 17,
+      sym_ident,
+      sym_string,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2072] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(122), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2101] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(124), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2130] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(124), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2159] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(124), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2188] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(124), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2217] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(124), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2246] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(124), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2275] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(124), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2304] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(126), 20,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2339] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(126), 20,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2374] = 7,
+    ACTIONS(7), 1,
+      sym_ident,
+    ACTIONS(128), 1,
+      anon_sym_RBRACE,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(41), 1,
+      sym__item,
+    STATE(4), 1,
+      sym_field,
+    STATE(5), 1,
+      sym_section,
+    STATE(73), 1,
+      aux_sym_section_repeat1,
+  [2396] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(130), 3,
+      ts_builtin_sym_end,
+      sym_ident,
+      anon_sym_RBRACE,
+  [2405] = 7,
+    ACTIONS(132), 1,
+      sym_ident,
+    ACTIONS(135), 1,
+      anon_sym_RBRACE,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(41), 1,
+      sym__item,
+    STATE(4), 1,
+      sym_field,
+    STATE(5), 1,
+      sym_section,
+    STATE(73), 1,
+      aux_sym_section_repeat1,
+  [2427] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(137), 3,
+      ts_builtin_sym_end,
+      sym_ident,
+      anon_sym_RBRACE,
+  [2436] = 30,
+    ACTIONS(28), 1,
+      sym_ident,
+    ACTIONS(30), 1,
+      sym_string,
+    ACTIONS(139), 1,
+      anon_sym_RBRACE,
+    ACTIONS(32), 1,
+      sym_int,
+    ACTIONS(34), 1,
+      anon_sym_LBRACK,
+    ACTIONS(36), 1,
+      anon_sym_LPAREN,
+    ACTIONS(38), 1,
+      anon_sym_EQ,
+    ACTIONS(40), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(42), 1,
+      anon_sym_LT,
+    ACTIONS(44), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(46), 1,
+      anon_sym_GT,
+    ACTIONS(48), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(50), 1,
+      anon_sym_TILDE,
+    ACTIONS(52), 1,
+      anon_sym_BANG,
+    ACTIONS(54), 1,
+      anon_sym_QMARK,
+    ACTIONS(56), 1,
+      anon_sym_true,
+    ACTIONS(58), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(76), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(95), 1,
+      aux_sym_option_repeat1,
+  [2527] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(141), 17,
+      sym_ident,
+      sym_string,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_LPAREN,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2559] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(143), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2588] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(145), 20,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2623] = 5,
+    ACTIONS(66), 1,
+      anon_sym_LBRACE,
+    ACTIONS(68), 1,
+      anon_sym_AMP,
+    ACTIONS(70), 1,
+      anon_sym_PIPE,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(145), 20,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2658] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(147), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2687] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(147), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2716] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(147), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2745] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(147), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2774] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(147), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2803] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(147), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2832] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(147), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2861] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(149), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2890] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(149), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2919] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(149), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [2948] = 30,
+    ACTIONS(151), 1,
+      sym_ident,
+    ACTIONS(154), 1,
+      sym_string,
+    ACTIONS(157), 1,
+      sym_int,
+    ACTIONS(160), 1,
+      anon_sym_LBRACK,
+    ACTIONS(163), 1,
+      anon_sym_RBRACK,
+    ACTIONS(165), 1,
+      anon_sym_LPAREN,
+    ACTIONS(168), 1,
+      anon_sym_EQ,
+    ACTIONS(171), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(174), 1,
+      anon_sym_LT,
+    ACTIONS(177), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(180), 1,
+      anon_sym_GT,
+    ACTIONS(183), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(186), 1,
+      anon_sym_TILDE,
+    ACTIONS(189), 1,
+      anon_sym_BANG,
+    ACTIONS(192), 1,
+      anon_sym_QMARK,
+    ACTIONS(195), 1,
+      anon_sym_true,
+    ACTIONS(198), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(57), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(90), 1,
+      aux_sym_list_repeat1,
+  [3039] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(201), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [3068] = 30,
+    ACTIONS(203), 1,
+      sym_ident,
+    ACTIONS(206), 1,
+      sym_string,
+    ACTIONS(209), 1,
+      sym_int,
+    ACTIONS(212), 1,
+      anon_sym_LBRACK,
+    ACTIONS(215), 1,
+      anon_sym_LPAREN,
+    ACTIONS(218), 1,
+      anon_sym_RPAREN,
+    ACTIONS(220), 1,
+      anon_sym_EQ,
+    ACTIONS(223), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(226), 1,
+      anon_sym_LT,
+    ACTIONS(229), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(232), 1,
+      anon_sym_GT,
+    ACTIONS(235), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(238), 1,
+      anon_sym_TILDE,
+    ACTIONS(241), 1,
+      anon_sym_BANG,
+    ACTIONS(244), 1,
+      anon_sym_QMARK,
+    ACTIONS(247), 1,
+      anon_sym_true,
+    ACTIONS(250), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(60), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(92), 1,
+      aux_sym_group_repeat1,
+  [3159] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(253), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+  [3188] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(255), 3,
+      ts_builtin_sym_end,
+      sym_ident,
+      anon_sym_RBRACE,
+  [3197] = 30,
+    ACTIONS(257), 1,
+      sym_ident,
+    ACTIONS(260), 1,
+      sym_string,
+    ACTIONS(263), 1,
+      anon_sym_RBRACE,
+    ACTIONS(265), 1,
+      sym_int,
+    ACTIONS(268), 1,
+      anon_sym_LBRACK,
+    ACTIONS(271), 1,
+      anon_sym_LPAREN,
+    ACTIONS(274), 1,
+      anon_sym_EQ,
+    ACTIONS(277), 1,
+      anon_sym_BANG_EQ,
+    ACTIONS(280), 1,
+      anon_sym_LT,
+    ACTIONS(283), 1,
+      anon_sym_LT_EQ,
+    ACTIONS(286), 1,
+      anon_sym_GT,
+    ACTIONS(289), 1,
+      anon_sym_GT_EQ,
+    ACTIONS(292), 1,
+      anon_sym_TILDE,
+    ACTIONS(295), 1,
+      anon_sym_BANG,
+    ACTIONS(298), 1,
+      anon_sym_QMARK,
+    ACTIONS(301), 1,
+      anon_sym_true,
+    ACTIONS(304), 1,
+      anon_sym_false,
+    ACTIONS(3), 1,
+      sym_comment,
+    STATE(76), 1,
+      sym__value,
+    STATE(13), 1,
+      sym__atom,
+    STATE(14), 1,
+      sym_list,
+    STATE(15), 1,
+      sym_group,
+    STATE(16), 1,
+      sym_option,
+    STATE(17), 1,
+      sym_logop,
+    STATE(18), 1,
+      sym_relop,
+    STATE(19), 1,
+      sym_prefix_relop,
+    STATE(20), 1,
+      sym_env_binding,
+    STATE(21), 1,
+      sym_pfxop,
+    STATE(23), 1,
+      sym_bool,
+    STATE(95), 1,
+      aux_sym_option_repeat1,
+  [3288] = 2,
+    ACTIONS(3), 1,
+      sym_comment,
+    ACTIONS(307), 23,
+      ts_builtin_sym_end,
+      sym_ident,
+      sym_string,
+      anon_sym_LBRACE,
+      anon_sym_RBRACE,
+      sym_int,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_LPAREN,
+      anon_sym_RPAREN,
+      anon_sym_AMP,
+      anon_sym_PIPE,
+      anon_sym_EQ,
+      anon_sym_BANG_EQ,
+      anon_sym_LT,
+      anon_sym_LT_EQ,
+      anon_sym_GT,
+      anon_sym_GT_EQ,
+      anon_sym_TILDE,
+      anon_sym_BANG,
+      anon_sym_QMARK,
+      anon_sym_true,
+      anon_sym_false,
+};
+
+static const uint32_t ts_small_parse_table_map[] = {
+  [SMALL_STATE(2)] = 0,
+  [SMALL_STATE(3)] = 22,
+  [SMALL_STATE(4)] = 30,
+  [SMALL_STATE(5)] = 39,
+  [SMALL_STATE(6)] = 48,
+  [SMALL_STATE(7)] = 61,
+  [SMALL_STATE(8)] = 68,
+  [SMALL_STATE(9)] = 90,
+  [SMALL_STATE(10)] = 175,
+  [SMALL_STATE(11)] = 182,
+  [SMALL_STATE(12)] = 204,
+  [SMALL_STATE(13)] = 222,
+  [SMALL_STATE(14)] = 274,
+  [SMALL_STATE(15)] = 303,
+  [SMALL_STATE(16)] = 332,
+  [SMALL_STATE(17)] = 361,
+  [SMALL_STATE(18)] = 390,
+  [SMALL_STATE(19)] = 419,
+  [SMALL_STATE(20)] = 448,
+  [SMALL_STATE(21)] = 477,
+  [SMALL_STATE(22)] = 506,
+  [SMALL_STATE(23)] = 538,
+  [SMALL_STATE(24)] = 570,
+  [SMALL_STATE(25)] = 602,
+  [SMALL_STATE(26)] = 634,
+  [SMALL_STATE(27)] = 725,
+  [SMALL_STATE(28)] = 816,
+  [SMALL_STATE(29)] = 841,
+  [SMALL_STATE(30)] = 866,
+  [SMALL_STATE(31)] = 891,
+  [SMALL_STATE(32)] = 916,
+  [SMALL_STATE(33)] = 941,
+  [SMALL_STATE(34)] = 966,
+  [SMALL_STATE(35)] = 991,
+  [SMALL_STATE(36)] = 1076,
+  [SMALL_STATE(37)] = 1161,
+  [SMALL_STATE(38)] = 1193,
+  [SMALL_STATE(39)] = 1225,
+  [SMALL_STATE(40)] = 1247,
+  [SMALL_STATE(41)] = 1269,
+  [SMALL_STATE(42)] = 1277,
+  [SMALL_STATE(43)] = 1286,
+  [SMALL_STATE(44)] = 1377,
+  [SMALL_STATE(45)] = 1462,
+  [SMALL_STATE(46)] = 1547,
+  [SMALL_STATE(47)] = 1572,
+  [SMALL_STATE(48)] = 1597,
+  [SMALL_STATE(49)] = 1622,
+  [SMALL_STATE(50)] = 1647,
+  [SMALL_STATE(51)] = 1672,
+  [SMALL_STATE(52)] = 1697,
+  [SMALL_STATE(53)] = 1722,
+  [SMALL_STATE(54)] = 1747,
+  [SMALL_STATE(55)] = 1772,
+  [SMALL_STATE(56)] = 1797,
+  [SMALL_STATE(57)] = 1888,
+  [SMALL_STATE(58)] = 1920,
+  [SMALL_STATE(59)] = 1949,
+  [SMALL_STATE(60)] = 2040,
+  [SMALL_STATE(61)] = 2072,
+  [SMALL_STATE(62)] = 2101,
+  [SMALL_STATE(63)] = 2130,
+  [SMALL_STATE(64)] = 2159,
+  [SMALL_STATE(65)] = 2188,
+  [SMALL_STATE(66)] = 2217,
+  [SMALL_STATE(67)] = 2246,
+  [SMALL_STATE(68)] = 2275,
+  [SMALL_STATE(69)] = 2304,
+  [SMALL_STATE(70)] = 2339,
+  [SMALL_STATE(71)] = 2374,
+  [SMALL_STATE(72)] = 2396,
+  [SMALL_STATE(73)] = 2405,
+  [SMALL_STATE(74)] = 2427,
+  [SMALL_STATE(75)] = 2436,
+  [SMALL_STATE(76)] = 2527,
+  [SMALL_STATE(77)] = 2559,
+  [SMALL_STATE(78)] = 2588,
+  [SMALL_STATE(79)] = 2623,
+  [SMALL_STATE(80)] = 2658,
+  [SMALL_STATE(81)] = 2687,
+  [SMALL_STATE(82)] = 2716,
+  [SMALL_STATE(83)] = 2745,
+  [SMALL_STATE(84)] = 2774,
+  [SMALL_STATE(85)] = 2803,
+  [SMALL_STATE(86)] = 2832,
+  [SMALL_STATE(87)] = 2861,
+  [SMALL_STATE(88)] = 2890,
+  [SMALL_STATE(89)] = 2919,
+  [SMALL_STATE(90)] = 2948,
+  [SMALL_STATE(91)] = 3039,
+  [SMALL_STATE(92)] = 3068,
+  [SMALL_STATE(93)] = 3159,
+  [SMALL_STATE(94)] = 3188,
+  [SMALL_STATE(95)] = 3197,
+  [SMALL_STATE(96)] = 3288,
+};
+
+static const TSParseActionEntry ts_parse_actions[] = {
+  [0] = {.entry = {.count = 0, .reusable = false}},
+  [1] = {.entry = {.count = 1, .reusable = false}}, RECOVER(),
+  [3] = {.entry = {.count = 1, .reusable = true}}, SHIFT_EXTRA(),
+  [5] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 0),
+  [7] = {.entry = {.count = 1, .reusable = true}}, SHIFT(6),
+  [9] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 1),
+  [11] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 1),
+  [13] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__item, 1),
+  [15] = {.entry = {.count = 1, .reusable = true}}, SHIFT(9),
+  [17] = {.entry = {.count = 1, .reusable = true}}, SHIFT(10),
+  [19] = {.entry = {.count = 1, .reusable = true}}, SHIFT(11),
+  [21] = {.entry = {.count = 1, .reusable = true}}, ACCEPT_INPUT(),
+  [23] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 2),
+  [25] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 2), SHIFT_REPEAT(6),
+  [28] = {.entry = {.count = 1, .reusable = true}}, SHIFT(22),
+  [30] = {.entry = {.count = 1, .reusable = true}}, SHIFT(25),
+  [32] = {.entry = {.count = 1, .reusable = true}}, SHIFT(24),
+  [34] = {.entry = {.count = 1, .reusable = true}}, SHIFT(26),
+  [36] = {.entry = {.count = 1, .reusable = true}}, SHIFT(27),
+  [38] = {.entry = {.count = 1, .reusable = true}}, SHIFT(28),
+  [40] = {.entry = {.count = 1, .reusable = true}}, SHIFT(29),
+  [42] = {.entry = {.count = 1, .reusable = true}}, SHIFT(30),
+  [44] = {.entry = {.count = 1, .reusable = true}}, SHIFT(31),
+  [46] = {.entry = {.count = 1, .reusable = true}}, SHIFT(32),
+  [48] = {.entry = {.count = 1, .reusable = true}}, SHIFT(33),
+  [50] = {.entry = {.count = 1, .reusable = true}}, SHIFT(34),
+  [52] = {.entry = {.count = 1, .reusable = true}}, SHIFT(35),
+  [54] = {.entry = {.count = 1, .reusable = true}}, SHIFT(36),
+  [56] = {.entry = {.count = 1, .reusable = true}}, SHIFT(37),
+  [58] = {.entry = {.count = 1, .reusable = true}}, SHIFT(38),
+  [60] = {.entry = {.count = 1, .reusable = true}}, SHIFT(39),
+  [62] = {.entry = {.count = 1, .reusable = true}}, SHIFT(42),
+  [64] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_field, 3, .production_id = 1),
+  [66] = {.entry = {.count = 1, .reusable = true}}, SHIFT(43),
+  [68] = {.entry = {.count = 1, .reusable = true}}, SHIFT(44),
+  [70] = {.entry = {.count = 1, .reusable = true}}, SHIFT(45),
+  [72] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__value, 1),
+  [74] = {.entry = {.count = 1, .reusable = true}}, SHIFT(46),
+  [76] = {.entry = {.count = 1, .reusable = true}}, SHIFT(47),
+  [78] = {.entry = {.count = 1, .reusable = true}}, SHIFT(48),
+  [80] = {.entry = {.count = 1, .reusable = true}}, SHIFT(49),
+  [82] = {.entry = {.count = 1, .reusable = true}}, SHIFT(50),
+  [84] = {.entry = {.count = 1, .reusable = true}}, SHIFT(51),
+  [86] = {.entry = {.count = 1, .reusable = true}}, SHIFT(52),
+  [88] = {.entry = {.count = 1, .reusable = true}}, SHIFT(53),
+  [90] = {.entry = {.count = 1, .reusable = true}}, SHIFT(54),
+  [92] = {.entry = {.count = 1, .reusable = true}}, SHIFT(55),
+  [94] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__atom, 1),
+  [96] = {.entry = {.count = 1, .reusable = true}}, SHIFT(58),
+  [98] = {.entry = {.count = 1, .reusable = true}}, SHIFT(61),
+  [100] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_bool, 1),
+  [102] = {.entry = {.count = 1, .reusable = true}}, SHIFT(72),
+  [104] = {.entry = {.count = 1, .reusable = true}}, SHIFT(74),
+  [106] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 1),
+  [108] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 3, .production_id = 3),
+  [110] = {.entry = {.count = 1, .reusable = true}}, SHIFT(77),
+  [112] = {.entry = {.count = 1, .reusable = true}}, SHIFT(91),
+  [114] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 1),
+  [116] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_list, 2),
+  [118] = {.entry = {.count = 1, .reusable = true}}, SHIFT(93),
+  [120] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 1),
+  [122] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_group, 2),
+  [124] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_prefix_relop, 2, .production_id = 6),
+  [126] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_pfxop, 2, .production_id = 6),
+  [128] = {.entry = {.count = 1, .reusable = true}}, SHIFT(94),
+  [130] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 4, .production_id = 2),
+  [132] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2), SHIFT_REPEAT(6),
+  [135] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2),
+  [137] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 4, .production_id = 3),
+  [139] = {.entry = {.count = 1, .reusable = true}}, SHIFT(96),
+  [141] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 1),
+  [143] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_option, 3, .production_id = 4),
+  [145] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_logop, 3, .production_id = 5),
+  [147] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_relop, 3, .production_id = 5),
+  [149] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_env_binding, 3, .production_id = 7),
+  [151] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(22),
+  [154] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(25),
+  [157] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(24),
+  [160] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(26),
+  [163] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2),
+  [165] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(27),
+  [168] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(28),
+  [171] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(29),
+  [174] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(30),
+  [177] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(31),
+  [180] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(32),
+  [183] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(33),
+  [186] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(34),
+  [189] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(35),
+  [192] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(36),
+  [195] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(37),
+  [198] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_list_repeat1, 2), SHIFT_REPEAT(38),
+  [201] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_list, 3),
+  [203] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(22),
+  [206] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(25),
+  [209] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(24),
+  [212] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(26),
+  [215] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(27),
+  [218] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2),
+  [220] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(28),
+  [223] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(29),
+  [226] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(30),
+  [229] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(31),
+  [232] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(32),
+  [235] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(33),
+  [238] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(34),
+  [241] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(35),
+  [244] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(36),
+  [247] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(37),
+  [250] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_group_repeat1, 2), SHIFT_REPEAT(38),
+  [253] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_group, 3),
+  [255] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 5, .production_id = 2),
+  [257] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(22),
+  [260] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(25),
+  [263] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2),
+  [265] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(24),
+  [268] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(26),
+  [271] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(27),
+  [274] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(28),
+  [277] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(29),
+  [280] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(30),
+  [283] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(31),
+  [286] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(32),
+  [289] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(33),
+  [292] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(34),
+  [295] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(35),
+  [298] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(36),
+  [301] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(37),
+  [304] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_option_repeat1, 2), SHIFT_REPEAT(38),
+  [307] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_option, 4, .production_id = 4),
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+void *tree_sitter_opam_external_scanner_create(void);
+void tree_sitter_opam_external_scanner_destroy(void *);
+bool tree_sitter_opam_external_scanner_scan(void *, TSLexer *, const bool *);
+unsigned tree_sitter_opam_external_scanner_serialize(void *, char *);
+void tree_sitter_opam_external_scanner_deserialize(void *, const char *, unsigned);
+
+#ifdef _WIN32
+#define extern __declspec(dllexport)
+#endif
+
+extern const TSLanguage *tree_sitter_opam(void) {
+  static const TSLanguage language = {
+    .version = LANGUAGE_VERSION,
+    .symbol_count = SYMBOL_COUNT,
+    .alias_count = ALIAS_COUNT,
+    .token_count = TOKEN_COUNT,
+    .external_token_count = EXTERNAL_TOKEN_COUNT,
+    .state_count = STATE_COUNT,
+    .large_state_count = LARGE_STATE_COUNT,
+    .production_id_count = PRODUCTION_ID_COUNT,
+    .field_count = FIELD_COUNT,
+    .max_alias_sequence_length = MAX_ALIAS_SEQUENCE_LENGTH,
+    .parse_table = &ts_parse_table[0][0],
+    .small_parse_table = ts_small_parse_table,
+    .small_parse_table_map = ts_small_parse_table_map,
+    .parse_actions = ts_parse_actions,
+    .symbol_names = ts_symbol_names,
+    .field_names = ts_field_names,
+    .field_map_slices = ts_field_map_slices,
+    .field_map_entries = ts_field_map_entries,
+    .symbol_metadata = ts_symbol_metadata,
+    .public_symbol_map = ts_symbol_map,
+    .alias_map = ts_non_terminal_alias_map,
+    .alias_sequences = &ts_alias_sequences[0][0],
+    .lex_modes = ts_lex_modes,
+    .lex_fn = ts_lex,
+    .external_scanner = {
+      &ts_external_scanner_states[0][0],
+      ts_external_scanner_symbol_map,
+      tree_sitter_opam_external_scanner_create,
+      tree_sitter_opam_external_scanner_destroy,
+      tree_sitter_opam_external_scanner_scan,
+      tree_sitter_opam_external_scanner_serialize,
+      tree_sitter_opam_external_scanner_deserialize,
+    },
+    .primary_state_ids = ts_primary_state_ids,
+  };
+  return &language;
+}
+#ifdef __cplusplus
+}
+#endif
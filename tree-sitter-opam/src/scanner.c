@@ -0,0 +1,55 @@
+#include <tree_sitter/parser.h>
+
+// Comments are scanned here, as `(* *)` comments nest.
+enum TokenType {
+  COMMENT,
+};
+
+void *tree_sitter_opam_external_scanner_create(void) { return NULL; }
+void tree_sitter_opam_external_scanner_destroy(void *payload) {}
+unsigned tree_sitter_opam_external_scanner_serialize(void *payload, char *buffer) { return 0; }
+void tree_sitter_opam_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {}
+
+static void advance(TSLexer *lexer) { lexer->advance(lexer, false); }
+
+static bool is_space(int32_t c) { return c == ' ' || c == '\t' || c == '\r' || c == '\n'; }
+
+bool tree_sitter_opam_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+  if (!valid_symbols[COMMENT]) return false;
+  while (is_space(lexer->lookahead)) lexer->advance(lexer, true);
+
+  // `#` comments run to the end of the line.
+  if (lexer->lookahead == '#') {
+    while (lexer->lookahead != '\n' && !lexer->eof(lexer)) advance(lexer);
+    lexer->result_symbol = COMMENT;
+    return true;
+  }
+
+  // `(*` opens a comment, closed by the matching `*)`, like in `lexer::Token::COMMENT`.
+  if (lexer->lookahead != '(') return false;
+  advance(lexer);
+  if (lexer->lookahead != '*') return false;
+  advance(lexer);
+  unsigned depth = 1;
+  while (!lexer->eof(lexer)) {
+    if (lexer->lookahead == '(') {
+      advance(lexer);
+      if (lexer->lookahead == '*') {
+        advance(lexer);
+        depth++;
+      }
+    } else if (lexer->lookahead == '*') {
+      advance(lexer);
+      if (lexer->lookahead == ')') {
+        advance(lexer);
+        if (--depth == 0) {
+          lexer->result_symbol = COMMENT;
+          return true;
+        }
+      }
+    } else {
+      advance(lexer);
+    }
+  }
+  return false;
+}
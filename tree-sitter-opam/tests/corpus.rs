@@ -0,0 +1,192 @@
+//! Checks the examples of `test/corpus`: each must parse to the expected tree, `parser::parse`
+//! must give the same tree for it, and the tokens of the tree must be those of `lexer::lex`.
+use std::fs;
+use std::ops::Range;
+
+use opam_file_format::lexer;
+use opam_file_format::parser::{self, Item, Value};
+use tree_sitter::{Node, Parser, Query, Tree};
+
+struct Example {
+    name: String,
+    input: String,
+    expected: String,
+}
+
+// Reads the examples, in the format of `tree-sitter test`: a name between `===` lines, the
+// input, a `---` line and the expected tree.
+fn corpus() -> Vec<Example> {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/test/corpus");
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    let mut examples = vec![];
+    for path in paths {
+        let text = fs::read_to_string(&path).unwrap();
+        let mut lines = text.lines().peekable();
+        while let Some(line) = lines.next() {
+            if !is_rule(line, '=') {
+                continue;
+            }
+            let name = lines.next().unwrap();
+            lines.next();
+            let input: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !is_rule(line, '-'))
+                .collect();
+            let mut expected = vec![];
+            while let Some(line) = lines.next_if(|line| !is_rule(line, '=')) {
+                expected.push(line);
+            }
+            examples.push(Example {
+                name: format!("{}: {}", path.file_name().unwrap().to_string_lossy(), name),
+                input: input.join("\n").trim_matches('\n').to_string(),
+                expected: normalize(&expected.join(" ")),
+            });
+        }
+    }
+    examples
+}
+
+fn is_rule(line: &str, c: char) -> bool {
+    line.len() >= 3 && line.chars().all(|x| x == c)
+}
+
+fn normalize(sexp: &str) -> String {
+    sexp.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" )", ")")
+}
+
+fn parse(input: &str) -> Tree {
+    let mut parser = Parser::new();
+    parser.set_language(tree_sitter_opam::language()).unwrap();
+    parser.parse(input, None).unwrap()
+}
+
+#[test]
+fn corpus_parses_to_expected_trees() {
+    for example in corpus() {
+        let tree = parse(&example.input);
+        assert_eq!(
+            tree.root_node().to_sexp(),
+            example.expected,
+            "{}",
+            example.name
+        );
+    }
+}
+
+#[test]
+fn corpus_trees_match_parser() {
+    for example in corpus() {
+        let tokens = lexer::lex(&example.input).unwrap();
+        // Unlike `parse`, this keeps every section of a kind, as the tree does.
+        let (ast, _) = parser::parse_with_spans(tokens.into_iter())
+            .unwrap_or_else(|e| panic!("{}: {:?}", example.name, e));
+        let items: String = ast.items.values().map(|item| item_sexp(item)).collect();
+        // The AST has no comments.
+        let expected = normalize(&example.expected.replace("(comment)", ""));
+        assert_eq!(
+            format!("(source_file{})", items),
+            expected,
+            "{}",
+            example.name
+        );
+    }
+}
+
+#[test]
+fn corpus_tokens_match_lexer() {
+    for example in corpus() {
+        let tree = parse(&example.input);
+        let mut leaves = vec![];
+        collect_leaves(tree.root_node(), &mut leaves);
+        let mut tokens = vec![];
+        for leaf in leaves {
+            let text = &example.input[leaf.byte_range()];
+            let lexed = lexer::lex(text).unwrap_or_else(|_| panic!("{}: {:?}", example.name, text));
+            if leaf.kind() == "comment" {
+                assert!(lexed.is_empty(), "{}: {:?}", example.name, text);
+                continue;
+            }
+            for (token, _) in lexed {
+                tokens.push((format!("{:?}", token), leaf.byte_range()));
+            }
+        }
+        let expected: Vec<(String, Range<usize>)> = lexer::lex(&example.input)
+            .unwrap()
+            .into_iter()
+            .map(|(token, span)| (format!("{:?}", token), span.start..span.end))
+            .collect();
+        assert_eq!(tokens, expected, "{}", example.name);
+    }
+}
+
+#[test]
+fn highlights_query_is_valid() {
+    Query::new(
+        tree_sitter_opam::language(),
+        tree_sitter_opam::HIGHLIGHTS_QUERY,
+    )
+    .unwrap();
+}
+
+fn collect_leaves<'a>(node: Node<'a>, leaves: &mut Vec<Node<'a>>) {
+    if node.child_count() == 0 {
+        leaves.push(node);
+    }
+    for i in 0..node.child_count() {
+        collect_leaves(node.child(i).unwrap(), leaves);
+    }
+}
+
+// The tree-sitter tree of a parsed item, without its tokens.
+fn item_sexp(item: &Item) -> String {
+    match item {
+        Item::Variable(value) => format!(" (field name: (ident) value: {})", value_sexp(value)),
+        Item::Section { name, items } => {
+            let name = if name.is_some() {
+                " name: (string)"
+            } else {
+                ""
+            };
+            let items: String = items.values().map(|item| item_sexp(item)).collect();
+            format!(" (section kind: (ident){}{})", name, items)
+        }
+    }
+}
+
+fn value_sexp(value: &Value) -> String {
+    let values = |values: &[Box<Value>]| -> String {
+        values
+            .iter()
+            .map(|value| format!(" {}", value_sexp(value)))
+            .collect()
+    };
+    match value {
+        Value::Bool(_) => "(bool)".to_string(),
+        Value::Int(_) => "(int)".to_string(),
+        Value::String(_) => "(string)".to_string(),
+        Value::Ident(_) => "(ident)".to_string(),
+        Value::List(vs) => format!("(list{})", values(vs)),
+        Value::Group(vs) => format!("(group{})", values(vs)),
+        Value::Option(v, vs) => format!("(option value: {}{})", value_sexp(v), values(vs)),
+        Value::Logop(_, l, r) => {
+            format!("(logop left: {} right: {})", value_sexp(l), value_sexp(r))
+        }
+        Value::Relop(_, l, r) => {
+            format!("(relop left: {} right: {})", value_sexp(l), value_sexp(r))
+        }
+        Value::PrefixRelop(_, v) => format!("(prefix_relop operand: {})", value_sexp(v)),
+        Value::Pfxop(_, v) => format!("(pfxop operand: {})", value_sexp(v)),
+        Value::EnvBinding(l, _, r) => format!(
+            "(env_binding variable: {} value: {})",
+            value_sexp(l),
+            value_sexp(r)
+        ),
+    }
+}